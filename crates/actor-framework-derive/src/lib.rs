@@ -0,0 +1,250 @@
+//! # ActorEntity Derive
+//!
+//! Provides `#[derive(ActorEntity)]`, which generates the [`ActorEntity`] boilerplate for plain
+//! CRUD entities: the seven associated types, a field-by-field `from_create_params`, an `id`
+//! accessor, and a field-by-field `on_update`. Enable it through the `derive` feature of `actor-framework`,
+//! which re-exports the macro next to the trait.
+//!
+//! ## Attributes
+//!
+//! On the struct, `#[actor(...)]` names the associated types:
+//!
+//! | Key | Required | Default |
+//! |-----|----------|---------|
+//! | `create` | yes | |
+//! | `update` | yes | |
+//! | `error` | yes | |
+//! | `action` | no | `()` |
+//! | `action_result` | no | `()` |
+//! | `context` | no | `()` |
+//!
+//! On fields:
+//!
+//! - `#[actor(id)]` - exactly one field; its type becomes `Id`, it receives the generated id,
+//!   and `id()` returns it.
+//! - `#[actor(skip_create)]` - not read from the Create DTO; initialized with `Default::default()`.
+//! - `#[actor(skip_update)]` - not present in the Update DTO.
+//!
+//! Every other field is copied from the Create DTO field of the same name, and updated from the
+//! `Option` field of the same name in the Update DTO when it is `Some`.
+//!
+//! ## Actions
+//!
+//! Actions remain manual. When `action` is given, the generated `handle_action` delegates to an
+//! inherent method you write:
+//!
+//! ```rust,ignore
+//! impl Product {
+//!     async fn on_action(&mut self, action: ProductAction, ctx: &()) -> Result<ProductActionResult, ProductError> { ... }
+//! }
+//! ```
+//!
+//! Without `action`, the entity has no custom actions (`Action = ()`).
+//!
+//! [`ActorEntity`]: https://schilit.github.io/actor-framework-recipe/actor_framework/entity/trait.ActorEntity.html
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, Type};
+
+/// Derives `actor_framework::ActorEntity` for a struct with named fields.
+///
+/// See the [crate-level documentation](crate) for the supported attributes.
+#[proc_macro_derive(ActorEntity, attributes(actor))]
+pub fn derive_actor_entity(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Associated types named by the struct-level `#[actor(...)]` attribute.
+#[derive(Default)]
+struct EntityTypes {
+    create: Option<Type>,
+    update: Option<Type>,
+    error: Option<Type>,
+    action: Option<Type>,
+    action_result: Option<Type>,
+    context: Option<Type>,
+}
+
+/// Flags set by a field-level `#[actor(...)]` attribute.
+#[derive(Default)]
+struct FieldFlags {
+    id: bool,
+    skip_create: bool,
+    skip_update: bool,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "ActorEntity cannot be derived for generic types",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "ActorEntity can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "ActorEntity can only be derived for structs",
+            ))
+        }
+    };
+
+    let types = parse_entity_types(&input)?;
+    let create = required(types.create, name, "create")?;
+    let update = required(types.update, name, "update")?;
+    let error = required(types.error, name, "error")?;
+    let unit: Type = syn::parse_quote!(());
+    let action_result = types.action_result.unwrap_or_else(|| unit.clone());
+    let context = types.context.unwrap_or_else(|| unit.clone());
+
+    let mut id: Option<(&Ident, &Type)> = None;
+    let mut create_inits = Vec::new();
+    let mut update_stmts = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field");
+        let flags = parse_field_flags(field)?;
+        if flags.id {
+            if id.is_some() {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "only one field may be marked #[actor(id)]",
+                ));
+            }
+            id = Some((ident, &field.ty));
+            create_inits.push(quote! { #ident: id });
+            continue;
+        }
+        if flags.skip_create {
+            create_inits.push(quote! { #ident: ::core::default::Default::default() });
+        } else {
+            create_inits.push(quote! { #ident: params.#ident });
+        }
+        if !flags.skip_update {
+            update_stmts.push(quote! {
+                if let ::core::option::Option::Some(value) = update.#ident {
+                    self.#ident = value;
+                }
+            });
+        }
+    }
+    let (id_ident, id_ty) =
+        id.ok_or_else(|| syn::Error::new_spanned(name, "one field must be marked #[actor(id)]"))?;
+
+    let (action, handle_action_body) = match types.action {
+        Some(action) => (action, quote! { self.on_action(action, ctx).await }),
+        None => (unit, quote! { ::core::result::Result::Ok(()) }),
+    };
+
+    Ok(quote! {
+        #[::actor_framework::__private::async_trait]
+        impl ::actor_framework::ActorEntity for #name {
+            type Id = #id_ty;
+            type Create = #create;
+            type Update = #update;
+            type Action = #action;
+            type ActionResult = #action_result;
+            type Context = #context;
+            type Error = #error;
+
+            fn from_create_params(
+                id: Self::Id,
+                params: Self::Create,
+            ) -> ::core::result::Result<Self, Self::Error> {
+                ::core::result::Result::Ok(Self { #(#create_inits),* })
+            }
+
+            fn id(&self) -> &Self::Id {
+                &self.#id_ident
+            }
+
+            #[allow(unused_variables)]
+            async fn on_update(
+                &mut self,
+                update: Self::Update,
+                _ctx: &Self::Context,
+            ) -> ::core::result::Result<bool, Self::Error> {
+                #(#update_stmts)*
+                ::core::result::Result::Ok(true)
+            }
+
+            #[allow(unused_variables)]
+            async fn handle_action(
+                &mut self,
+                action: Self::Action,
+                ctx: &Self::Context,
+            ) -> ::core::result::Result<Self::ActionResult, Self::Error> {
+                #handle_action_body
+            }
+        }
+    })
+}
+
+fn required(ty: Option<Type>, name: &Ident, key: &str) -> syn::Result<Type> {
+    ty.ok_or_else(|| {
+        syn::Error::new_spanned(
+            name,
+            format!("missing `{key} = ...` in #[actor(...)] on the struct"),
+        )
+    })
+}
+
+fn parse_entity_types(input: &DeriveInput) -> syn::Result<EntityTypes> {
+    let mut types = EntityTypes::default();
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("actor")) {
+        attr.parse_nested_meta(|meta| {
+            let slot = if meta.path.is_ident("create") {
+                &mut types.create
+            } else if meta.path.is_ident("update") {
+                &mut types.update
+            } else if meta.path.is_ident("error") {
+                &mut types.error
+            } else if meta.path.is_ident("action") {
+                &mut types.action
+            } else if meta.path.is_ident("action_result") {
+                &mut types.action_result
+            } else if meta.path.is_ident("context") {
+                &mut types.context
+            } else {
+                return Err(meta.error("unknown #[actor(...)] key"));
+            };
+            *slot = Some(meta.value()?.parse()?);
+            Ok(())
+        })?;
+    }
+    Ok(types)
+}
+
+fn parse_field_flags(field: &syn::Field) -> syn::Result<FieldFlags> {
+    let mut flags = FieldFlags::default();
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("actor")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("id") {
+                flags.id = true;
+            } else if meta.path.is_ident("skip_create") {
+                flags.skip_create = true;
+            } else if meta.path.is_ident("skip_update") {
+                flags.skip_update = true;
+            } else {
+                return Err(meta.error("unknown #[actor(...)] field flag"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(flags)
+}
@@ -0,0 +1,108 @@
+use actor_framework::{ActorEntity, ResourceActor};
+
+#[derive(Debug, thiserror::Error)]
+#[error("Widget error")]
+struct WidgetError;
+
+#[derive(Debug)]
+struct WidgetCreate {
+    name: String,
+    price: f64,
+}
+
+#[derive(Debug)]
+struct WidgetUpdate {
+    price: Option<f64>,
+}
+
+#[derive(Clone, Debug, ActorEntity)]
+#[actor(create = WidgetCreate, update = WidgetUpdate, error = WidgetError)]
+struct Widget {
+    #[actor(id)]
+    id: u32,
+    #[actor(skip_update)]
+    name: String,
+    price: f64,
+    #[actor(skip_create, skip_update)]
+    views: u32,
+}
+
+#[derive(Debug)]
+enum CounterAction {
+    Increment,
+}
+
+#[derive(Debug)]
+struct CounterCreate {}
+
+#[derive(Debug)]
+struct CounterUpdate {}
+
+#[derive(Clone, Debug, ActorEntity)]
+#[actor(
+    create = CounterCreate,
+    update = CounterUpdate,
+    error = WidgetError,
+    action = CounterAction,
+    action_result = u32
+)]
+struct Counter {
+    #[actor(id)]
+    id: u32,
+    #[actor(skip_create, skip_update)]
+    value: u32,
+}
+
+impl Counter {
+    async fn on_action(&mut self, action: CounterAction, _ctx: &()) -> Result<u32, WidgetError> {
+        match action {
+            CounterAction::Increment => {
+                self.value += 1;
+                Ok(self.value)
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_derived_create_and_update() {
+    let (actor, client) = ResourceActor::<Widget>::new(10);
+    tokio::spawn(actor.run(()));
+
+    let id = client
+        .create(WidgetCreate {
+            name: "Gear".into(),
+            price: 2.5,
+        })
+        .await
+        .unwrap();
+    let widget = client.get(id).await.unwrap().unwrap();
+    assert_eq!(widget.id, id);
+    assert_eq!(widget.name, "Gear");
+    assert_eq!(widget.views, 0);
+
+    let widget = client
+        .update(id, WidgetUpdate { price: Some(3.0) })
+        .await
+        .unwrap();
+    assert_eq!(widget.price, 3.0);
+    assert_eq!(widget.name, "Gear");
+}
+
+#[tokio::test]
+async fn test_derived_action_delegates_to_inherent_method() {
+    let (actor, client) = ResourceActor::<Counter>::new(10);
+    tokio::spawn(actor.run(()));
+
+    let id = client.create(CounterCreate {}).await.unwrap();
+    assert_eq!(
+        client
+            .perform_action(id, CounterAction::Increment)
+            .await
+            .unwrap(),
+        1
+    );
+    let counter = client.get(id).await.unwrap().unwrap();
+    assert_eq!(counter.id, id);
+    assert_eq!(counter.value, 1);
+}
@@ -0,0 +1,159 @@
+//! Throughput and latency of a `ResourceActor` serving a trivial entity.
+//!
+//! Run with `cargo bench -p actor-framework`. Each benchmark sends a fixed workload through a
+//! `ResourceClient` and reports ops/sec (criterion's `elem/s`); `get_latency` also prints the
+//! p99 of single `get` round-trips. The entity's hooks do nothing, so the numbers measure the
+//! framework itself: channel hops, request dispatch, logging and spans.
+
+use actor_framework::{ActorEntity, ResourceActor, ResourceClient};
+use async_trait::async_trait;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+
+/// Requests per iteration of the throughput benchmarks.
+const WORKLOAD: u64 = 1_000;
+/// Concurrent clients in `concurrent_get`.
+const CLIENTS: u64 = 8;
+
+#[derive(Clone, Debug)]
+struct Counter {
+    id: u32,
+    value: u64,
+}
+
+#[async_trait]
+impl ActorEntity for Counter {
+    type Id = u32;
+    type Create = u64;
+    type Update = u64;
+    type Action = ();
+    type ActionResult = ();
+    type Context = ();
+    type Error = std::convert::Infallible;
+
+    fn from_create_params(id: u32, value: u64) -> Result<Self, Self::Error> {
+        Ok(Self { id, value })
+    }
+
+    fn id(&self) -> &u32 {
+        &self.id
+    }
+
+    async fn on_update(&mut self, value: u64, _ctx: &()) -> Result<bool, Self::Error> {
+        self.value = value;
+        Ok(true)
+    }
+
+    async fn handle_action(&mut self, _action: (), _ctx: &()) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+fn runtime() -> Runtime {
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+}
+
+/// Spawns a quiet actor on `runtime` holding one entity, returning its client and the id.
+fn spawn_actor(runtime: &Runtime) -> (ResourceClient<Counter>, u32) {
+    runtime.block_on(async {
+        let (actor, client) = ResourceActor::<Counter>::new(1024);
+        tokio::spawn(actor.quiet().run(()));
+        let id = client.create(0).await.unwrap();
+        (client, id)
+    })
+}
+
+fn create(c: &mut Criterion) {
+    let runtime = runtime();
+    let mut group = c.benchmark_group("actor");
+    group.throughput(Throughput::Elements(WORKLOAD));
+    // A fresh actor per iteration, so the store does not grow across samples
+    group.bench_function("create", |b| {
+        b.to_async(&runtime).iter_batched(
+            || {
+                let (actor, client) = ResourceActor::<Counter>::new(1024);
+                (actor.quiet(), client)
+            },
+            |(actor, client)| async move {
+                tokio::spawn(actor.run(()));
+                for value in 0..WORKLOAD {
+                    client.create(value).await.unwrap();
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn get(c: &mut Criterion) {
+    let runtime = runtime();
+    let (client, id) = spawn_actor(&runtime);
+    let mut group = c.benchmark_group("actor");
+    group.throughput(Throughput::Elements(WORKLOAD));
+    group.bench_function("get", |b| {
+        b.to_async(&runtime).iter(|| async {
+            for _ in 0..WORKLOAD {
+                client.get(id).await.unwrap();
+            }
+        })
+    });
+    group.throughput(Throughput::Elements(WORKLOAD * CLIENTS));
+    group.bench_function("concurrent_get", |b| {
+        b.to_async(&runtime).iter(|| async {
+            let tasks: Vec<_> = (0..CLIENTS)
+                .map(|_| {
+                    let client = client.clone();
+                    tokio::spawn(async move {
+                        for _ in 0..WORKLOAD {
+                            client.get(id).await.unwrap();
+                        }
+                    })
+                })
+                .collect();
+            for task in tasks {
+                task.await.unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+fn get_latency(c: &mut Criterion) {
+    let runtime = runtime();
+    let (client, id) = spawn_actor(&runtime);
+    let latencies = RefCell::new(Vec::new());
+    c.bench_function("actor/get_latency", |b| {
+        b.to_async(&runtime).iter_custom(|iters| {
+            let client = client.clone();
+            let latencies = &latencies;
+            async move {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    let started = Instant::now();
+                    client.get(id).await.unwrap();
+                    let elapsed = started.elapsed();
+                    latencies.borrow_mut().push(elapsed);
+                    total += elapsed;
+                }
+                total
+            }
+        })
+    });
+    let mut latencies = latencies.into_inner();
+    latencies.sort_unstable();
+    if let Some(p99) = latencies.get(latencies.len() * 99 / 100) {
+        println!(
+            "actor/get_latency p99: {p99:?} over {} requests",
+            latencies.len()
+        );
+    }
+}
+
+criterion_group!(benches, create, get, get_latency);
+criterion_main!(benches);
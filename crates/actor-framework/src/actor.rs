@@ -4,13 +4,42 @@
 //! and state of entities. It implements the "Server" side of the Actor Model, processing
 //! messages sequentially and ensuring exclusive access to the entity store.
 
+use crate::audit::{self, AuditEntry};
 use crate::client::ResourceClient;
-use crate::entity::ActorEntity;
-use crate::error::FrameworkError;
-use crate::message::ResourceRequest;
-use std::collections::HashMap;
+use crate::clock::{HasClock, SystemClock};
+use crate::entity::{ActorEntity, StopReason};
+use crate::error::{FrameworkError, HookPanic};
+use crate::event::ResourceEvent;
+use crate::eviction::{CapacityLimit, EvictionPolicy};
+use crate::message::{RequestKind, ResourceRequest, Response};
+use crate::priority::PriorityClient;
+use crate::request_context;
+use crate::schedule::DeadlineQueue;
+use crate::stats::ActorStats;
+use crate::validate::ValidationError;
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime};
 use tokio::sync::mpsc;
-use tracing::{debug, info, warn};
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, MissedTickBehavior};
+use tracing::{debug, error, field, info, info_span, trace, warn, Instrument};
+
+/// Logs a per-operation success event at `info`, or at `trace` when the actor is quiet.
+macro_rules! op_info {
+    ($quiet:expr, $($arg:tt)+) => {
+        if $quiet {
+            trace!($($arg)+)
+        } else {
+            info!($($arg)+)
+        }
+    };
+}
 
 /// The generic actor that manages a collection of entities.
 ///
@@ -23,6 +52,21 @@ use tracing::{debug, info, warn};
 /// processes its own messages *sequentially* in a loop. This means we don't need
 /// `Mutex` or `RwLock` for the `store`! The "Actor Model" gives us safety through
 /// exclusive ownership of state within the task.
+///
+/// **Ordering Guarantee**:
+/// Requests are handled one at a time in the order they arrive on the channel, and each
+/// handler (including its `await`ed hooks) completes before the next request is read.
+/// Requests sent concurrently from different tasks are therefore *serialized*, though their
+/// relative order is decided by which send reaches the channel first. Requests sent
+/// sequentially from a single task are processed in that order. This is what makes
+/// check-then-act logic inside hooks (e.g. "reserve stock if enough is available") safe.
+///
+/// The exception is an actor built
+/// [`with_deadline_scheduling`](ResourceActor::with_deadline_scheduling): it reorders the
+/// requests in its read-ahead window by deadline, so a request sent with a nearer deadline
+/// (see [`ResourceClient::with_deadline`]) may be handled before one sent earlier, even from
+/// the same task. Requests are still handled one at a time.
+///
 /// ## ResourceActor
 ///
 /// The `ResourceActor<T>` struct is the *server* side of the framework. It owns the in‑memory store for a given entity type `T: ActorEntity` and processes all incoming `ResourceRequest<T>` messages sequentially. Each actor runs in its own Tokio task, guaranteeing exclusive access to its state without any locking.
@@ -67,7 +111,8 @@ use tracing::{debug, info, warn};
 ///     type Error = MyError;
 ///
 ///     fn from_create_params(id: u32, _: MyCreate) -> Result<Self, Self::Error> { Ok(Self { id }) }
-///     async fn on_update(&mut self, _: MyUpdate, _: &()) -> Result<(), Self::Error> { Ok(()) }
+///     fn id(&self) -> &u32 { &self.id }
+///     async fn on_update(&mut self, _: MyUpdate, _: &()) -> Result<bool, Self::Error> { Ok(true) }
 ///     async fn handle_action(&mut self, _: MyAction, _: &()) -> Result<(), Self::Error> { Ok(()) }
 /// }
 ///
@@ -86,43 +131,232 @@ use tracing::{debug, info, warn};
 ///
 /// # Implementation Details
 ///
-/// The actor maintains an internal `HashMap` (`store`) mapping IDs to entities and a `u32` counter (`next_id`) for ID generation.
+/// The actor maintains an internal `HashMap` (`store`) mapping IDs to entities and an ID generator (`next_id`).
+/// By default the generator is a `u32` counter starting at 1; use [`ResourceActor::with_id_generator`] to supply your own.
+/// Once the counter has handed out `u32::MAX`, every further create fails with `IdExhausted`
+/// instead of wrapping around and reusing an ID.
 ///
 /// ## Operations
 ///
 /// * **Create**:
-///     1. Generates a new ID using the internal `next_id` counter (incrementing it).
+///     1. Generates a new ID by calling the `next_id` generator, failing with `IdExhausted` if
+///        it has none left.
 ///     2. Converts the `u32` ID to `T::Id`.
 ///     3. Calls `T::from_create_params` to instantiate the entity.
 ///     4. Calls the `on_create` lifecycle hook.
 ///     5. Inserts the new entity into the `store`.
 ///     6. Returns the new ID.
 ///
+/// * **CreateReturning**:
+///     1. Same as **Create**, but returns a clone of the stored entity instead of its ID.
+///
+/// * **CreateCancelSafe**:
+///     1. Skipped if the caller is already gone.
+///     2. Otherwise same as **Create**. If the caller gave up while it ran, so the reply cannot
+///        be delivered, calls the `on_delete` hook and removes the new entity again.
+///
 /// * **Get**:
 ///     1. Looks up the entity in the `store` by ID.
 ///     2. Returns a clone of the entity if found, or `None`.
+///     3. `GetProjection` returns the entity's projection instead of a clone (see
+///        [`Project`](crate::entity::Project)).
+///     4. `GetMap` looks up several IDs at once and returns the entities found, keyed by ID.
+///     5. A soft-deleted tombstone (see [`ActorEntity::is_deleted`]) is treated as absent,
+///        except by `GetIncludingDeleted`.
 ///
 /// * **Update**:
-///     1. Looks up the entity in the `store` (mutable access).
-///     2. Calls the `on_update` lifecycle hook with the update DTO.
+///     1. Looks up the entity in the `store` (mutable access) and clones it as `prev`. A
+///        conditional update whose [`Expected`](crate::Expected) snapshot no longer matches
+///        fails with `VersionConflict` here.
+///     2. Calls the `on_update_with_prev` lifecycle hook (which defaults to `on_update`) with the update DTO.
 ///     3. The entity modifies its own state within the hook.
-///     4. Returns the updated entity state.
+///     4. If the hook fails, the entity is rolled back to `prev`.
+///     5. Logs "Updated", or a `debug` no-op if the hook reports nothing changed.
+///     6. Returns the updated entity state.
+///
+/// * **Replace**:
+///     1. Looks up the entity in the `store`, returning `NotFound` if absent.
+///     2. Fails with `Validation` if the supplied entity's [`ActorEntity::id`] is not `id`.
+///     3. Calls [`ActorEntity::validate`] on the supplied entity; an invalid one fails with
+///        the error it converts to and leaves the stored value unchanged.
+///     4. Overwrites the stored value with the supplied entity and calls the `on_replace`
+///        hook with the previous value, restoring it if the hook fails.
+///     5. Returns the new entity state.
+///
+/// * **PatchJson** (`json-patch` feature only):
+///     1. Looks up the entity in the `store`, returning `NotFound` if absent.
+///     2. Merges the JSON patch into a copy and calls [`ActorEntity::validate`] on it (no hooks
+///        are called); a patch that does not yield a valid entity fails with `EntityError`.
+///     3. Fails with `Validation` if the patched entity's [`ActorEntity::id`] is not `id`.
+///     4. Stores the patched entity and calls the `on_replace` hook, like `Replace`.
+///     5. Returns the patched entity.
 ///
 /// * **Delete**:
 ///     1. Looks up the entity in the `store`.
 ///     2. Calls the `on_delete` lifecycle hook.
-///     3. Removes the entity from the `store`.
+///     3. Removes the entity from the `store`, unless [`ActorEntity::mark_deleted`] turns it
+///        into a tombstone. Every other request then treats a tombstone as not found, and
+///        `List`, `FindWhere` and `Count` skip it.
+///     4. `DeleteReturning` does the same and returns the removed entity (or the tombstone).
 ///
 /// * **Action**:
 ///     1. Looks up the entity in the `store` (mutable access).
 ///     2. Calls the `handle_action` hook with the custom action enum.
 ///     3. Returns the result of the action.
+///     4. `ActionReturning` does the same and also returns a clone of the entity after the
+///        action ran.
+///
+/// * **NotifyAction**:
+///     1. Runs **Action** but sends no reply; a failure is only logged.
+///
+/// * **ActionMany**:
+///     1. Runs **Action** for each `(id, action)` pair in order, within a single turn.
+///     2. Returns one result per pair; a failure does not stop or undo the others.
+///
+/// * **ActionAtomic**:
+///     1. Runs **Action** for each `(id, action)` pair in order, within a single turn,
+///        snapshotting each entity before its first action.
+///     2. If an action fails, restores every snapshot and returns that error: either all
+///        actions apply or none do.
+///
+/// * **List**:
+///     1. Clones every entity in the `store` (in no particular order).
+///
+/// * **ListPaginated**:
+///     1. Clones at most `limit` entities, skipping the first `offset` in the store's iteration
+///        order. The order is stable only while the store is not modified.
+///
+/// * **FindWhere**:
+///     1. Clones the entities for which the [`Predicate`](crate::Predicate) returns `true`.
+///
+/// * **Count**:
+///     1. Returns the number of entities in the `store`.
+///
+/// * **Batch limit**:
+///     1. With [`ResourceActor::with_max_batch`], an `ActionMany`, `ActionAtomic` or
+///        `Pipeline` larger than the limit is rejected whole with `BatchTooLarge` before any
+///        item runs.
+///
+/// * **Pipeline**:
+///     1. Runs each contained request in order, as if it had arrived on the channel.
+///     2. No other request or expiry tick is handled until the whole pipeline has run.
+///     3. Each request replies on its own `respond_to`; a failure does not stop the rest.
+///
+/// * **WithContext**:
+///     1. Handles the wrapped request with its [`RequestContext`](crate::RequestContext) readable
+///        through [`request_context::current`] by every hook that runs for it.
+///
+/// * **WithDeadline**:
+///     1. Handles the wrapped request. With [`ResourceActor::with_deadline_scheduling`] it is
+///        handled ahead of queued requests with a later or no deadline.
+///
+/// * **NotifyCount**:
+///     1. Replies `Ok(())` at once if the `store` holds `target` live entities.
+///     2. Otherwise registers the waiter; after each later request or expiry tick that leaves
+///        exactly `target` live entities, every waiter for that count is answered.
+///
+/// * **Ping**:
+///     1. Replies `Ok(())` without touching the `store`.
+///
+/// * **Stats**:
+///     1. Returns the actor's [`ActorStats`]: messages handled and idle vs busy time.
+///
+/// * **Pause**:
+///     1. Stops reading the normal channel: later requests stay queued there, and expiry
+///        ticks are skipped. Requests sent through a [`PriorityClient`] are still handled.
+///
+/// * **Resume**:
+///     1. Reads the normal channel again, starting with the requests queued while paused.
+///
+/// * **BeginDraining**:
+///     1. From then on, answers every write (create, update, replace, delete or action)
+///        with `ShuttingDown` without running it; reads are still served.
+///     2. The actor keeps running until the channel closes or a **Shutdown** arrives.
+///
+/// * **Inspect** (`testing` feature only):
+///     1. Returns a clone of the stored entity without calling any hook, or `NotFound`.
+///
+/// * **Start**:
+///     1. Runs each time the loop starts, before the first request is read, including after
+///        a restart by [`ResourceActor::run_supervised`].
+///     2. Calls the `on_start` lifecycle hook with the `store`.
+///
+/// * **Expiry** (timer, not a request):
+///     1. Every expiry interval, finds entities whose [`ActorEntity::ttl`] has elapsed on
+///        the actor's clock (see [`ResourceActor::with_clock`]).
+///     2. Calls the `on_delete` lifecycle hook and removes each one from the `store`.
+///
+/// * **Shutdown**:
+///     1. Ends the loop after the current request, leaving any queued requests unprocessed.
+///     2. Replies once **Stop** has run.
+///
+/// * **Stop**:
+///     1. Runs once after the channel closes (or a **Shutdown**) and the loop exits, or when
+///        [`ResourceActor::run_supervised`] gives up after its last restart.
+///     2. Calls the `on_stop_with_reason` lifecycle hook (which defaults to `on_stop`) with the
+///        final `store` and the [`StopReason`].
+///
+/// Requests sent through a [`PriorityClient`] arrive on a second channel that the loop always
+/// reads first, so control requests are not stuck behind a backlog.
+///
+/// With [`ResourceActor::with_audit`], every read and write is also reported on an audit
+/// channel once handled (see [`audit`]).
+///
+/// With [`ResourceActor::with_capacity_limit`], the store holds at most a fixed number of
+/// entities, evicting one on each create beyond it (see [`crate::eviction`]).
 pub struct ResourceActor<T: ActorEntity> {
     receiver: mpsc::Receiver<ResourceRequest<T>>,
+    priority_receiver: mpsc::Receiver<ResourceRequest<T>>,
+    /// Kept so priority clients can be handed out at any time; it also stops the priority
+    /// channel from ever closing, so only the normal channel decides when the actor stops.
+    priority_sender: mpsc::Sender<ResourceRequest<T>>,
     store: HashMap<T::Id, T>,
-    next_id: u32,
+    next_id: IdGenerator,
+    resilient: bool,
+    /// Deadlines of entities whose `ttl` returned `Some`, on `clock`.
+    expires_at: HashMap<T::Id, std::time::Instant>,
+    expiry_interval: Duration,
+    /// The time TTLs are measured in.
+    clock: Arc<dyn HasClock + Send + Sync>,
+    quiet: bool,
+    /// Largest `ActionMany`, `ActionAtomic` or `Pipeline` accepted; `None` accepts any size.
+    max_batch: Option<usize>,
+    stats: ActorStats,
+    /// Unfinished requests of the pipeline being run.
+    pipeline: VecDeque<ResourceRequest<T>>,
+    /// Set by a `Shutdown` request; answered once the actor has stopped.
+    shutdown: Option<Response<()>>,
+    /// Set by a `BeginDraining` request; writes are rejected from then on.
+    draining: bool,
+    /// Set by a `Pause` request; the normal channel is not read until a `Resume`.
+    paused: bool,
+    /// Requests read ahead of the channel, when scheduling by deadline.
+    scheduled: Option<DeadlineQueue<T>>,
+    /// Messages handled between yields to the runtime; `0` never yields.
+    yield_every: usize,
+    /// Where an entry is sent for each operation handled, when auditing.
+    audit: Option<mpsc::Sender<AuditEntry>>,
+    /// Id assigned by the last successful create, for its audit entry.
+    last_created: Option<T::Id>,
+    /// Eviction order of the stored entities, when the store is bounded.
+    capacity: Option<CapacityLimit<T::Id>>,
+    /// `NotifyCount` requests waiting for the store to hold their target count.
+    count_waiters: Vec<(usize, Response<()>)>,
 }
 
+/// Produces the raw id of each new entity, or `None` once no ids are left.
+type IdGenerator = Box<dyn FnMut() -> Option<u32> + Send>;
+
+/// How often an actor checks for expired entities unless configured otherwise.
+pub const DEFAULT_EXPIRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Capacity of the priority channel; control requests are rare, so it is kept small.
+pub const PRIORITY_CHANNEL_CAPACITY: usize = 8;
+
+/// How many messages an actor handles back to back before yielding to the runtime, unless
+/// configured otherwise.
+pub const DEFAULT_YIELD_EVERY: usize = 64;
+
 impl<T: ActorEntity> ResourceActor<T> {
     /// Creates a new `ResourceActor` and its associated `ResourceClient`.
     ///
@@ -137,16 +371,258 @@ impl<T: ActorEntity> ResourceActor<T> {
     /// 1. The `ResourceActor` instance (the server), which must be run via `.run()`.
     /// 2. The `ResourceClient` instance, which can be cloned and shared to send requests.
     pub fn new(buffer_size: usize) -> (Self, ResourceClient<T>) {
+        Self::with_counter(buffer_size, 1)
+    }
+
+    /// Creates a new `ResourceActor` whose IDs count up from `first`.
+    ///
+    /// After handing out `u32::MAX` the counter is exhausted: later creates fail with
+    /// [`FrameworkError::IdExhausted`] rather than wrapping around to reuse an ID.
+    pub fn with_counter(buffer_size: usize, first: u32) -> (Self, ResourceClient<T>) {
+        let mut next = Some(first);
+        Self::with_generator(
+            buffer_size,
+            Box::new(move || {
+                let id = next?;
+                next = id.checked_add(1);
+                Some(id)
+            }),
+        )
+    }
+
+    /// Creates a new `ResourceActor` that draws IDs from a custom generator.
+    ///
+    /// The generator is called once per `Create` request and its result converted to `T::Id`.
+    /// It must never return the same value twice, otherwise an existing entity is overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer_size` - The capacity of the MPSC channel (see [`ResourceActor::new`]).
+    /// * `next_id` - Produces the raw `u32` for each new entity.
+    pub fn with_id_generator(
+        buffer_size: usize,
+        mut next_id: impl FnMut() -> u32 + Send + 'static,
+    ) -> (Self, ResourceClient<T>) {
+        Self::with_generator(buffer_size, Box::new(move || Some(next_id())))
+    }
+
+    /// Creates an actor with [`ResourceActor::with_id_generator`] and spawns its event loop
+    /// with `context`, returning its client and task handle.
+    ///
+    /// This is the common case when the context is known upfront; use the `new` family and
+    /// [`ResourceActor::run`] separately when the context depends on clients created later.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn start(
+        buffer_size: usize,
+        next_id: impl FnMut() -> u32 + Send + 'static,
+        context: T::Context,
+    ) -> (ResourceClient<T>, JoinHandle<()>) {
+        let (actor, client) = Self::with_id_generator(buffer_size, next_id);
+        (client, tokio::spawn(actor.run(context)))
+    }
+
+    fn with_generator(buffer_size: usize, next_id: IdGenerator) -> (Self, ResourceClient<T>) {
         let (sender, receiver) = mpsc::channel(buffer_size);
+        let (priority_sender, priority_receiver) = mpsc::channel(PRIORITY_CHANNEL_CAPACITY);
         let actor = Self {
             receiver,
+            priority_receiver,
+            priority_sender,
             store: HashMap::new(),
-            next_id: 1,
+            next_id,
+            resilient: false,
+            expires_at: HashMap::new(),
+            expiry_interval: DEFAULT_EXPIRY_INTERVAL,
+            clock: Arc::new(SystemClock),
+            quiet: false,
+            max_batch: None,
+            stats: ActorStats::default(),
+            pipeline: VecDeque::new(),
+            shutdown: None,
+            draining: false,
+            paused: false,
+            scheduled: None,
+            yield_every: DEFAULT_YIELD_EVERY,
+            audit: None,
+            last_created: None,
+            capacity: None,
+            count_waiters: Vec::new(),
         };
         let client = ResourceClient::new(sender);
         (actor, client)
     }
 
+    /// Returns a [`PriorityClient`] whose requests are handled ahead of the normal queue.
+    pub fn priority_client(&self) -> PriorityClient<T> {
+        PriorityClient::new(self.priority_sender.clone())
+    }
+
+    /// Creates a `ResourceActor` whose store is rebuilt by replaying `events`, and its client.
+    ///
+    /// Events are applied in the order given, which must be the order they were recorded:
+    /// replaying an `Updated` before its `Created`, or a `Deleted` before a re-creation,
+    /// yields a different store. Replay writes entities directly, without running any hook.
+    /// An `Updated` or `Deleted` for an unknown id is logged at `warn` as a sign of a
+    /// misordered or truncated log; the `Updated` state is stored anyway.
+    ///
+    /// `next_id` is used for entities created after replay, so it must not return a replayed
+    /// id (e.g. start after the highest one). TTLs of replayed entities restart from now.
+    pub fn from_events(
+        buffer_size: usize,
+        next_id: impl FnMut() -> u32 + Send + 'static,
+        events: impl IntoIterator<Item = ResourceEvent<T>>,
+    ) -> (Self, ResourceClient<T>) {
+        let entity_type = T::entity_name();
+        let (mut actor, client) = Self::with_id_generator(buffer_size, next_id);
+        let mut replayed = 0;
+        for event in events {
+            replayed += 1;
+            match event {
+                ResourceEvent::Created { id, entity } => {
+                    if let Some(ttl) = entity.ttl() {
+                        let deadline = actor.clock.now() + ttl;
+                        actor.expires_at.insert(id.clone(), deadline);
+                    }
+                    actor.store.insert(id, entity);
+                }
+                ResourceEvent::Updated { id, entity } => {
+                    if !actor.store.contains_key(&id) {
+                        warn!(entity_type, %id, "Replayed update of unknown entity");
+                    }
+                    actor.store.insert(id, entity);
+                }
+                ResourceEvent::Deleted { id } => {
+                    if actor.store.remove(&id).is_none() {
+                        warn!(entity_type, %id, "Replayed delete of unknown entity");
+                    }
+                    actor.expires_at.remove(&id);
+                }
+            }
+        }
+        info!(
+            entity_type,
+            replayed,
+            size = actor.store.len(),
+            "Replayed events"
+        );
+        (actor, client)
+    }
+
+    /// Sets how often the actor removes entities whose [`ActorEntity::ttl`] has elapsed.
+    ///
+    /// Defaults to [`DEFAULT_EXPIRY_INTERVAL`]. An entity may outlive its TTL by up to one
+    /// interval. Ticks only scan entities that have a TTL, so the timer is cheap otherwise.
+    pub fn with_expiry_interval(mut self, interval: Duration) -> Self {
+        self.expiry_interval = interval;
+        self
+    }
+
+    /// Measures TTLs on `clock` instead of the system clock, e.g. a [`TestClock`] so a test
+    /// expires an entity by advancing time rather than sleeping through its TTL.
+    ///
+    /// Only the deadlines move with the clock: expiry still runs on each tick of the expiry
+    /// interval, so an entity is removed on the first tick after `clock` passes its deadline.
+    /// TTLs already running (e.g. of replayed entities) keep the time they have left.
+    ///
+    /// [`TestClock`]: crate::TestClock
+    pub fn with_clock(mut self, clock: impl HasClock + Send + Sync + 'static) -> Self {
+        let (before, after) = (self.clock.now(), clock.now());
+        for deadline in self.expires_at.values_mut() {
+            *deadline = after + deadline.saturating_duration_since(before);
+        }
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Downgrades per-operation success logs ("Created", "Updated", "Deleted", ...) from `info`
+    /// to `trace`.
+    ///
+    /// For high-throughput actors where a log line per request is noise even at
+    /// `RUST_LOG=info`. Startup, shutdown, warnings and errors keep their levels.
+    pub fn quiet(mut self) -> Self {
+        self.quiet = true;
+        self
+    }
+
+    /// Rejects `ActionMany`, `ActionAtomic` and `Pipeline` requests of more than `max` items
+    /// with `FrameworkError::BatchTooLarge`.
+    ///
+    /// A batch runs in one turn with no other request in between, which is what makes it
+    /// useful, but an unbounded one starves every other client for as long as it runs.
+    /// Rather than splitting oversized batches into chunks (which would silently drop the
+    /// no-interleaving guarantee), the actor rejects them whole before running any item, so
+    /// the caller decides how to split. Unbounded by default.
+    pub fn with_max_batch(mut self, max: usize) -> Self {
+        self.max_batch = Some(max);
+        self
+    }
+
+    /// Handles queued requests nearest deadline first (see [`schedule`](crate::schedule)),
+    /// reading up to `window` requests ahead of the channel to pick from.
+    ///
+    /// Without it requests are handled strictly in arrival order. The window bounds how many
+    /// requests are held outside the channel, where they no longer count against its capacity,
+    /// but not how often a request is overtaken: requests with deadlines keep going ahead of a
+    /// queued request without one, which waits as long as they keep arriving (there is no
+    /// aging). Give every request a deadline if none may starve.
+    pub fn with_deadline_scheduling(mut self, window: usize) -> Self {
+        self.scheduled = Some(DeadlineQueue::new(window.max(1)));
+        self
+    }
+
+    /// Yields to the runtime after every `n` messages handled, `0` meaning never.
+    ///
+    /// Cheap requests (a `Get`, a `Count`) complete without reaching an `await` point, so a
+    /// backlog of them is handled without giving other tasks a turn, which on a current-thread
+    /// runtime stalls everything else until the backlog is gone. Defaults to
+    /// [`DEFAULT_YIELD_EVERY`]. Yielding never lets another request into a pipeline.
+    pub fn with_yield_every(mut self, n: usize) -> Self {
+        self.yield_every = n;
+        self
+    }
+
+    /// Sends an [`AuditEntry`] to `sink` after each read or write (see [`audit`]).
+    ///
+    /// Entries are sent with backpressure, never dropped: a full sink holds up the actor until
+    /// it has room, so drain it promptly. If the receiver is dropped, auditing stops with a
+    /// warning and the actor carries on.
+    pub fn with_audit(mut self, sink: mpsc::Sender<AuditEntry>) -> Self {
+        self.audit = Some(sink);
+        self
+    }
+
+    /// Holds at most `max` entities (at least one), evicting the one `policy` picks whenever
+    /// a create goes beyond that (see [`crate::eviction`]).
+    ///
+    /// The evicted entity's `on_delete` runs first; if it fails the entity is evicted anyway,
+    /// with a warning, so the store stays bounded. Soft-deleted tombstones count towards the
+    /// limit and are evicted without calling `on_delete` again. Unbounded by default.
+    pub fn with_capacity_limit(mut self, max: usize, policy: EvictionPolicy) -> Self {
+        let mut capacity = CapacityLimit::new(max, policy);
+        // Entities replayed by `from_events` are evicted first, in no particular order
+        for id in self.store.keys() {
+            capacity.insert(id.clone());
+        }
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Runs the event loop like [`ResourceActor::run`], but survives panics in entity hooks.
+    ///
+    /// A panic inside `from_create_params`, `on_create`, `on_update`, `on_delete` or
+    /// `handle_action` is caught and returned to that one caller as
+    /// `FrameworkError::EntityError` wrapping a [`HookPanic`]; the actor keeps serving
+    /// other requests. A panicking update is rolled back like any failed update, but a
+    /// panicking action may leave the entity partially modified.
+    ///
+    /// `run` remains the fail-fast default: there a panic ends the actor task and every
+    /// later call returns `FrameworkError::ActorClosed`.
+    pub async fn run_resilient(mut self, context: T::Context) {
+        self.resilient = true;
+        self.run(context).await
+    }
+
     /// Runs the actor's event loop, processing messages until the channel closes.
     ///
     /// # Context Injection
@@ -154,107 +630,971 @@ impl<T: ActorEntity> ResourceActor<T> {
     /// to access external dependencies (like other clients) that were created *after*
     /// the actor was instantiated but *before* the loop started.
     pub async fn run(mut self, context: T::Context) {
-        // Extract just the type name (e.g., "User" instead of "actor_recipe::model::user::User")
-        let entity_type = std::any::type_name::<T>()
-            .split("::")
-            .last()
-            .unwrap_or("Unknown");
+        let entity_type = T::entity_name();
         info!(entity_type, "Actor started");
+        self.serve(&context).await;
+        let reason = self.stop_reason();
+        self.stop(&context, reason).await;
+    }
+
+    /// Runs the event loop, restarting it with an empty store whenever it panics.
+    ///
+    /// Unlike [`ResourceActor::run_resilient`], which isolates each hook call, this treats a
+    /// panic as the death of the actor: the in-flight caller gets
+    /// `FrameworkError::ActorDropped`, and the loop is started again on the same channel so
+    /// existing clients stay connected. A restart discards everything the crashed run built
+    /// up: stored entities, a pause or drain, `NotifyCount` waiters and requests read ahead
+    /// for deadline scheduling (those callers also get `ActorDropped`), and `on_start` runs
+    /// again on the empty store, so state a hook keeps in the context can be rebuilt to match.
+    /// After `max_restarts` restarts the next panic stops the actor for good:
+    /// `on_stop_with_reason` runs with [`StopReason::Crashed`], the channel closes and later
+    /// calls return `FrameworkError::ActorClosed`.
+    ///
+    /// The restart happens inside the actor task, because the channel receiver does not
+    /// survive a panic that unwinds out of the task.
+    pub async fn run_supervised(mut self, context: T::Context, max_restarts: usize) {
+        let entity_type = T::entity_name();
+        info!(entity_type, "Actor started");
+
+        let mut restarts = 0;
+        while let Err(payload) = CatchUnwind(Box::pin(self.serve(&context))).await {
+            let message = panic_message(payload);
+            if restarts == max_restarts {
+                error!(entity_type, restarts, %message, "Actor crashed, giving up");
+                self.stop(&context, StopReason::Crashed).await;
+                return;
+            }
+            restarts += 1;
+            error!(entity_type, restarts, %message, "Actor crashed, restarting");
+            self.reset();
+        }
+        let reason = self.stop_reason();
+        self.stop(&context, reason).await;
+    }
+
+    /// Discards the state of a crashed run, keeping the configuration and statistics.
+    fn reset(&mut self) {
+        self.store.clear();
+        self.expires_at.clear();
+        self.pipeline.clear();
+        if let Some(capacity) = &mut self.capacity {
+            capacity.clear();
+        }
+        if let Some(scheduled) = &mut self.scheduled {
+            scheduled.clear();
+        }
+        self.count_waiters.clear();
+        self.paused = false;
+        self.draining = false;
+        self.last_created = None;
+    }
+
+    /// Processes messages until the channel closes or a `Shutdown` arrives.
+    async fn serve(&mut self, context: &T::Context) {
+        let entity_type = T::entity_name();
+        if let Err(e) = T::on_start(&self.store, context).await {
+            warn!(entity_type, error = %e, "on_start failed");
+        }
+        let mut expiry = tokio::time::interval(self.expiry_interval);
+        expiry.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut handled = 0;
+
+        loop {
+            // Unfinished requests of a pipeline are drained before the channel is read again
+            let msg = if let Some(msg) = self.pipeline.pop_front() {
+                msg
+            } else if let Some(msg) = self.next_scheduled() {
+                msg
+            } else {
+                let waiting = Instant::now();
+                let msg = tokio::select! {
+                    biased;
+                    // Never `None`: the actor holds a priority sender itself
+                    Some(msg) = self.priority_receiver.recv() => Some(msg),
+                    msg = self.receiver.recv(), if !self.paused => msg,
+                    _ = expiry.tick(), if !self.paused => {
+                        self.stats.idle_time += waiting.elapsed();
+                        let started = Instant::now();
+                        self.expire(context, entity_type).await;
+                        self.notify_count_waiters();
+                        self.stats.busy_time += started.elapsed();
+                        continue;
+                    }
+                };
+                self.stats.idle_time += waiting.elapsed();
+                let Some(msg) = msg else { break };
+                self.stats.messages += 1;
+                match &mut self.scheduled {
+                    Some(scheduled) => {
+                        scheduled.push(msg);
+                        self.next_scheduled().expect("a request was just queued")
+                    }
+                    None => msg,
+                }
+            };
+            self.process(msg, context).await;
+            if self.shutdown.is_some() {
+                break;
+            }
+            handled += 1;
+            if handled == self.yield_every {
+                handled = 0;
+                tokio::task::yield_now().await;
+            }
+        }
+    }
+
+    /// Processes exactly one queued request, without spawning the actor, and returns whether
+    /// one was queued.
+    ///
+    /// For white-box tests that need a precise interleaving: enqueue requests from several
+    /// clients (e.g. from spawned tasks), then step through them in order. Priority requests
+    /// still go first, and each request of a pipeline takes one step. Stepping never runs
+    /// `on_start`, `on_stop` or TTL expiry.
+    pub async fn step(&mut self, context: &T::Context) -> bool {
+        let msg = match self.pipeline.pop_front() {
+            Some(msg) => msg,
+            None if self.scheduled.is_some() => match self.next_scheduled() {
+                Some(msg) => msg,
+                None => return false,
+            },
+            None => match self
+                .priority_receiver
+                .try_recv()
+                .or_else(|e| match self.paused {
+                    true => Err(e),
+                    false => self.receiver.try_recv(),
+                }) {
+                Ok(msg) => {
+                    self.stats.messages += 1;
+                    msg
+                }
+                Err(_) => return false,
+            },
+        };
+        self.process(msg, context).await;
+        true
+    }
 
-        while let Some(msg) = self.receiver.recv().await {
+    /// With deadline scheduling, returns the next request to handle without waiting: a
+    /// priority request if one is queued, otherwise the nearest-deadline request after
+    /// reading ahead of the channel up to the window. Returns `None` when nothing is queued,
+    /// or while paused and no priority request is queued.
+    fn next_scheduled(&mut self) -> Option<ResourceRequest<T>> {
+        let scheduled = self.scheduled.as_mut()?;
+        if let Ok(msg) = self.priority_receiver.try_recv() {
+            self.stats.messages += 1;
+            return Some(msg);
+        }
+        if self.paused {
+            return None;
+        }
+        while !scheduled.is_full() {
+            let Ok(msg) = self.receiver.try_recv() else {
+                break;
+            };
+            self.stats.messages += 1;
+            scheduled.push(msg);
+        }
+        scheduled.pop()
+    }
+
+    /// Handles a request taken off a channel or the pipeline, timing it as busy time.
+    ///
+    /// The handling runs inside a `handle` span (with the entity type, the request kind as
+    /// `op` and the target id, if any), so hooks and the requests they send nest under it.
+    async fn process(&mut self, msg: ResourceRequest<T>, context: &T::Context) {
+        // The deadline only decided when the request is handled, not how
+        let msg = match msg {
+            ResourceRequest::WithDeadline { request, .. } => *request,
+            msg => msg,
+        };
+        let span = info_span!(
+            "handle",
+            entity_type = T::entity_name(),
+            op = ?msg.kind(),
+            id = field::Empty,
+        );
+        if let Some(id) = msg.id() {
+            span.record("id", field::display(id));
+        }
+        let operation = msg.kind();
+        let id = msg.id().map(ToString::to_string);
+        // Requests addressing one entity count as a use of it for LRU eviction
+        let used = self.capacity.as_ref().and(msg.id().cloned());
+        let started = Instant::now();
+        async {
             match msg {
-                ResourceRequest::Create { params, respond_to } => {
-                    debug!(entity_type, ?params, "Create");
-                    let id = T::Id::from(self.next_id);
-                    self.next_id += 1;
-
-                    match T::from_create_params(id.clone(), params) {
-                        Ok(mut item) => {
-                            // Await the async hook
-                            if let Err(e) = item.on_create(&context).await {
-                                warn!(entity_type, error = %e, "on_create failed");
-                                let _ =
-                                    respond_to.send(Err(FrameworkError::EntityError(Box::new(e))));
-                                continue;
-                            }
-                            self.store.insert(id.clone(), item);
-                            info!(entity_type, %id, size = self.store.len(), "Created");
-                            let _ = respond_to.send(Ok(id));
+                // The context is only visible to hooks run for this one request
+                ResourceRequest::WithContext {
+                    context: request_context,
+                    request,
+                } => request_context::scope(request_context, self.handle(*request, context)).await,
+                msg => self.handle(msg, context).await,
+            }
+        }
+        .instrument(span)
+        .await;
+        self.notify_count_waiters();
+        self.stats.busy_time += started.elapsed();
+        if let (Some(capacity), Some(id)) = (&mut self.capacity, used) {
+            capacity.touch(&id);
+        }
+        let created = self.last_created.take();
+        if self.audit.is_some() && audit::is_audited(operation) {
+            let id = id.or_else(|| created.map(|id| id.to_string()));
+            self.audit(operation, id).await;
+        }
+    }
+
+    /// Sends an audit entry, giving up on auditing if the sink's receiver is gone.
+    async fn audit(&mut self, operation: RequestKind, id: Option<String>) {
+        let Some(sink) = &self.audit else { return };
+        let entry = AuditEntry {
+            timestamp: SystemTime::now(),
+            entity_type: T::entity_name(),
+            id,
+            operation,
+        };
+        if sink.send(entry).await.is_err() {
+            warn!(
+                entity_type = T::entity_name(),
+                "Audit sink closed, auditing stopped"
+            );
+            self.audit = None;
+        }
+    }
+
+    /// Handles a single request and sends its reply.
+    async fn handle(&mut self, msg: ResourceRequest<T>, context: &T::Context) {
+        let resilient = self.resilient;
+        let quiet = self.quiet;
+        let entity_type = T::entity_name();
+
+        if self.draining && msg.kind().is_write() {
+            debug!(entity_type, kind = ?msg.kind(), "Rejected while draining");
+            self.stats.errors += 1;
+            msg.reject(&|| FrameworkError::ShuttingDown);
+            return;
+        }
+
+        match msg {
+            ResourceRequest::Create { params, respond_to } => {
+                debug!(entity_type, ?params, "Create");
+                let result = self.create(params, context, entity_type).await;
+                if result.is_err() {
+                    self.stats.errors += 1;
+                }
+                let _ = respond_to.send(result);
+            }
+            ResourceRequest::CreateReturning { params, respond_to } => {
+                debug!(entity_type, ?params, "CreateReturning");
+                let result = self
+                    .create(params, context, entity_type)
+                    .await
+                    .map(|id| self.store[&id].clone());
+                if result.is_err() {
+                    self.stats.errors += 1;
+                }
+                let _ = respond_to.send(result);
+            }
+            ResourceRequest::CreateCancelSafe { params, respond_to } => {
+                debug!(entity_type, ?params, "CreateCancelSafe");
+                if respond_to.is_closed() {
+                    debug!(entity_type, "Create abandoned before it ran");
+                    return;
+                }
+                let result = self.create(params, context, entity_type).await;
+                if result.is_err() {
+                    self.stats.errors += 1;
+                }
+                if let Err(Ok(id)) = respond_to.send(result) {
+                    self.discard(id, context, entity_type).await;
+                }
+            }
+            ResourceRequest::Get { id, respond_to } => {
+                let item = self.live(&id).cloned();
+                let found = item.is_some();
+                debug!(entity_type, %id, found, "Get");
+                let _ = respond_to.send(Ok(item));
+            }
+            ResourceRequest::GetIncludingDeleted { id, respond_to } => {
+                let item = self.store.get(&id).cloned();
+                let deleted = item.as_ref().map(T::is_deleted);
+                debug!(entity_type, %id, ?deleted, "GetIncludingDeleted");
+                let _ = respond_to.send(Ok(item));
+            }
+            ResourceRequest::GetProjection { id, respond_to } => {
+                let item = self.live(&id);
+                debug!(entity_type, %id, found = item.is_some(), "GetProjection");
+                respond_to.send(Ok(item));
+            }
+            ResourceRequest::GetMap { ids, respond_to } => {
+                let items: HashMap<T::Id, T> = ids
+                    .into_iter()
+                    .filter_map(|id| {
+                        let item = self.live(&id)?.clone();
+                        Some((id, item))
+                    })
+                    .collect();
+                debug!(entity_type, found = items.len(), "GetMap");
+                let _ = respond_to.send(Ok(items));
+            }
+            ResourceRequest::Update {
+                id,
+                update,
+                expected,
+                respond_to,
+            } => {
+                debug!(entity_type, %id, ?update, "Update");
+                if let Some(item) = self.live_mut(&id) {
+                    if let Some(expected) = expected {
+                        if !expected.matches(item) {
+                            warn!(entity_type, %id, "Version conflict");
+                            self.stats.errors += 1;
+                            let _ = respond_to
+                                .send(Err(FrameworkError::VersionConflict(id.to_string())));
+                            return;
                         }
+                    }
+                    // Snapshot the pre-update state for the hook and for rollback
+                    let prev = item.clone();
+                    // Await the async hook
+                    let hook = item.on_update_with_prev(update, &prev, context);
+                    let changed = match guard::<T, _, _>(resilient, hook).await {
+                        Ok(changed) => changed,
                         Err(e) => {
-                            warn!(entity_type, error = %e, "Create failed");
-                            let _ = respond_to.send(Err(FrameworkError::EntityError(Box::new(e))));
+                            *item = prev;
+                            warn!(entity_type, %id, error = %e, "Update failed");
+                            self.stats.errors += 1;
+                            let _ = respond_to.send(Err(e));
+                            return;
                         }
+                    };
+                    if changed {
+                        op_info!(quiet, entity_type, %id, "Updated");
+                    } else {
+                        debug!(entity_type, %id, "update no-op");
                     }
+                    let _ = respond_to.send(Ok(item.clone()));
+                } else {
+                    warn!(entity_type, %id, "Not found");
+                    self.stats.errors += 1;
+                    let _ = respond_to.send(Err(FrameworkError::NotFound(id.to_string())));
                 }
-                ResourceRequest::Get { id, respond_to } => {
-                    let item = self.store.get(&id).cloned();
-                    let found = item.is_some();
-                    debug!(entity_type, %id, found, "Get");
-                    let _ = respond_to.send(Ok(item));
+            }
+            ResourceRequest::Replace {
+                id,
+                entity,
+                respond_to,
+            } => {
+                debug!(entity_type, %id, "Replace");
+                if self.live(&id).is_none() {
+                    warn!(entity_type, %id, "Not found");
+                    self.stats.errors += 1;
+                    let _ = respond_to.send(Err(FrameworkError::NotFound(id.to_string())));
+                    return;
                 }
-                ResourceRequest::Update {
-                    id,
-                    update,
-                    respond_to,
-                } => {
-                    debug!(entity_type, %id, ?update, "Update");
-                    if let Some(item) = self.store.get_mut(&id) {
-                        // Await the async hook
-                        if let Err(e) = item.on_update(update, &context).await {
-                            warn!(entity_type, %id, error = %e, "Update failed");
-                            let _ = respond_to.send(Err(FrameworkError::EntityError(Box::new(e))));
-                            continue;
-                        }
-                        info!(entity_type, %id, "Updated");
+                let checked = if entity.id() != &id {
+                    warn!(entity_type, %id, other = %entity.id(), "Replacement has another id");
+                    Err(Self::id_changed(&id))
+                } else {
+                    entity.validate().map_err(T::into_framework_error)
+                };
+                let item = self.live_mut(&id).expect("checked above");
+                let replaced = match checked {
+                    Ok(()) => Self::swap(item, entity, context, resilient).await,
+                    Err(e) => Err(e),
+                };
+                match replaced {
+                    Ok(()) => {
+                        op_info!(quiet, entity_type, %id, "Replaced");
                         let _ = respond_to.send(Ok(item.clone()));
-                    } else {
-                        warn!(entity_type, %id, "Not found");
-                        let _ = respond_to.send(Err(FrameworkError::NotFound(id.to_string())));
+                    }
+                    Err(e) => {
+                        warn!(entity_type, %id, error = %e, "Replacement rejected");
+                        self.stats.errors += 1;
+                        let _ = respond_to.send(Err(e));
                     }
                 }
-                ResourceRequest::Delete { id, respond_to } => {
-                    debug!(entity_type, %id, "Delete");
-                    if let Some(item) = self.store.get(&id) {
-                        // Await the async hook
-                        if let Err(e) = item.on_delete(&context).await {
-                            warn!(entity_type, %id, error = %e, "on_delete failed");
-                            let _ = respond_to.send(Err(FrameworkError::EntityError(Box::new(e))));
-                            continue;
-                        }
-                        self.store.remove(&id);
-                        info!(entity_type, %id, size = self.store.len(), "Deleted");
-                        let _ = respond_to.send(Ok(()));
-                    } else {
-                        warn!(entity_type, %id, "Not found");
-                        let _ = respond_to.send(Err(FrameworkError::NotFound(id.to_string())));
+            }
+            ResourceRequest::Delete { id, respond_to } => {
+                debug!(entity_type, %id, "Delete");
+                let result = self.delete(id, context, entity_type).await;
+                let _ = respond_to.send(result.map(|_| ()));
+            }
+            ResourceRequest::DeleteReturning { id, respond_to } => {
+                debug!(entity_type, %id, "DeleteReturning");
+                let result = self.delete(id, context, entity_type).await;
+                let _ = respond_to.send(result);
+            }
+            ResourceRequest::Action {
+                id,
+                action,
+                respond_to,
+            } => {
+                debug!(entity_type, %id, ?action, "Action");
+                let result = self.action(id, action, context, entity_type).await;
+                let _ = respond_to.send(result);
+            }
+            ResourceRequest::ActionReturning {
+                id,
+                action,
+                respond_to,
+            } => {
+                debug!(entity_type, %id, ?action, "ActionReturning");
+                let result = self
+                    .action(id.clone(), action, context, entity_type)
+                    .await
+                    .map(|result| {
+                        let entity = self.store.get(&id).expect("action ran on a stored entity");
+                        (result, entity.clone())
+                    });
+                let _ = respond_to.send(result);
+            }
+            ResourceRequest::NotifyAction { id, action } => {
+                debug!(entity_type, %id, ?action, "NotifyAction");
+                // Nobody awaits the result; `action` already logs a failure
+                let _ = self.action(id, action, context, entity_type).await;
+            }
+            ResourceRequest::ActionMany { items, respond_to } => {
+                debug!(entity_type, count = items.len(), "ActionMany");
+                if let Err(e) = self.check_batch(items.len(), entity_type) {
+                    let _ = respond_to.send(Err(e));
+                    return;
+                }
+                let mut results = Vec::with_capacity(items.len());
+                for (id, action) in items {
+                    results.push(self.action(id, action, context, entity_type).await);
+                }
+                let _ = respond_to.send(Ok(results));
+            }
+            ResourceRequest::ActionAtomic { items, respond_to } => {
+                debug!(entity_type, count = items.len(), "ActionAtomic");
+                if let Err(e) = self.check_batch(items.len(), entity_type) {
+                    let _ = respond_to.send(Err(e));
+                    return;
+                }
+                let result = self.action_atomic(items, context, entity_type).await;
+                let _ = respond_to.send(result);
+            }
+            ResourceRequest::List { respond_to } => {
+                let items: Vec<T> = self.live_values().cloned().collect();
+                debug!(entity_type, count = items.len(), "List");
+                let _ = respond_to.send(Ok(items));
+            }
+            ResourceRequest::FindWhere {
+                predicate,
+                respond_to,
+            } => {
+                let items: Vec<T> = self
+                    .live_values()
+                    .filter(|item| predicate.matches(item))
+                    .cloned()
+                    .collect();
+                debug!(entity_type, count = items.len(), "FindWhere");
+                let _ = respond_to.send(Ok(items));
+            }
+            ResourceRequest::ListPaginated {
+                offset,
+                limit,
+                respond_to,
+            } => {
+                let items: Vec<T> = self
+                    .live_values()
+                    .skip(offset)
+                    .take(limit)
+                    .cloned()
+                    .collect();
+                debug!(entity_type, offset, count = items.len(), "ListPaginated");
+                let _ = respond_to.send(Ok(items));
+            }
+            ResourceRequest::Count { respond_to } => {
+                let size = self.live_values().count();
+                debug!(entity_type, size, "Count");
+                let _ = respond_to.send(Ok(size));
+            }
+            ResourceRequest::Pipeline { requests } => {
+                debug!(entity_type, count = requests.len(), "Pipeline");
+                if let Err(FrameworkError::BatchTooLarge { size, max }) =
+                    self.check_batch(requests.len(), entity_type)
+                {
+                    // Every request of the pipeline learns why it did not run
+                    for request in requests {
+                        request.reject(&|| FrameworkError::BatchTooLarge { size, max });
                     }
+                    return;
                 }
-                ResourceRequest::Action {
-                    id,
-                    action,
-                    respond_to,
-                } => {
-                    debug!(entity_type, %id, ?action, "Action");
-                    if let Some(item) = self.store.get_mut(&id) {
-                        // Await the async hook
-                        let result = item
-                            .handle_action(action, &context)
-                            .await
-                            .map_err(|e| FrameworkError::EntityError(Box::new(e)));
-                        match &result {
-                            Ok(_) => info!(entity_type, %id, "Action ok"),
-                            Err(e) => warn!(entity_type, %id, error = %e, "Action failed"),
+                // Nested pipelines run in place, ahead of anything already queued
+                for request in requests.into_iter().rev() {
+                    self.pipeline.push_front(request);
+                }
+            }
+            ResourceRequest::WithContext {
+                context: request_context,
+                request,
+            } => {
+                // Only reached when contexts are nested: requeue the inner request so the
+                // innermost context wins
+                self.pipeline.push_front(ResourceRequest::WithContext {
+                    context: request_context,
+                    request,
+                });
+            }
+            ResourceRequest::WithDeadline { request, .. } => {
+                // Only reached when nested in another request, where the deadline has no effect
+                Box::pin(self.handle(*request, context)).await;
+            }
+            ResourceRequest::NotifyCount { target, respond_to } => {
+                debug!(entity_type, target, "NotifyCount");
+                // Answered by `notify_count_waiters` once this request is done, possibly at once
+                self.count_waiters.push((target, respond_to));
+            }
+            ResourceRequest::Ping { respond_to } => {
+                trace!(entity_type, "Ping");
+                let _ = respond_to.send(Ok(()));
+            }
+            ResourceRequest::Stats { respond_to } => {
+                debug!(entity_type, messages = self.stats.messages, "Stats");
+                let _ = respond_to.send(Ok(self.stats.clone()));
+            }
+            ResourceRequest::Pause { respond_to } => {
+                info!(entity_type, queued = self.receiver.len(), "Paused");
+                self.paused = true;
+                let _ = respond_to.send(Ok(()));
+            }
+            ResourceRequest::Resume { respond_to } => {
+                info!(entity_type, queued = self.receiver.len(), "Resumed");
+                self.paused = false;
+                let _ = respond_to.send(Ok(()));
+            }
+            ResourceRequest::BeginDraining { respond_to } => {
+                info!(entity_type, "Draining: rejecting writes");
+                self.draining = true;
+                let _ = respond_to.send(Ok(()));
+            }
+            ResourceRequest::Shutdown { respond_to } => {
+                info!(
+                    entity_type,
+                    queued = self.receiver.len(),
+                    "Shutdown requested"
+                );
+                self.shutdown = Some(respond_to);
+            }
+            #[cfg(feature = "testing")]
+            ResourceRequest::Inspect { id, respond_to } => {
+                debug!(entity_type, %id, "Inspect");
+                let result = self
+                    .store
+                    .get(&id)
+                    .cloned()
+                    .ok_or_else(|| FrameworkError::NotFound(id.to_string()));
+                let _ = respond_to.send(result);
+            }
+            #[cfg(feature = "json-patch")]
+            ResourceRequest::PatchJson {
+                id,
+                patch,
+                respond_to,
+            } => {
+                debug!(entity_type, %id, ?patch, "PatchJson");
+                let Some(item) = self.live_mut(&id) else {
+                    warn!(entity_type, %id, "Not found");
+                    self.stats.errors += 1;
+                    let _ = respond_to.send(Err(FrameworkError::NotFound(id.to_string())));
+                    return;
+                };
+                let patched = patch
+                    .apply(item)
+                    .map_err(|e| FrameworkError::entity(entity_type, e))
+                    .and_then(|patched| {
+                        patched
+                            .validate()
+                            .map(|()| patched)
+                            .map_err(T::into_framework_error)
+                    })
+                    .and_then(|patched| {
+                        if patched.id() == &id {
+                            Ok(patched)
+                        } else {
+                            Err(Self::id_changed(&id))
                         }
-                        let _ = respond_to.send(result);
-                    } else {
-                        warn!(entity_type, %id, "Not found");
-                        let _ = respond_to.send(Err(FrameworkError::NotFound(id.to_string())));
+                    });
+                let patched = match patched {
+                    Ok(patched) => Self::swap(item, patched, context, resilient).await,
+                    Err(e) => Err(e),
+                };
+                match patched {
+                    Ok(()) => {
+                        op_info!(quiet, entity_type, %id, "Patched");
+                        let _ = respond_to.send(Ok(item.clone()));
+                    }
+                    Err(e) => {
+                        warn!(entity_type, %id, error = %e, "Patch rejected");
+                        self.stats.errors += 1;
+                        let _ = respond_to.send(Err(e));
                     }
                 }
             }
         }
+    }
+
+    /// Stores `new` in place of `item` and runs `on_replace`, restoring `item` if it fails.
+    async fn swap(
+        item: &mut T,
+        new: T,
+        context: &T::Context,
+        resilient: bool,
+    ) -> Result<(), FrameworkError> {
+        let prev = std::mem::replace(item, new);
+        let result = guard::<T, _, _>(resilient, item.on_replace(&prev, context)).await;
+        if result.is_err() {
+            *item = prev;
+        }
+        result
+    }
+
+    /// The error for a `Replace` or `PatchJson` that would store an entity under another id.
+    fn id_changed(id: &T::Id) -> FrameworkError {
+        let mut error = ValidationError::default();
+        error.add("id", format!("must be {id}, the id of the replaced entity"));
+        FrameworkError::Validation(error)
+    }
+
+    /// Why the loop exited on its own: a `Shutdown` request or the channel closing.
+    fn stop_reason(&self) -> StopReason {
+        if self.shutdown.is_some() {
+            StopReason::Shutdown
+        } else {
+            StopReason::AllClientsDropped
+        }
+    }
+
+    /// Runs `on_stop_with_reason` and answers a pending `Shutdown`.
+    async fn stop(&mut self, context: &T::Context, reason: StopReason) {
+        let entity_type = T::entity_name();
+        if let Err(e) = T::on_stop_with_reason(&self.store, reason, context).await {
+            warn!(entity_type, error = %e, "on_stop failed");
+        }
+
+        info!(entity_type, size = self.store.len(), ?reason, "Shutdown");
+        if let Some(respond_to) = self.shutdown.take() {
+            let _ = respond_to.send(Ok(()));
+        }
+    }
 
-        info!(entity_type, size = self.store.len(), "Shutdown");
+    /// Builds a new entity, runs `on_create`, and stores it. Returns the new id.
+    async fn create(
+        &mut self,
+        params: T::Create,
+        context: &T::Context,
+        entity_type: &str,
+    ) -> Result<T::Id, FrameworkError> {
+        let Some(raw_id) = (self.next_id)() else {
+            warn!(entity_type, "No ids left");
+            return Err(FrameworkError::IdExhausted);
+        };
+        let id = T::Id::from(raw_id);
+
+        let built = if self.resilient {
+            catch_unwind(AssertUnwindSafe(|| {
+                T::from_create_params(id.clone(), params)
+            }))
+            .map_err(|payload| panic_error(T::entity_name(), payload))?
+        } else {
+            T::from_create_params(id.clone(), params)
+        };
+        let mut item = built.map_err(|e| {
+            warn!(entity_type, error = %e, "Create failed");
+            T::into_framework_error(e)
+        })?;
+        // Checked before on_create, whose side effects (e.g. reserved stock) would be keyed
+        // by the wrong id
+        debug_assert_eq!(
+            item.id(),
+            &id,
+            "{entity_type} entity does not report the id it was created with"
+        );
+
+        // Await the async hook
+        if let Err(e) = guard::<T, _, _>(self.resilient, item.on_create(context)).await {
+            warn!(entity_type, error = %e, "on_create failed");
+            return Err(e);
+        }
+        if let Some(ttl) = item.ttl() {
+            self.expires_at.insert(id.clone(), self.clock.now() + ttl);
+        }
+        self.store.insert(id.clone(), item);
+        op_info!(self.quiet, entity_type, %id, size = self.store.len(), "Created");
+        if let Some(capacity) = &mut self.capacity {
+            capacity.insert(id.clone());
+            self.evict(context, entity_type).await;
+        }
+        if self.audit.is_some() {
+            self.last_created = Some(id.clone());
+        }
+        Ok(id)
+    }
+
+    /// Returns the stored entity with this id, unless it is a soft-deleted tombstone.
+    fn live(&self, id: &T::Id) -> Option<&T> {
+        self.store.get(id).filter(|item| !item.is_deleted())
+    }
+
+    /// Mutable counterpart of [`ResourceActor::live`].
+    fn live_mut(&mut self, id: &T::Id) -> Option<&mut T> {
+        self.store.get_mut(id).filter(|item| !item.is_deleted())
+    }
+
+    /// Iterates over the stored entities that are not soft-deleted tombstones.
+    fn live_values(&self) -> impl Iterator<Item = &T> {
+        self.store.values().filter(|item| !item.is_deleted())
+    }
+
+    /// Fails with `BatchTooLarge` if a batch of `size` items exceeds the configured maximum.
+    fn check_batch(&mut self, size: usize, entity_type: &str) -> Result<(), FrameworkError> {
+        match self.max_batch {
+            Some(max) if size > max => {
+                warn!(entity_type, size, max, "Batch too large");
+                self.stats.errors += 1;
+                Err(FrameworkError::BatchTooLarge { size, max })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Runs `on_delete`, then removes the entity or turns it into a tombstone. Returns the
+    /// removed entity, or a clone of the tombstone.
+    async fn delete(
+        &mut self,
+        id: T::Id,
+        context: &T::Context,
+        entity_type: &str,
+    ) -> Result<T, FrameworkError> {
+        let resilient = self.resilient;
+        let Some(item) = self.live_mut(&id) else {
+            warn!(entity_type, %id, "Not found");
+            self.stats.errors += 1;
+            return Err(FrameworkError::NotFound(id.to_string()));
+        };
+        // Await the async hook
+        if let Err(e) = guard::<T, _, _>(resilient, item.on_delete(context)).await {
+            warn!(entity_type, %id, error = %e, "on_delete failed");
+            self.stats.errors += 1;
+            return Err(e);
+        }
+        let tombstone = item.mark_deleted();
+        let deleted = if tombstone {
+            item.clone()
+        } else {
+            self.store.remove(&id).expect("entity was just found")
+        };
+        self.expires_at.remove(&id);
+        if let (false, Some(capacity)) = (tombstone, &mut self.capacity) {
+            capacity.remove(&id);
+        }
+        op_info!(self.quiet, entity_type, %id, tombstone, size = self.store.len(), "Deleted");
+        Ok(deleted)
+    }
+
+    /// Answers the `NotifyCount` waiters whose target is the current live count, and drops
+    /// those whose caller is gone.
+    fn notify_count_waiters(&mut self) {
+        if self.count_waiters.is_empty() {
+            return;
+        }
+        let count = self.live_values().count();
+        self.count_waiters
+            .retain(|(_, respond_to)| !respond_to.is_closed());
+        let (reached, waiting) = std::mem::take(&mut self.count_waiters)
+            .into_iter()
+            .partition(|(target, _)| *target == count);
+        self.count_waiters = waiting;
+        for (_, respond_to) in reached {
+            let _ = respond_to.send(Ok(()));
+        }
+    }
+
+    /// Undoes a create whose caller gave up before learning the new id: runs `on_delete` and
+    /// removes the entity, even if the hook fails, so nothing is left orphaned.
+    async fn discard(&mut self, id: T::Id, context: &T::Context, entity_type: &str) {
+        let Some(item) = self.store.remove(&id) else {
+            return;
+        };
+        if let Err(e) = guard::<T, _, _>(self.resilient, item.on_delete(context)).await {
+            warn!(entity_type, %id, error = %e, "on_delete failed while discarding");
+        }
+        self.expires_at.remove(&id);
+        if let Some(capacity) = &mut self.capacity {
+            capacity.remove(&id);
+        }
+        // Nothing was created as far as the audit log is concerned
+        self.last_created = None;
+        op_info!(self.quiet, entity_type, %id, size = self.store.len(), "Discarded unclaimed create");
+    }
+
+    /// Runs `handle_action` on one stored entity.
+    async fn action(
+        &mut self,
+        id: T::Id,
+        action: T::Action,
+        context: &T::Context,
+        entity_type: &str,
+    ) -> Result<T::ActionResult, FrameworkError> {
+        let resilient = self.resilient;
+        let Some(item) = self.live_mut(&id) else {
+            warn!(entity_type, %id, "Not found");
+            self.stats.errors += 1;
+            return Err(FrameworkError::NotFound(id.to_string()));
+        };
+        // Await the async hook
+        let result = guard::<T, _, _>(resilient, item.handle_action(action, context)).await;
+        match &result {
+            Ok(_) => op_info!(self.quiet, entity_type, %id, "Action ok"),
+            Err(e) => {
+                warn!(entity_type, %id, error = %e, "Action failed");
+                self.stats.errors += 1;
+            }
+        }
+        result
+    }
+
+    /// Runs `handle_action` for each item in order, restoring every entity touched so far if
+    /// one of them fails.
+    async fn action_atomic(
+        &mut self,
+        items: Vec<(T::Id, T::Action)>,
+        context: &T::Context,
+        entity_type: &str,
+    ) -> Result<Vec<T::ActionResult>, FrameworkError> {
+        let mut snapshots: HashMap<T::Id, T> = HashMap::new();
+        let mut results = Vec::with_capacity(items.len());
+        for (id, action) in items {
+            if let Some(item) = self.store.get(&id) {
+                snapshots.entry(id.clone()).or_insert_with(|| item.clone());
+            }
+            match self.action(id, action, context, entity_type).await {
+                Ok(result) => results.push(result),
+                Err(e) => {
+                    warn!(
+                        entity_type,
+                        restored = snapshots.len(),
+                        "ActionAtomic rolled back"
+                    );
+                    self.store.extend(snapshots);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    /// Removes every entity whose TTL has elapsed, calling `on_delete` first.
+    ///
+    /// If `on_delete` fails the entity is kept and retried on the next tick.
+    async fn expire(&mut self, context: &T::Context, entity_type: &str) {
+        let now = self.clock.now();
+        let expired: Vec<T::Id> = self
+            .expires_at
+            .iter()
+            .filter(|(_, deadline)| **deadline <= now)
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        for id in expired {
+            let Some(item) = self.store.get(&id) else {
+                self.expires_at.remove(&id);
+                continue;
+            };
+            if let Err(e) = guard::<T, _, _>(self.resilient, item.on_delete(context)).await {
+                warn!(entity_type, %id, error = %e, "on_delete failed during expiry");
+                continue;
+            }
+            self.store.remove(&id);
+            self.expires_at.remove(&id);
+            if let Some(capacity) = &mut self.capacity {
+                capacity.remove(&id);
+            }
+            op_info!(self.quiet, entity_type, %id, size = self.store.len(), "Expired");
+        }
+    }
+
+    /// Evicts entities until the store is within its capacity limit, calling `on_delete`
+    /// on each live one first and auditing each eviction.
+    async fn evict(&mut self, context: &T::Context, entity_type: &str) {
+        loop {
+            let Some(capacity) = &mut self.capacity else {
+                return;
+            };
+            if self.store.len() <= capacity.max() {
+                return;
+            }
+            let Some(id) = capacity.pop() else { return };
+            let Some(item) = self.store.remove(&id) else {
+                continue;
+            };
+            self.expires_at.remove(&id);
+            if !item.is_deleted() {
+                if let Err(e) = guard::<T, _, _>(self.resilient, item.on_delete(context)).await {
+                    warn!(entity_type, %id, error = %e, "on_delete failed during eviction");
+                }
+            }
+            self.stats.evictions += 1;
+            op_info!(self.quiet, entity_type, %id, size = self.store.len(), "Evicted");
+            self.audit(RequestKind::Evict, Some(id.to_string())).await;
+        }
+    }
+}
+
+/// Awaits a hook of entity `T`, mapping its error with [`ActorEntity::into_framework_error`].
+///
+/// When `resilient` is set, a panic while polling the hook is caught and reported as a
+/// [`HookPanic`] instead of unwinding through the actor task.
+async fn guard<T, R, F>(resilient: bool, hook: F) -> Result<R, FrameworkError>
+where
+    T: ActorEntity,
+    F: Future<Output = Result<R, T::Error>> + Unpin,
+{
+    let result = if resilient {
+        CatchUnwind(hook)
+            .await
+            .map_err(|payload| panic_error(T::entity_name(), payload))?
+    } else {
+        hook.await
+    };
+    result.map_err(T::into_framework_error)
+}
+
+/// Extracts the message from a caught panic payload.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Converts a caught panic payload into a `FrameworkError`.
+fn panic_error(entity_type: &'static str, payload: Box<dyn Any + Send>) -> FrameworkError {
+    let message = panic_message(payload);
+    warn!(entity_type, %message, "Hook panicked");
+    FrameworkError::entity(entity_type, HookPanic(message))
+}
+
+/// Future adapter that catches panics raised while polling the inner future.
+struct CatchUnwind<F>(F);
+
+impl<F: Future + Unpin> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let inner = &mut self.0;
+        match catch_unwind(AssertUnwindSafe(|| Pin::new(inner).poll(cx))) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
     }
 }
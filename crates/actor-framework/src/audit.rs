@@ -0,0 +1,42 @@
+//! # Audit Trail
+//!
+//! With [`ResourceActor::with_audit`](crate::ResourceActor::with_audit), the actor sends an
+//! [`AuditEntry`] to a channel after handling each read or write, for an audit log kept apart
+//! from tracing. Tracing output is filtered by level and may be sampled or dropped; the audit
+//! stream has one entry per operation, in the order the actor handled them, ready to persist.
+//!
+//! Each request of a pipeline gets its own entry, and so does each entity a bounded store
+//! evicts (operation `Evict`), since its removal changes the store like a delete. Control
+//! requests (`NotifyCount`, `Ping`, `Stats`, `BeginDraining`, `Shutdown`) and TTL expiry are
+//! not audited.
+
+use crate::message::RequestKind;
+use std::time::SystemTime;
+
+/// One operation handled by an actor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditEntry {
+    /// When the actor finished handling the request.
+    pub timestamp: SystemTime,
+    /// The [`ActorEntity::entity_name`](crate::ActorEntity::entity_name) of the actor.
+    pub entity_type: &'static str,
+    /// The entity the request targeted, or the one it created; `None` for requests on many
+    /// entities (e.g. `List`) and for a create that failed.
+    pub id: Option<String>,
+    pub operation: RequestKind,
+}
+
+/// Whether requests of this kind are recorded.
+pub(crate) fn is_audited(kind: RequestKind) -> bool {
+    !matches!(
+        kind,
+        RequestKind::Pipeline
+            | RequestKind::NotifyCount
+            | RequestKind::Ping
+            | RequestKind::Stats
+            | RequestKind::Pause
+            | RequestKind::Resume
+            | RequestKind::BeginDraining
+            | RequestKind::Shutdown
+    )
+}
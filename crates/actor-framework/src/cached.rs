@@ -0,0 +1,192 @@
+//! # Read-Through Cache
+//!
+//! A thin wrapper around [`ResourceClient`] that serves repeated `get` calls from a short-lived
+//! client-side cache, cutting channel traffic for read-heavy callers of rarely-changing entities.
+//!
+//! ## Staleness
+//!
+//! A cached entity may be up to `ttl` old. Writes made **through the same wrapper** (or its clones)
+//! invalidate the affected entry immediately, but writes made through any other client are not
+//! seen until the entry expires. The cache is per-wrapper: two wrappers created separately from
+//! the same client do not share entries.
+//!
+//! A `get` that overlaps a write through the wrapper (sent before the write finished) may read
+//! either state, so its result is returned but not cached. This holds for a write whose future
+//! is dropped before its reply too, as the write may still reach the actor.
+
+use crate::client::ResourceClient;
+use crate::entity::ActorEntity;
+use crate::error::FrameworkError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Cached entities keyed by id, with the instant each was fetched.
+type Entries<T> = HashMap<<T as ActorEntity>::Id, (Instant, T)>;
+
+/// The cache shared by a wrapper and its clones.
+struct Cache<T: ActorEntity> {
+    entries: Entries<T>,
+    /// Bumped at the start and end of every write, so a read can tell it overlapped one.
+    writes: u64,
+}
+
+/// A `ResourceClient` wrapper whose `get` is served from a TTL cache when fresh.
+///
+/// Created with [`ResourceClient::with_read_cache`].
+pub struct CachedClient<T: ActorEntity> {
+    inner: ResourceClient<T>,
+    ttl: Duration,
+    cache: Arc<Mutex<Cache<T>>>,
+}
+
+impl<T: ActorEntity> Clone for CachedClient<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            ttl: self.ttl,
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+impl<T: ActorEntity> ResourceClient<T> {
+    /// Wraps this client with a read cache whose entries live for `ttl`.
+    pub fn with_read_cache(self, ttl: Duration) -> CachedClient<T> {
+        CachedClient {
+            inner: self,
+            ttl,
+            cache: Arc::new(Mutex::new(Cache {
+                entries: HashMap::new(),
+                writes: 0,
+            })),
+        }
+    }
+}
+
+impl<T: ActorEntity> CachedClient<T> {
+    /// Access the underlying uncached client.
+    pub fn inner(&self) -> &ResourceClient<T> {
+        &self.inner
+    }
+
+    /// Fetches an entity, serving it from the cache if the entry is younger than `ttl`.
+    ///
+    /// Only found entities are cached; a `None` result always goes to the actor next time.
+    pub async fn get(&self, id: impl Into<T::Id>) -> Result<Option<T>, FrameworkError> {
+        let id = id.into();
+        let writes = {
+            let cache = self.cache.lock().unwrap();
+            if let Some((fetched_at, item)) = cache.entries.get(&id) {
+                if fetched_at.elapsed() < self.ttl {
+                    return Ok(Some(item.clone()));
+                }
+            }
+            cache.writes
+        };
+
+        let item = self.inner.get(id.clone()).await?;
+        self.store(id, &item, writes);
+        Ok(item)
+    }
+
+    /// Like [`CachedClient::get`], but never waits for channel capacity.
+    ///
+    /// A fresh entry is served as usual. Otherwise the actor is asked via
+    /// [`ResourceClient::try_get`]; if its channel is full, a *stale* cached entry is returned
+    /// instead of failing, and `FrameworkError::Full` only when nothing is cached.
+    pub async fn try_get(&self, id: impl Into<T::Id>) -> Result<Option<T>, FrameworkError> {
+        let id = id.into();
+        let (cached, writes) = {
+            let cache = self.cache.lock().unwrap();
+            (cache.entries.get(&id).cloned(), cache.writes)
+        };
+        if let Some((fetched_at, item)) = &cached {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(Some(item.clone()));
+            }
+        }
+
+        match self.inner.try_get(id.clone()).await {
+            Ok(item) => {
+                self.store(id, &item, writes);
+                Ok(item)
+            }
+            Err(FrameworkError::Full) => match cached {
+                Some((_, stale)) => Ok(Some(stale)),
+                None => Err(FrameworkError::Full),
+            },
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Records a fresh read result: found entities are cached, misses evicted. A read that
+    /// overlapped a write (the write count moved since `writes`) is not cached.
+    fn store(&self, id: T::Id, item: &Option<T>, writes: u64) {
+        let mut cache = self.cache.lock().unwrap();
+        if cache.writes != writes {
+            return;
+        }
+        match item {
+            Some(item) => cache.entries.insert(id, (Instant::now(), item.clone())),
+            None => cache.entries.remove(&id),
+        };
+    }
+
+    pub async fn create(&self, params: T::Create) -> Result<T::Id, FrameworkError> {
+        self.inner.create(params).await
+    }
+
+    pub async fn update(
+        &self,
+        id: impl Into<T::Id>,
+        update: T::Update,
+    ) -> Result<T, FrameworkError> {
+        let id = id.into();
+        let _write = self.begin_write(id.clone());
+        self.inner.update(id, update).await
+    }
+
+    pub async fn delete(&self, id: impl Into<T::Id>) -> Result<(), FrameworkError> {
+        let id = id.into();
+        let _write = self.begin_write(id.clone());
+        self.inner.delete(id).await
+    }
+
+    pub async fn perform_action(
+        &self,
+        id: impl Into<T::Id>,
+        action: T::Action,
+    ) -> Result<T::ActionResult, FrameworkError> {
+        let id = id.into();
+        let _write = self.begin_write(id.clone());
+        self.inner.perform_action(id, action).await
+    }
+
+    /// Invalidates `id` for a write about to be sent, and again when the returned guard is
+    /// dropped: once the write finished, or when its future was dropped.
+    fn begin_write(&self, id: T::Id) -> WriteGuard<'_, T> {
+        self.invalidate(&id);
+        WriteGuard { client: self, id }
+    }
+
+    /// Drops the cached entry for `id`, forcing the next `get` to hit the actor. Reads in
+    /// flight meanwhile are not cached, as they may predate the change being invalidated.
+    pub fn invalidate(&self, id: &T::Id) {
+        let mut cache = self.cache.lock().unwrap();
+        cache.entries.remove(id);
+        cache.writes += 1;
+    }
+}
+
+/// A write through a [`CachedClient`] in progress; invalidates its entry when dropped.
+struct WriteGuard<'a, T: ActorEntity> {
+    client: &'a CachedClient<T>,
+    id: T::Id,
+}
+
+impl<T: ActorEntity> Drop for WriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.client.invalidate(&self.id);
+    }
+}
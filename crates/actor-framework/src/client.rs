@@ -2,84 +2,968 @@
 //!
 //! This module defines the generic client for communicating with actors.
 
-use crate::entity::ActorEntity;
+use crate::entity::{ActorEntity, Project, TypedAction};
 use crate::error::FrameworkError;
-use crate::message::ResourceRequest;
+use crate::message::{Expected, Predicate, ProjectionResponse, ResourceRequest};
+use crate::request_context::RequestContext;
+use crate::stats::ActorStats;
+use crate::validate::{Validate, ValidationError};
+use futures_core::Stream;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::{mpsc, oneshot};
+use tokio::time::{timeout_at, Instant};
+use tracing::warn;
 
 /// A type-safe client for interacting with a `ResourceActor`.
 #[derive(Clone)]
+#[must_use = "dropping every ResourceClient shuts the actor down"]
 /// ## ResourceClient
 ///
 /// The `ResourceClient<T>` provides a type‑safe, async API for interacting with a `ResourceActor<T>`. It forwards CRUD + Action requests over a Tokio mpsc channel and returns results via oneshot channels. The client is cheap to clone and can be shared across tasks.
 ///
-/// * **Cloneable** – holds only a sender, so cloning is inexpensive.
+/// * **Cloneable** – holds only a sender (and an optional request context and deadline), so cloning is inexpensive.
 /// * **Async API** – all methods return `Future`s that resolve to `Result<…, FrameworkError>`.
 /// * **Generic** – works with any entity that implements `ActorEntity`.
+/// * **Flexible ids** – `get`, `update`, `replace`, `delete` and `perform_action` take `impl Into<T::Id>`, so a bare `u32` works wherever `T::Id: From<u32>`.
+///
+/// ### Cancellation
+///
+/// Dropping a request future (e.g. the losing branch of a `select!` or a `timeout`) never
+/// corrupts the actor, but it does not take the request back either: once it is enqueued the
+/// actor handles it and its reply is discarded. A dropped read or a dropped `delete` is
+/// harmless; a dropped `create` still creates the entity, whose id the caller never learns.
+/// Use [`ResourceClient::create_cancel_safe`] where that orphan matters.
 pub struct ResourceClient<T: ActorEntity> {
     sender: mpsc::Sender<ResourceRequest<T>>,
+    request_context: Option<RequestContext>,
+    /// When this client or a clone of it last warned that the channel was at capacity.
+    capacity_warned_at: Arc<Mutex<Option<Instant>>>,
+    deadline: Option<Instant>,
+    /// Checks create payloads before they are sent, when validating.
+    validate_create: Option<ValidateCreate<T>>,
+}
+
+/// Checks a create payload with its [`Validate`] impl.
+type ValidateCreate<T> = fn(&<T as ActorEntity>::Create) -> Result<(), ValidationError>;
+
+/// Minimum time between two "channel at capacity" warnings from a client and its clones.
+pub const CAPACITY_WARNING_INTERVAL: Duration = Duration::from_secs(10);
+
+impl<T: ActorEntity> std::fmt::Debug for ResourceClient<T> {
+    /// Formats as `ResourceClient<User> { closed: false }`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("ResourceClient<{}>", T::entity_name()))
+            .field("closed", &self.sender.is_closed())
+            .finish()
+    }
 }
 
 impl<T: ActorEntity> ResourceClient<T> {
     pub fn new(sender: mpsc::Sender<ResourceRequest<T>>) -> Self {
-        Self { sender }
+        Self {
+            sender,
+            request_context: None,
+            capacity_warned_at: Arc::default(),
+            deadline: None,
+            validate_create: None,
+        }
     }
 
-    pub async fn create(&self, params: T::Create) -> Result<T::Id, FrameworkError> {
-        let (respond_to, response) = oneshot::channel();
+    /// Returns a client whose requests all carry `value` as their [`RequestContext`].
+    ///
+    /// Entity hooks read it with [`request_context::current`](crate::request_context::current),
+    /// e.g. to check the acting principal or scope work to a tenant. The original client is
+    /// unaffected; calling this on a client that already carries a context replaces it.
+    pub fn with_request_context<C: std::any::Any + Send + Sync>(&self, value: C) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            request_context: Some(RequestContext::new(value)),
+            capacity_warned_at: self.capacity_warned_at.clone(),
+            deadline: self.deadline,
+            validate_create: self.validate_create,
+        }
+    }
+
+    /// Returns a client whose requests all carry `deadline`.
+    ///
+    /// An actor built with
+    /// [`ResourceActor::with_deadline_scheduling`](crate::ResourceActor::with_deadline_scheduling)
+    /// handles them ahead of queued requests with a later or no deadline, e.g. checkouts ahead
+    /// of background listings. The deadline only orders requests: one that has passed is still
+    /// handled. Other actors handle the requests in arrival order as usual.
+    pub fn with_deadline(&self, deadline: Instant) -> Self {
+        Self {
+            deadline: Some(deadline),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a client that validates create payloads before sending them.
+    ///
+    /// `create`, `create_before` and `create_returning` call [`Validate::validate`] on the
+    /// payload and, if it fails, return `FrameworkError::Validation` without touching the
+    /// channel, sparing the actor a request it would reject anyway. A create queued on a
+    /// [`Pipeline`] is checked as it is queued, and its [`Reply`] resolves to the error
+    /// without the request being sent.
+    pub fn validating(&self) -> Self
+    where
+        T::Create: Validate,
+    {
+        Self {
+            validate_create: Some(|params| params.validate()),
+            ..self.clone()
+        }
+    }
+
+    /// Returns `true` if both clients send to the same actor.
+    ///
+    /// Clones compare equal, and so do clients that differ only in their request context; e.g.
+    /// for keeping a registry of distinct actors.
+    pub fn same_actor(&self, other: &Self) -> bool {
+        self.sender.same_channel(&other.sender)
+    }
+
+    /// Returns `true` once the actor has stopped, so every later request fails with
+    /// `ActorClosed`.
+    ///
+    /// Lets a caller that got an error from this client tell "the actor is gone" apart from an
+    /// error the actor returned, e.g. to report a downstream service as unavailable.
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+
+    /// Sends a request (with this client's request context), waiting for channel capacity.
+    async fn send(&self, request: ResourceRequest<T>) -> Result<(), FrameworkError> {
+        self.enqueue(self.wrap(request)).await
+    }
+
+    /// Sends an already wrapped request, waiting for channel capacity.
+    ///
+    /// A full channel makes the caller wait silently, which looks like an unexplained latency
+    /// spike, so it is logged at `warn` first, at most once per [`CAPACITY_WARNING_INTERVAL`].
+    async fn enqueue(&self, request: ResourceRequest<T>) -> Result<(), FrameworkError> {
+        if self.sender.capacity() == 0 {
+            let mut warned_at = self.capacity_warned_at.lock().unwrap();
+            if warned_at.is_none_or(|at| at.elapsed() >= CAPACITY_WARNING_INTERVAL) {
+                *warned_at = Some(Instant::now());
+                warn!(
+                    entity_type = T::entity_name(),
+                    "channel at capacity, caller will block"
+                );
+            }
+        }
         self.sender
-            .send(ResourceRequest::Create { params, respond_to })
+            .send(request)
             .await
-            .map_err(|_| FrameworkError::ActorClosed)?;
+            .map_err(|_| FrameworkError::ActorClosed)
+    }
+
+    /// Runs the create check of a [validating](ResourceClient::validating) client.
+    fn check(&self, params: &T::Create) -> Result<(), FrameworkError> {
+        match self.validate_create {
+            Some(validate) => validate(params).map_err(FrameworkError::Validation),
+            None => Ok(()),
+        }
+    }
+
+    /// Attaches this client's request context and deadline, if any, to an outgoing request.
+    ///
+    /// The deadline wraps outermost, so the actor can schedule by it without unwrapping.
+    fn wrap(&self, request: ResourceRequest<T>) -> ResourceRequest<T> {
+        self.attach_deadline(self.attach_context(request))
+    }
+
+    /// Attaches this client's request context, if any.
+    fn attach_context(&self, request: ResourceRequest<T>) -> ResourceRequest<T> {
+        match &self.request_context {
+            Some(context) => ResourceRequest::WithContext {
+                context: context.clone(),
+                request: Box::new(request),
+            },
+            None => request,
+        }
+    }
+
+    /// Attaches this client's deadline, if any.
+    fn attach_deadline(&self, request: ResourceRequest<T>) -> ResourceRequest<T> {
+        match self.deadline {
+            Some(deadline) => ResourceRequest::WithDeadline {
+                deadline,
+                request: Box::new(request),
+            },
+            None => request,
+        }
+    }
+
+    /// Returns the raw channel sender, e.g. for forwarding through a proxy actor.
+    pub fn sender(&self) -> &mpsc::Sender<ResourceRequest<T>> {
+        &self.sender
+    }
+
+    /// Returns the number of live clients (senders) connected to the actor, including this one.
+    ///
+    /// A result of `1` means this is the sole owner, so it is safe to drain and migrate the
+    /// actor's store. This is a **best-effort snapshot**: other tasks may clone or drop clients
+    /// right after it is taken, so only rely on it when you control every clone.
+    pub fn active_clients(&self) -> usize {
+        self.sender.strong_count()
+    }
+
+    /// Consumes the client and returns the raw channel sender.
+    ///
+    /// The inverse of [`ResourceClient::new`].
+    pub fn into_sender(self) -> mpsc::Sender<ResourceRequest<T>> {
+        self.sender
+    }
+
+    pub async fn create(&self, params: T::Create) -> Result<T::Id, FrameworkError> {
+        self.check(&params)?;
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::Create { params, respond_to })
+            .await?;
         response.await.map_err(|_| FrameworkError::ActorDropped)?
     }
 
-    pub async fn get(&self, id: T::Id) -> Result<Option<T>, FrameworkError> {
+    /// Creates an entity, waiting for channel capacity only until `deadline`.
+    ///
+    /// If the actor's channel is still full at `deadline`, the request is not sent and
+    /// `FrameworkError::Timeout` is returned. Taking an absolute deadline (rather than a
+    /// duration) lets callers share one time budget across several downstream calls.
+    ///
+    /// Once the request is enqueued it will be processed; the response is then awaited
+    /// without a deadline so the caller always learns the new id.
+    pub async fn create_before(
+        &self,
+        params: T::Create,
+        deadline: Instant,
+    ) -> Result<T::Id, FrameworkError> {
+        self.check(&params)?;
         let (respond_to, response) = oneshot::channel();
-        self.sender
-            .send(ResourceRequest::Get { id, respond_to })
+        let permit = timeout_at(deadline, self.sender.reserve())
             .await
+            .map_err(|_| FrameworkError::Timeout)?
             .map_err(|_| FrameworkError::ActorClosed)?;
+        permit.send(self.wrap(ResourceRequest::Create { params, respond_to }));
         response.await.map_err(|_| FrameworkError::ActorDropped)?
     }
 
-    pub async fn update(&self, id: T::Id, update: T::Update) -> Result<T, FrameworkError> {
+    /// Like [`ResourceClient::create`], but leaves no entity behind if this future is dropped
+    /// before it completes.
+    ///
+    /// The actor skips the create if the caller is already gone when it is dequeued, and
+    /// undoes it (calling `on_delete`) if the caller gave up while it ran, so the entity only
+    /// stays once the reply is delivered. A future dropped after that, with the id waiting
+    /// unread, still leaves the entity in place. The undo runs `on_delete` on an entity no
+    /// caller ever saw, so that hook must not assume the entity was used.
+    pub async fn create_cancel_safe(&self, params: T::Create) -> Result<T::Id, FrameworkError> {
+        self.check(&params)?;
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::CreateCancelSafe { params, respond_to })
+            .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Creates a new entity from an existing one, e.g. a copy or a fixture, deriving the
+    /// create payload with `T::Create::from`.
+    ///
+    /// The conversion is one-way: the entity's id and any other field the actor or its hooks
+    /// set are dropped, and the new entity gets a fresh id from the actor.
+    pub async fn create_from(&self, entity: T) -> Result<T::Id, FrameworkError>
+    where
+        T::Create: From<T>,
+    {
+        self.create(T::Create::from(entity)).await
+    }
+
+    /// Creates an entity and returns it as stored, saving a follow-up `get` round-trip.
+    pub async fn create_returning(&self, params: T::Create) -> Result<T, FrameworkError> {
+        self.check(&params)?;
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::CreateReturning { params, respond_to })
+            .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    pub async fn get(&self, id: impl Into<T::Id>) -> Result<Option<T>, FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::Get {
+            id: id.into(),
+            respond_to,
+        })
+        .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Like [`ResourceClient::get`], but also returns an entity that was soft-deleted.
+    ///
+    /// Check [`ActorEntity::is_deleted`] on the result to tell a tombstone from a live entity.
+    /// For entities without soft-delete this is the same as `get`.
+    pub async fn get_including_deleted(
+        &self,
+        id: impl Into<T::Id>,
+    ) -> Result<Option<T>, FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::GetIncludingDeleted {
+            id: id.into(),
+            respond_to,
+        })
+        .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Fetches several entities in one round-trip, keyed by id for easy lookup.
+    ///
+    /// Ids with no entity are left out of the map rather than reported as errors, so e.g. a
+    /// join over references can check `map.get(id)` per reference.
+    pub async fn get_map(&self, ids: Vec<T::Id>) -> Result<HashMap<T::Id, T>, FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::GetMap { ids, respond_to })
+            .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Like [`ResourceClient::get`], but returns only the entity's
+    /// [`Project::Projection`](crate::entity::Project) instead of a clone of the whole entity.
+    pub async fn get_projection(
+        &self,
+        id: impl Into<T::Id>,
+    ) -> Result<Option<T::Projection>, FrameworkError>
+    where
+        T: Project,
+    {
+        let (respond_to, response) = ProjectionResponse::channel();
+        self.send(ResourceRequest::GetProjection {
+            id: id.into(),
+            respond_to,
+        })
+        .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Like [`ResourceClient::get`], but never waits for channel capacity.
+    ///
+    /// Returns `FrameworkError::Full` immediately if the actor's channel is full, so a
+    /// latency-sensitive read path can fail fast and fall back elsewhere (see
+    /// [`CachedClient::try_get`](crate::CachedClient::try_get)). Once enqueued, the request
+    /// still waits for the actor's reply.
+    pub async fn try_get(&self, id: impl Into<T::Id>) -> Result<Option<T>, FrameworkError> {
         let (respond_to, response) = oneshot::channel();
         self.sender
-            .send(ResourceRequest::Update {
-                id,
-                update,
+            .try_send(self.wrap(ResourceRequest::Get {
+                id: id.into(),
                 respond_to,
-            })
-            .await
-            .map_err(|_| FrameworkError::ActorClosed)?;
+            }))
+            .map_err(|e| match e {
+                TrySendError::Full(_) => FrameworkError::Full,
+                TrySendError::Closed(_) => FrameworkError::ActorClosed,
+            })?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    pub async fn update(
+        &self,
+        id: impl Into<T::Id>,
+        update: T::Update,
+    ) -> Result<T, FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::Update {
+            id: id.into(),
+            update,
+            expected: None,
+            respond_to,
+        })
+        .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Like [`ResourceClient::update`], but applies the update only if the stored entity still
+    /// equals `expected`, typically the value an earlier `get` returned.
+    ///
+    /// Fails with [`FrameworkError::VersionConflict`] (leaving the entity untouched) if another
+    /// request changed it in between; the caller can `get` again and retry.
+    pub async fn update_if_unchanged(
+        &self,
+        id: impl Into<T::Id>,
+        expected: T,
+        update: T::Update,
+    ) -> Result<T, FrameworkError>
+    where
+        T: PartialEq,
+    {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::Update {
+            id: id.into(),
+            update,
+            expected: Some(Expected::new(expected)),
+            respond_to,
+        })
+        .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Like [`ResourceClient::update`], but treats a missing entity as a soft miss.
+    ///
+    /// Returns `Ok(None)` if no entity has this id and `Ok(Some(updated))` otherwise, mirroring
+    /// `get`. `Err` is reserved for channel failures and errors raised by the entity's hooks.
+    pub async fn try_update(
+        &self,
+        id: impl Into<T::Id>,
+        update: T::Update,
+    ) -> Result<Option<T>, FrameworkError> {
+        match self.update(id, update).await {
+            Ok(updated) => Ok(Some(updated)),
+            Err(FrameworkError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Overwrites an existing entity with `entity` (PUT semantics).
+    ///
+    /// Unlike [`ResourceClient::update`], which applies a partial `T::Update`, this swaps the
+    /// stored value wholesale. Returns `FrameworkError::NotFound` if no entity has this id,
+    /// `FrameworkError::Validation` if `entity` reports a different [`id`](ActorEntity::id),
+    /// and the converted error if `entity` fails [`ActorEntity::validate`]; the stored entity
+    /// is then unchanged.
+    pub async fn replace(&self, id: impl Into<T::Id>, entity: T) -> Result<T, FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::Replace {
+            id: id.into(),
+            entity,
+            respond_to,
+        })
+        .await?;
         response.await.map_err(|_| FrameworkError::ActorDropped)?
     }
 
     #[allow(dead_code)]
-    pub async fn delete(&self, id: T::Id) -> Result<(), FrameworkError> {
+    pub async fn delete(&self, id: impl Into<T::Id>) -> Result<(), FrameworkError> {
         let (respond_to, response) = oneshot::channel();
-        self.sender
-            .send(ResourceRequest::Delete { id, respond_to })
-            .await
-            .map_err(|_| FrameworkError::ActorClosed)?;
+        self.send(ResourceRequest::Delete {
+            id: id.into(),
+            respond_to,
+        })
+        .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Like [`ResourceClient::delete`], but returns the deleted entity, e.g. to record it in
+    /// an audit log or to undo the delete by creating it again.
+    ///
+    /// Fails with `NotFound` if there is no such entity. For an entity with soft-delete the
+    /// result is its tombstone.
+    pub async fn delete_returning(&self, id: impl Into<T::Id>) -> Result<T, FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::DeleteReturning {
+            id: id.into(),
+            respond_to,
+        })
+        .await?;
         response.await.map_err(|_| FrameworkError::ActorDropped)?
     }
 
     pub async fn perform_action(
         &self,
-        id: T::Id,
+        id: impl Into<T::Id>,
         action: T::Action,
     ) -> Result<T::ActionResult, FrameworkError> {
         let (respond_to, response) = oneshot::channel();
-        self.sender
-            .send(ResourceRequest::Action {
-                id,
-                action,
-                respond_to,
-            })
+        self.send(ResourceRequest::Action {
+            id: id.into(),
+            action,
+            respond_to,
+        })
+        .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Performs a [`TypedAction`] and returns its output, e.g. `typed_action(id, CheckStock)`
+    /// returning the stock level as a `u32`.
+    ///
+    /// Fails like [`ResourceClient::perform_action`]. A result of another action's variant is
+    /// a bug in the entity's `handle_action` and panics.
+    pub async fn typed_action<A: TypedAction<T>>(
+        &self,
+        id: impl Into<T::Id>,
+        action: A,
+    ) -> Result<A::Output, FrameworkError> {
+        let result = self.perform_action(id, action.into()).await?;
+        Ok(A::extract(result).unwrap_or_else(|other| {
+            unreachable!(
+                "{} action {} got a mismatched result: {:?}",
+                T::entity_name(),
+                std::any::type_name::<A>(),
+                other
+            )
+        }))
+    }
+
+    /// Performs an action and returns its result together with the entity as the action left
+    /// it, in one round-trip instead of a `perform_action` followed by a `get`.
+    ///
+    /// Fails like [`ResourceClient::perform_action`]; the entity is only returned on success.
+    pub async fn perform_action_returning(
+        &self,
+        id: impl Into<T::Id>,
+        action: T::Action,
+    ) -> Result<(T::ActionResult, T), FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::ActionReturning {
+            id: id.into(),
+            action,
+            respond_to,
+        })
+        .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Sends an action without waiting for it to run, e.g. for a metrics increment.
+    ///
+    /// No reply channel is allocated: this returns once the request is enqueued, and the
+    /// action's result (including `NotFound` or a hook error) is discarded by the actor and
+    /// only logged. Requests from one client are still handled in the order they were sent,
+    /// so a later `get` on this client observes the action's effect, unless the actor uses
+    /// [deadline scheduling](crate::ResourceActor::with_deadline_scheduling) and the `get` carries a
+    /// nearer deadline than the action.
+    pub async fn perform_action_detached(
+        &self,
+        id: impl Into<T::Id>,
+        action: T::Action,
+    ) -> Result<(), FrameworkError> {
+        self.send(ResourceRequest::NotifyAction {
+            id: id.into(),
+            action,
+        })
+        .await
+    }
+
+    /// Performs several actions in a single round-trip, returning one result per item.
+    ///
+    /// The actor runs the items in order within one turn, so no other request interleaves
+    /// with them. This is **not** transactional: a failing item does not stop the remaining
+    /// items or undo the ones already applied; see [`ResourceClient::perform_action_atomic`]
+    /// for all-or-nothing semantics.
+    ///
+    /// The outer `Result` reports channel failures; per-item failures (e.g. `NotFound`) are in
+    /// the returned `Vec`.
+    pub async fn perform_action_many(
+        &self,
+        items: Vec<(T::Id, T::Action)>,
+    ) -> Result<Vec<Result<T::ActionResult, FrameworkError>>, FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::ActionMany { items, respond_to })
+            .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Performs several actions as one all-or-nothing unit, returning one result per item.
+    ///
+    /// The actor runs the items in order within one turn, like
+    /// [`ResourceClient::perform_action_many`], but if any action fails (including `NotFound`)
+    /// it restores every entity the batch touched and returns that error. Items may target
+    /// different entities, or the same entity several times. Only the entities' state is
+    /// restored: side effects of the hooks (e.g. calls to other actors) are not undone.
+    pub async fn perform_action_atomic(
+        &self,
+        items: Vec<(T::Id, T::Action)>,
+    ) -> Result<Vec<T::ActionResult>, FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::ActionAtomic { items, respond_to })
+            .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Returns a snapshot of every entity held by the actor, in no particular order.
+    pub async fn list(&self) -> Result<Vec<T>, FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::List { respond_to }).await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Returns every entity for which `predicate` returns `true`, in no particular order.
+    ///
+    /// The predicate runs on the actor's task against each stored entity, so only the matches
+    /// are cloned and sent back. It holds up every other request while it runs: keep it a
+    /// cheap, non-blocking check.
+    pub async fn find_where(
+        &self,
+        predicate: impl Fn(&T) -> bool + Send + 'static,
+    ) -> Result<Vec<T>, FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::FindWhere {
+            predicate: Predicate::new(predicate),
+            respond_to,
+        })
+        .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Returns at most `limit` entities, skipping the first `offset`.
+    ///
+    /// Pages follow the store's iteration order, which is only stable while the store is not
+    /// modified: creates or deletes between calls may cause entities to be skipped or repeated.
+    /// The actor finds a page by walking past the `offset` entities before it, so each call costs
+    /// O(`offset` + `limit`).
+    pub async fn list_paginated(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<T>, FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::ListPaginated {
+            offset,
+            limit,
+            respond_to,
+        })
+        .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Returns an [`EntityStream`] over every entity, fetched `chunk` at a time.
+    ///
+    /// Unlike [`ResourceClient::list`], at most one chunk is held in memory, which suits export
+    /// jobs over very large actors. Shares the consistency caveats of
+    /// [`ResourceClient::list_paginated`]: writes during the stream may make it skip or repeat
+    /// entities, and since each page is found by skipping the entities before it, the actor
+    /// walks O(n² / `chunk`) entities over the whole stream. Pick a large `chunk` for large
+    /// actors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk` is 0.
+    pub fn stream(&self, chunk: usize) -> EntityStream<T> {
+        assert!(chunk > 0, "chunk size must be non-zero");
+        EntityStream {
+            client: self.clone(),
+            chunk,
+            offset: 0,
+            buffer: VecDeque::new(),
+            fetching: None,
+            done: false,
+        }
+    }
+
+    /// Returns the number of entities held by the actor.
+    pub async fn count(&self) -> Result<usize, FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::Count { respond_to }).await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Waits until the actor's loop is running and has handled every request this client sent
+    /// before, by sending a `Ping` and awaiting the reply.
+    ///
+    /// Sending before the actor task is scheduled is already safe (requests wait in the
+    /// channel), so this is for setup that must *observe* a running actor, instead of
+    /// `yield_now` or `sleep` guesses.
+    pub async fn ready(&self) -> Result<(), FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::Ping { respond_to }).await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Puts the actor into draining mode: once the requests queued before this one have run,
+    /// every write is answered with [`FrameworkError::ShuttingDown`] while reads are still
+    /// served.
+    ///
+    /// The first phase of a graceful shutdown (stop accepting writes, finish reads, then exit):
+    /// the actor stops once its clients are dropped, as usual.
+    pub async fn begin_draining(&self) -> Result<(), FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::BeginDraining { respond_to })
+            .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Returns the actor's runtime counters, e.g. to tell an overloaded actor (mostly busy)
+    /// from a starved one (mostly idle).
+    pub async fn stats(&self) -> Result<ActorStats, FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::Stats { respond_to }).await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Starts a [`Pipeline`] of requests that the actor will run back to back.
+    ///
+    /// The pipeline is sent like a single request of this client: with its request context
+    /// and deadline, and with the create check of a [validating](ResourceClient::validating)
+    /// client.
+    pub fn pipeline(&self) -> Pipeline<T> {
+        Pipeline {
+            client: self.clone(),
+            requests: Vec::new(),
+        }
+    }
+
+    /// Waits until the actor holds exactly `target` entities, e.g. `wait_for_count(0)` until a
+    /// work queue drains. Returns at once if it already does.
+    ///
+    /// Unlike [`ResourceClient::wait_until`] this does not poll: the actor answers once a
+    /// request (or TTL expiry) leaves `target` entities, however many callers are waiting. A
+    /// count passed through in between, e.g. by one request of a pipeline, also counts.
+    /// Fails with `FrameworkError::ActorDropped` if the actor stops first; combine it with
+    /// `tokio::time::timeout` to bound the wait.
+    pub async fn wait_for_count(&self, target: usize) -> Result<(), FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::NotifyCount { target, respond_to })
+            .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Polls [`ResourceClient::count`] every `poll` until `predicate` holds, returning the
+    /// count that satisfied it.
+    ///
+    /// Intended for tests that must wait for background work (expiry, other tasks' requests)
+    /// instead of sleeping for a guessed duration. Returns `FrameworkError::Timeout` if the
+    /// predicate still fails after `timeout`.
+    pub async fn wait_until(
+        &self,
+        predicate: impl Fn(usize) -> bool,
+        poll: Duration,
+        timeout: Duration,
+    ) -> Result<usize, FrameworkError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let count = self.count().await?;
+            if predicate(count) {
+                return Ok(count);
+            }
+            if Instant::now() >= deadline {
+                return Err(FrameworkError::Timeout);
+            }
+            tokio::time::sleep(poll).await;
+        }
+    }
+
+    /// Waits until the actor holds no entities (see [`ResourceClient::wait_until`]).
+    pub async fn wait_until_empty(
+        &self,
+        poll: Duration,
+        timeout: Duration,
+    ) -> Result<(), FrameworkError> {
+        self.wait_until(|count| count == 0, poll, timeout)
             .await
-            .map_err(|_| FrameworkError::ActorClosed)?;
+            .map(|_| ())
+    }
+
+    /// Merges a JSON merge patch into an entity and returns the result (see
+    /// [`json_patch`](crate::json_patch)).
+    ///
+    /// Only available with the `json-patch` feature. Returns `FrameworkError::NotFound` if no
+    /// entity has this id, `FrameworkError::EntityError` if the patched entity cannot be
+    /// deserialized or fails [`ActorEntity::validate`], and `FrameworkError::Validation` if the
+    /// patch changes the entity's [`id`](ActorEntity::id); the stored entity is then unchanged.
+    #[cfg(feature = "json-patch")]
+    pub async fn patch_json(
+        &self,
+        id: impl Into<T::Id>,
+        patch: serde_json::Value,
+    ) -> Result<T, FrameworkError>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::PatchJson {
+            id: id.into(),
+            patch: crate::json_patch::JsonPatch::new(patch),
+            respond_to,
+        })
+        .await?;
         response.await.map_err(|_| FrameworkError::ActorDropped)?
     }
+
+    /// Returns the stored entity exactly as the actor holds it, bypassing every hook.
+    ///
+    /// Only available with the `testing` feature. Returns `FrameworkError::NotFound` if no
+    /// entity has this id.
+    #[cfg(feature = "testing")]
+    pub async fn inspect(&self, id: impl Into<T::Id>) -> Result<T, FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.send(ResourceRequest::Inspect {
+            id: id.into(),
+            respond_to,
+        })
+        .await?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+}
+
+/// A batch of requests sent to one actor as a single message and run contiguously.
+///
+/// Each queued request returns a [`Reply`] to await once the pipeline has been sent. The
+/// actor handles the requests in order with no other client's request (or expiry tick) in
+/// between, in one round-trip. It is **not** a transaction: a failed request does not stop
+/// or undo the others.
+///
+/// A pipeline only spans one actor. Atomicity across actors (e.g. create user, then reserve
+/// stock, then create order) still needs the client-side saga pattern: run the steps in
+/// order and compensate for the completed ones when a later step fails.
+///
+/// ```rust,ignore
+/// let mut pipeline = client.pipeline();
+/// let created = pipeline.create(params);
+/// let reserved = pipeline.perform_action(id, ProductAction::ReserveStock(1));
+/// pipeline.send().await?;
+/// let new_id = created.await?;
+/// ```
+#[must_use = "a pipeline does nothing until `send` is awaited"]
+pub struct Pipeline<T: ActorEntity> {
+    client: ResourceClient<T>,
+    requests: Vec<ResourceRequest<T>>,
+}
+
+impl<T: ActorEntity> Pipeline<T> {
+    /// Queues a create; with a [validating](ResourceClient::validating) client, an invalid
+    /// payload is not queued and its reply is `FrameworkError::Validation`.
+    pub fn create(&mut self, params: T::Create) -> Reply<T::Id> {
+        let (respond_to, response) = oneshot::channel();
+        match self.client.check(&params) {
+            Ok(()) => self
+                .requests
+                .push(ResourceRequest::Create { params, respond_to }),
+            Err(e) => drop(respond_to.send(Err(e))),
+        }
+        Reply(response)
+    }
+
+    pub fn get(&mut self, id: impl Into<T::Id>) -> Reply<Option<T>> {
+        let (respond_to, response) = oneshot::channel();
+        self.requests.push(ResourceRequest::Get {
+            id: id.into(),
+            respond_to,
+        });
+        Reply(response)
+    }
+
+    pub fn update(&mut self, id: impl Into<T::Id>, update: T::Update) -> Reply<T> {
+        let (respond_to, response) = oneshot::channel();
+        self.requests.push(ResourceRequest::Update {
+            id: id.into(),
+            update,
+            expected: None,
+            respond_to,
+        });
+        Reply(response)
+    }
+
+    pub fn delete(&mut self, id: impl Into<T::Id>) -> Reply<()> {
+        let (respond_to, response) = oneshot::channel();
+        self.requests.push(ResourceRequest::Delete {
+            id: id.into(),
+            respond_to,
+        });
+        Reply(response)
+    }
+
+    pub fn perform_action(
+        &mut self,
+        id: impl Into<T::Id>,
+        action: T::Action,
+    ) -> Reply<T::ActionResult> {
+        let (respond_to, response) = oneshot::channel();
+        self.requests.push(ResourceRequest::Action {
+            id: id.into(),
+            action,
+            respond_to,
+        });
+        Reply(response)
+    }
+
+    /// Sends every queued request to the actor as one message.
+    ///
+    /// The request context goes on each request, since the actor runs them after unwrapping
+    /// the pipeline, and the deadline on the pipeline, which is scheduled as a whole.
+    pub async fn send(self) -> Result<(), FrameworkError> {
+        let client = self.client;
+        let requests = self
+            .requests
+            .into_iter()
+            .map(|request| client.attach_context(request))
+            .collect();
+        client
+            .enqueue(client.attach_deadline(ResourceRequest::Pipeline { requests }))
+            .await
+    }
+}
+
+/// Lazily yields every entity of an actor, one page at a time (see [`ResourceClient::stream`]).
+///
+/// A [`Stream`], so it works with `futures::StreamExt` combinators:
+///
+/// ```rust,ignore
+/// use futures::StreamExt;
+///
+/// let mut entities = client.stream(500);
+/// while let Some(entity) = entities.next().await {
+///     export(entity?)?;
+/// }
+/// ```
+///
+/// A failed page fetch is yielded once as an `Err`, after which the stream ends.
+pub struct EntityStream<T: ActorEntity> {
+    client: ResourceClient<T>,
+    chunk: usize,
+    offset: usize,
+    buffer: VecDeque<T>,
+    /// The page being fetched, if any.
+    fetching: Option<PageFuture<T>>,
+    done: bool,
+}
+
+/// A `list_paginated` call in flight.
+type PageFuture<T> = Pin<Box<dyn Future<Output = Result<Vec<T>, FrameworkError>> + Send>>;
+
+// Nothing is pinned structurally: the page future is boxed and the entities are only moved
+// out of the buffer.
+impl<T: ActorEntity> Unpin for EntityStream<T> {}
+
+impl<T: ActorEntity> Stream for EntityStream<T> {
+    type Item = Result<T, FrameworkError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if this.buffer.is_empty() && !this.done {
+            let fetching = this.fetching.get_or_insert_with(|| {
+                let client = this.client.clone();
+                let (offset, chunk) = (this.offset, this.chunk);
+                Box::pin(async move { client.list_paginated(offset, chunk).await })
+            });
+            let page = std::task::ready!(fetching.as_mut().poll(cx));
+            this.fetching = None;
+            match page {
+                Ok(page) => {
+                    // A short page means the store is exhausted
+                    this.done = page.len() < this.chunk;
+                    this.offset += page.len();
+                    this.buffer.extend(page);
+                }
+                Err(e) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+        Poll::Ready(this.buffer.pop_front().map(Ok))
+    }
+}
+
+/// The pending result of one request in a [`Pipeline`]; await it after sending.
+///
+/// Resolves to `FrameworkError::ActorDropped` if the pipeline was never sent.
+#[must_use = "the reply carries the request's result"]
+pub struct Reply<R>(oneshot::Receiver<Result<R, FrameworkError>>);
+
+impl<R> Future for Reply<R> {
+    type Output = Result<R, FrameworkError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0)
+            .poll(cx)
+            .map(|result| result.map_err(|_| FrameworkError::ActorDropped)?)
+    }
 }
@@ -48,7 +48,8 @@ use async_trait::async_trait;
 ///     fn from_create_params(id: u32, _: UserCreate) -> Result<Self, Self::Error> {
 ///         Ok(Self { id })
 ///     }
-///     async fn on_update(&mut self, _: UserUpdate, _: &()) -> Result<(), Self::Error> { Ok(()) }
+///     fn id(&self) -> &u32 { &self.id }
+///     async fn on_update(&mut self, _: UserUpdate, _: &()) -> Result<bool, Self::Error> { Ok(true) }
 ///     async fn handle_action(&mut self, _: UserAction, _: &()) -> Result<(), Self::Error> { Ok(()) }
 /// }
 ///
@@ -103,3 +104,163 @@ pub trait ActorClient<T: ActorEntity>: Send + Sync {
         self.inner().delete(id).await.map_err(Self::map_error)
     }
 }
+
+/// Generates a domain client wrapper around a [`ResourceClient`] and its [`ActorClient`] impl.
+///
+/// The generated struct derives `Clone` and `Debug`, has a `new(inner)` constructor and an
+/// `inner` field, so custom methods go in a separate `impl` block as usual. Its `map_error`
+/// recovers the domain error raised by an entity hook (so callers can match on its variants)
+/// and passes any other framework error's message to `fallback`.
+///
+/// A client that orchestrates other actors lists their clients in an optional `fields` block;
+/// each becomes a field of the struct and a trailing argument of `new`:
+///
+/// ```rust,ignore
+/// actor_framework::define_client! {
+///     pub struct OrderClient {
+///         entity: Order,
+///         error: OrderError,
+///         fallback: OrderError::ActorCommunicationError,
+///         fields: { users: UserClient, products: ProductClient },
+///     }
+/// }
+/// // OrderClient::new(inner, users, products)
+/// ```
+///
+/// State the client keeps for itself goes in an optional `state` block after `fields`; each
+/// becomes a field initialised with `Default::default()` by `new`, shared by the clones
+/// only if the type itself is shared (e.g. an `Arc`).
+///
+/// ```rust,ignore
+/// actor_framework::define_client! {
+///     /// Client for interacting with the User actor.
+///     pub struct UserClient {
+///         entity: User,
+///         error: UserError,
+///         fallback: UserError::ActorCommunicationError,
+///     }
+/// }
+///
+/// impl UserClient {
+///     pub async fn create_user(&self, params: UserCreate) -> Result<UserId, UserError> {
+///         self.inner.create(params).await.map_err(Self::map_error)
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! define_client {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            entity: $entity:ty,
+            error: $error:ty,
+            fallback: $fallback:path
+            $(, fields: { $($field:ident: $field_ty:ty),* $(,)? })?
+            $(, state: { $($state:ident: $state_ty:ty),* $(,)? })? $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug)]
+        $vis struct $name {
+            inner: $crate::ResourceClient<$entity>,
+            $($($field: $field_ty,)*)?
+            $($($state: $state_ty,)*)?
+        }
+
+        impl $name {
+            pub fn new(
+                inner: $crate::ResourceClient<$entity>,
+                $($($field: $field_ty,)*)?
+            ) -> Self {
+                Self {
+                    inner,
+                    $($($field,)*)?
+                    $($($state: ::std::default::Default::default(),)*)?
+                }
+            }
+        }
+
+        #[$crate::__private::async_trait]
+        impl $crate::ActorClient<$entity> for $name {
+            type Error = $error;
+
+            fn inner(&self) -> &$crate::ResourceClient<$entity> {
+                &self.inner
+            }
+
+            fn map_error(e: $crate::FrameworkError) -> Self::Error {
+                match e {
+                    $crate::FrameworkError::EntityError {
+                        entity_type,
+                        source,
+                    } => match source.downcast::<$error>() {
+                        Ok(error) => *error,
+                        Err(source) => $fallback(
+                            $crate::FrameworkError::EntityError {
+                                entity_type,
+                                source,
+                            }
+                            .to_string(),
+                        ),
+                    },
+                    e => $fallback(e.to_string()),
+                }
+            }
+        }
+    };
+}
+
+/// Declares [`TypedAction`](crate::TypedAction) types for an entity's actions.
+///
+/// For each entry it generates a struct named after the action variant (with its fields as
+/// public tuple fields), a `From` conversion into the variant, and a `TypedAction` impl that
+/// takes the output out of the matching result variant. Each entry names the variant and its
+/// fields, then the result pattern, the output type and the value to return from it:
+///
+/// ```rust,ignore
+/// actor_framework::typed_actions! {
+///     Product, ProductAction, ProductActionResult;
+///     /// [`ProductAction::CheckStock`], returning the stock level.
+///     CheckStock => ProductActionResult::CheckStock(level) => u32 = level;
+///     /// [`ProductAction::ReserveStock`] of a quantity for an order.
+///     ReserveStock(order_id: OrderId, quantity: u32) => ProductActionResult::ReserveStock(()) => () = ();
+/// }
+/// ```
+///
+/// A client method then calls
+/// [`ResourceClient::typed_action`](crate::ResourceClient::typed_action) with the struct and
+/// gets the output directly, e.g. `inner.typed_action(id, CheckStock).await` returns `u32`.
+#[macro_export]
+macro_rules! typed_actions {
+    (
+        $entity:ty, $action:ident, $action_result:ty;
+        $(
+            $(#[$meta:meta])*
+            $name:ident $(($($field:ident: $field_ty:ty),*))? => $result:pat => $output:ty = $value:expr;
+        )*
+    ) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone)]
+            pub struct $name $(($(pub $field_ty),*))?;
+
+            impl From<$name> for $action {
+                fn from(action: $name) -> Self {
+                    let $name $(($($field),*))? = action;
+                    $action::$name $(($($field),*))?
+                }
+            }
+
+            impl $crate::TypedAction<$entity> for $name {
+                type Output = $output;
+
+                fn extract(result: $action_result) -> Result<$output, $action_result> {
+                    match result {
+                        $result => Ok($value),
+                        other => Err(other),
+                    }
+                }
+            }
+        )*
+    };
+}
@@ -0,0 +1,111 @@
+//! # Clock
+//!
+//! Entities that reason about time (expiry, rate limits, "created N minutes ago") should not
+//! call `Instant::now()` directly: a test would then have to sleep for real to observe the
+//! passage of time. Instead, put a clock in the actor's `Context` and read the time through it.
+//!
+//! ## Pattern
+//!
+//! - Bound the context on [`HasClock`] and call `ctx.now()` inside hooks.
+//! - In production, pass [`SystemClock`] (or a context struct that holds one).
+//! - In tests, pass a [`TestClock`] and [`advance`](TestClock::advance) it explicitly.
+//!
+//! ```rust,ignore
+//! #[async_trait]
+//! impl ActorEntity for Session {
+//!     type Context = TestClock; // or SystemClock, or any `C: HasClock`
+//!     ...
+//!     async fn on_create(&mut self, ctx: &Self::Context) -> Result<(), Self::Error> {
+//!         self.created_at = ctx.now();
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let clock = TestClock::new();
+//! tokio::spawn(actor.run(clock.clone()));
+//! clock.advance(Duration::from_secs(15 * 60));
+//! ```
+//!
+//! A context that carries other dependencies as well implements `HasClock` by delegating to
+//! its clock field.
+//!
+//! ## TTL expiry
+//!
+//! An actor measures TTLs ([`ActorEntity::ttl`](crate::ActorEntity::ttl)) on the clock given
+//! to [`ResourceActor::with_clock`](crate::ResourceActor::with_clock), the system clock by
+//! default. With a `TestClock`, an entity expires once the clock is advanced past its TTL, on
+//! the next tick of the expiry interval, and never before, however long the test runs:
+//!
+//! ```rust,ignore
+//! let clock = TestClock::new();
+//! let (actor, client) = ResourceActor::<Session>::new(10);
+//! tokio::spawn(actor.with_clock(clock.clone()).run(()));
+//! clock.advance(Duration::from_secs(15 * 60));
+//! client.wait_for_count(0).await?;
+//! ```
+//!
+//! The framework's other timers (the expiry interval itself, deadlines, timeouts) still run on
+//! Tokio's clock.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A source of the current time, exposed by an actor's `Context`.
+pub trait HasClock {
+    /// Returns the current instant according to this clock.
+    fn now(&self) -> Instant;
+}
+
+/// The real clock, backed by [`Instant::now`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl HasClock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A manually driven clock for deterministic tests.
+///
+/// Time starts at the instant the clock was created and only moves when
+/// [`TestClock::advance`] is called. Clones share the same time, so the test can keep one
+/// handle while the actor holds another in its context.
+#[derive(Clone, Debug)]
+pub struct TestClock {
+    start: Instant,
+    elapsed: Arc<Mutex<Duration>>,
+}
+
+impl TestClock {
+    /// Creates a clock frozen at the current instant.
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            elapsed: Arc::new(Mutex::new(Duration::ZERO)),
+        }
+    }
+
+    /// Moves the clock forward by `duration` for every clone.
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().unwrap() += duration;
+    }
+}
+
+impl Default for TestClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HasClock for TestClock {
+    fn now(&self) -> Instant {
+        self.start + *self.elapsed.lock().unwrap()
+    }
+}
+
+impl<C: HasClock> HasClock for Arc<C> {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+}
@@ -17,14 +17,34 @@
 //! # Provided Methods (Hooks)
 //! This trait includes **Provided Methods** (methods with default implementations) for lifecycle hooks:
 //! - [`ActorEntity::on_create`]
+//! - [`ActorEntity::on_update_with_prev`]
 //! - [`ActorEntity::on_delete`]
+//! - [`ActorEntity::on_stop`]
+//! - [`ActorEntity::on_stop_with_reason`]
 //!
 //! You do **not** need to implement these methods unless you want to customize behavior.
 //! The default implementation does nothing (`Ok(())`).
 
+use crate::error::FrameworkError;
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
+use std::time::Duration;
+
+/// Why an actor's run loop exited, passed to [`ActorEntity::on_stop_with_reason`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    /// The last `ResourceClient` clone was dropped, closing the channel. Dropping any other
+    /// clone does not stop the actor.
+    AllClientsDropped,
+    /// A [`PriorityClient::shutdown`](crate::PriorityClient::shutdown) stopped the actor while
+    /// clients were still connected.
+    Shutdown,
+    /// The actor panicked with no restarts left under
+    /// [`ResourceActor::run_supervised`](crate::ResourceActor::run_supervised).
+    Crashed,
+}
 
 /// Trait that any resource entity must implement to be managed by ResourceActor.
 ///
@@ -77,10 +97,30 @@ pub trait ActorEntity: Clone + Send + Sync + 'static {
     /// the massive reduction in code complexity.
     type Error: std::error::Error + Send + Sync + 'static;
 
+    /// A stable, human-readable label for this entity type, used as the `entity_type` field in logs.
+    ///
+    /// The default derives it from [`std::any::type_name`], dropping the module path and any
+    /// generic parameters (e.g. `my_app::model::User` becomes `User`). Override it to pin an
+    /// author-chosen label that is unaffected by module moves or wrapper types.
+    fn entity_name() -> &'static str {
+        let full = std::any::type_name::<Self>();
+        let base = full.split('<').next().unwrap_or(full);
+        base.rsplit("::").next().unwrap_or(base)
+    }
+
     /// Construct the full Entity from the ID and Payload.
     /// This is called synchronously before `on_create`.
     fn from_create_params(id: Self::Id, params: Self::Create) -> Result<Self, Self::Error>;
 
+    /// The id of this entity, which must be the id `from_create_params` was given.
+    ///
+    /// In debug builds the actor checks this on every create, before `on_create`, so an entity
+    /// that keeps a placeholder id (e.g. one built with a `new` that sets id `0`) fails fast
+    /// instead of running its hook and being stored under a key that differs from its own id. A `Replace` or `PatchJson` that would
+    /// store an entity with another id under this one fails with `FrameworkError::Validation`.
+    /// Since every entity knows its id, a `List` result needs no separate keys.
+    fn id(&self) -> &Self::Id;
+
     // --- Lifecycle Hooks (Async) ---
 
     /// Called immediately after the entity is created and initialized.
@@ -90,17 +130,138 @@ pub trait ActorEntity: Clone + Send + Sync + 'static {
     }
 
     /// Called when an update request is received.
+    ///
+    /// Returns whether the entity actually changed. The entity knows best whether a patch
+    /// was a no-op (e.g. it set a field to its current value): the actor logs a changed
+    /// entity as "Updated" at `info`, and an unchanged one only at `debug`.
     async fn on_update(
         &mut self,
         update: Self::Update,
         _ctx: &Self::Context,
-    ) -> Result<(), Self::Error>;
+    ) -> Result<bool, Self::Error>;
+
+    /// Called when an update request is received, with a snapshot of the entity taken
+    /// before the update was applied.
+    ///
+    /// Override this instead of [`ActorEntity::on_update`] to compute diffs or write audit
+    /// logs (e.g. "email changed from X to Y"). The default ignores `prev` and delegates to
+    /// `on_update`. If the hook returns an error, the actor restores `prev`.
+    async fn on_update_with_prev(
+        &mut self,
+        update: Self::Update,
+        _prev: &Self,
+        ctx: &Self::Context,
+    ) -> Result<bool, Self::Error> {
+        self.on_update(update, ctx).await
+    }
+
+    /// Called after a `Replace` or a JSON patch swapped in a new value for the entity, with
+    /// the value it replaced.
+    ///
+    /// Neither goes through `on_update`, so this is where state kept outside the entity (e.g.
+    /// an index in the context) follows the change. If the hook returns an error, the actor
+    /// restores `prev`.
+    async fn on_replace(&mut self, _prev: &Self, _ctx: &Self::Context) -> Result<(), Self::Error> {
+        Ok(())
+    }
 
     /// Called immediately before the entity is removed from the system.
     async fn on_delete(&self, _ctx: &Self::Context) -> Result<(), Self::Error> {
         Ok(())
     }
 
+    /// Called when the actor's run loop starts, before the first request is read.
+    ///
+    /// Receives the store as the loop finds it: the entities the actor was built with, and
+    /// empty after a supervised restart. Rebuild state kept in the context from it here, so it
+    /// cannot outlive a crashed run. An error is logged and the loop starts anyway.
+    async fn on_start(
+        _store: &HashMap<Self::Id, Self>,
+        _ctx: &Self::Context,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called once when the actor's run loop exits (channel closed, shutdown, or a supervised
+    /// actor out of restarts).
+    ///
+    /// Receives the final contents of the store, making this the place to persist a
+    /// snapshot or emit a final metric before the in-memory state is dropped.
+    async fn on_stop(
+        _store: &HashMap<Self::Id, Self>,
+        _ctx: &Self::Context,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called once when the actor's run loop exits, with the reason it stopped.
+    ///
+    /// Override this instead of [`ActorEntity::on_stop`] to act only on some exits, e.g.
+    /// persist a snapshot when the last client is dropped
+    /// ([`StopReason::AllClientsDropped`]) but not on an administrative shutdown. The
+    /// default ignores `reason` and delegates to `on_stop`.
+    async fn on_stop_with_reason(
+        store: &HashMap<Self::Id, Self>,
+        _reason: StopReason,
+        ctx: &Self::Context,
+    ) -> Result<(), Self::Error> {
+        Self::on_stop(store, ctx).await
+    }
+
+    /// How long the entity lives after it is created, or `None` (the default) to keep it
+    /// until it is deleted.
+    ///
+    /// Read once, right after `on_create`. When the TTL elapses the actor removes the entity
+    /// on its next expiry tick (see [`ResourceActor::with_expiry_interval`]), calling
+    /// `on_delete` first. Useful for ephemeral resources such as sessions or reservations.
+    ///
+    /// The TTL is measured on the actor's clock (see [`ResourceActor::with_clock`]), so a test
+    /// can expire the entity by advancing a [`TestClock`](crate::TestClock).
+    ///
+    /// [`ResourceActor::with_expiry_interval`]: crate::ResourceActor::with_expiry_interval
+    /// [`ResourceActor::with_clock`]: crate::ResourceActor::with_clock
+    fn ttl(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Whether this entity is a soft-deleted tombstone.
+    ///
+    /// A tombstone stays in the store but is hidden from every read and write except
+    /// [`ResourceClient::get_including_deleted`](crate::ResourceClient::get_including_deleted).
+    /// The default, `false`, never hides anything.
+    fn is_deleted(&self) -> bool {
+        false
+    }
+
+    /// Turns the entity into a tombstone instead of letting a delete remove it.
+    ///
+    /// Called after `on_delete` succeeds. Set a marker (e.g. `deleted_at`) that makes
+    /// [`ActorEntity::is_deleted`] return `true`, and return `true` to keep the entity. The
+    /// default returns `false`, so the entity is removed from the store (hard delete).
+    fn mark_deleted(&mut self) -> bool {
+        false
+    }
+
+    /// Checks an entity that was assembled without `from_create_params` or a hook: one passed
+    /// to `Replace`, or produced by a JSON merge patch (the `json-patch` feature). An error
+    /// rejects the write and leaves the stored entity unchanged. The default accepts
+    /// everything.
+    fn validate(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Converts an error returned by this entity's constructor or hooks into the
+    /// `FrameworkError` the caller receives.
+    ///
+    /// The default wraps it in [`FrameworkError::EntityError`], which typed clients unwrap back
+    /// into `Self::Error`. Override it to surface an error as its framework-level equivalent,
+    /// e.g. the entity's own "not found" as [`FrameworkError::NotFound`], so generic callers
+    /// (retries, HTTP status mapping) treat it like the actor's. A panicking hook is still
+    /// reported as a [`HookPanic`](crate::HookPanic) entity error.
+    fn into_framework_error(error: Self::Error) -> FrameworkError {
+        FrameworkError::entity(Self::entity_name(), error)
+    }
+
     // --- Action Handler (Async) ---
 
     /// Handle a custom resource-specific action.
@@ -110,3 +271,35 @@ pub trait ActorEntity: Clone + Send + Sync + 'static {
         _ctx: &Self::Context,
     ) -> Result<Self::ActionResult, Self::Error>;
 }
+
+/// An entity that can be read as a lighter projection, e.g. a few fields of a large entity.
+///
+/// [`ResourceClient::get_projection`](crate::ResourceClient::get_projection) sends back only
+/// the projection instead of a clone of the whole entity. A separate trait rather than an
+/// associated type of [`ActorEntity`] (which can't have a default on stable Rust), so entities
+/// without a projection are unaffected.
+pub trait Project: ActorEntity {
+    /// The projected view of the entity.
+    type Projection: Debug + Send + 'static;
+
+    /// Builds the projection of this entity.
+    fn project(&self) -> Self::Projection;
+}
+
+/// One of an entity's actions, typed with the result it produces.
+///
+/// `T::Action` and `T::ActionResult` are enums, so a plain
+/// [`ResourceClient::perform_action`](crate::ResourceClient::perform_action) caller has to
+/// match the result variant it expects. Implement this for a type per action (e.g. a
+/// `CheckStock` struct converting into `ProductAction::CheckStock`) and
+/// [`ResourceClient::typed_action`](crate::ResourceClient::typed_action) returns the
+/// [`Output`](TypedAction::Output) directly. [`typed_actions!`](crate::typed_actions)
+/// declares such types for several actions at once.
+pub trait TypedAction<T: ActorEntity>: Into<T::Action> {
+    /// What the action returns, e.g. `u32` for a stock level.
+    type Output;
+
+    /// Takes the output out of the action's result, or hands back a result that belongs to
+    /// another action.
+    fn extract(result: T::ActionResult) -> Result<Self::Output, T::ActionResult>;
+}
@@ -3,6 +3,13 @@
 //! This module defines the common error types used throughout the actor framework.
 //! By centralizing error definitions, we ensure consistent error handling across
 //! all actors and clients.
+//!
+//! [`FrameworkError`] cannot be serialized (an entity error is an arbitrary boxed error), so a
+//! transport in front of the actors (HTTP, gRPC) sends [`WireError`] instead, obtained with
+//! [`FrameworkError::to_wire`].
+
+use crate::validate::ValidationError;
+use serde::{Deserialize, Serialize};
 
 /// Errors that can occur within the actor framework itself.
 #[derive(Debug, thiserror::Error)]
@@ -11,8 +18,125 @@ pub enum FrameworkError {
     ActorClosed,
     #[error("Actor dropped response channel")]
     ActorDropped,
+    #[error("Timed out waiting for the actor")]
+    Timeout,
+    #[error("Actor channel is full")]
+    Full,
     #[error("Item not found: {0}")]
     NotFound(String),
-    #[error("Entity error: {0}")]
-    EntityError(Box<dyn std::error::Error + Send + Sync>),
+    #[error("Actor is shutting down")]
+    ShuttingDown,
+    #[error("Entity {0} changed since it was read")]
+    VersionConflict(String),
+    #[error("Batch of {size} requests exceeds the maximum of {max}")]
+    BatchTooLarge { size: usize, max: usize },
+    #[error("Actor has no ids left to assign")]
+    IdExhausted,
+    /// A create payload failed [`Validate`](crate::Validate) in a
+    /// [validating](crate::ResourceClient::validating) client and was never sent.
+    #[error("Invalid input: {0}")]
+    Validation(ValidationError),
+    /// An entity hook failed; `entity_type` is the [`ActorEntity::entity_name`] of the actor,
+    /// so a log line tells which of several actors raised it. The entity's own error is the
+    /// [`source`](std::error::Error::source), so error reporters can walk the cause chain.
+    ///
+    /// [`ActorEntity::entity_name`]: crate::ActorEntity::entity_name
+    #[error("{entity_type} entity error: {source}")]
+    EntityError {
+        entity_type: &'static str,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl FrameworkError {
+    /// Wraps an error raised by a hook of the `entity_type` actor.
+    pub fn entity(
+        entity_type: &'static str,
+        error: impl std::error::Error + Send + Sync + 'static,
+    ) -> Self {
+        Self::EntityError {
+            entity_type,
+            source: Box::new(error),
+        }
+    }
+
+    /// Returns the serializable form of this error: a stable [`ErrorCode`] and the display
+    /// message. An entity error keeps only its message.
+    pub fn to_wire(&self) -> WireError {
+        let code = match self {
+            Self::ActorClosed => ErrorCode::ActorClosed,
+            Self::ActorDropped => ErrorCode::ActorDropped,
+            Self::Timeout => ErrorCode::Timeout,
+            Self::Full => ErrorCode::Full,
+            Self::NotFound(_) => ErrorCode::NotFound,
+            Self::ShuttingDown => ErrorCode::ShuttingDown,
+            Self::VersionConflict(_) => ErrorCode::VersionConflict,
+            Self::BatchTooLarge { .. } => ErrorCode::BatchTooLarge,
+            Self::IdExhausted => ErrorCode::IdExhausted,
+            Self::Validation(_) => ErrorCode::Validation,
+            Self::EntityError { .. } => ErrorCode::Entity,
+        };
+        WireError {
+            code,
+            message: self.to_string(),
+        }
+    }
 }
+
+/// Stable, transport-independent identifier of a [`FrameworkError`] variant.
+///
+/// Serialized as `SCREAMING_SNAKE_CASE` strings (e.g. `"NOT_FOUND"`). Existing codes are
+/// never renamed, so remote callers can match on them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    ActorClosed,
+    ActorDropped,
+    Timeout,
+    Full,
+    NotFound,
+    BatchTooLarge,
+    Entity,
+    VersionConflict,
+    ShuttingDown,
+    IdExhausted,
+    Validation,
+}
+
+impl ErrorCode {
+    /// The HTTP status an HTTP gateway should answer with for this code.
+    ///
+    /// Overload (`Full`) and a stopped or draining actor are retryable (`503`), a deadline is
+    /// `504`, a conditional update that lost a race is a conflict (`409`), invalid input is a
+    /// bad request (`400`), and an entity error
+    /// is assumed to be a rejected request (`422`); entity types that need finer statuses
+    /// should map their own error before it is wrapped.
+    pub fn http_status(self) -> u16 {
+        match self {
+            Self::Validation => 400,
+            Self::NotFound => 404,
+            Self::VersionConflict => 409,
+            Self::BatchTooLarge => 413,
+            Self::Entity => 422,
+            Self::ActorDropped | Self::IdExhausted => 500,
+            Self::ActorClosed | Self::Full | Self::ShuttingDown => 503,
+            Self::Timeout => 504,
+        }
+    }
+}
+
+/// A [`FrameworkError`] in serializable form, from [`FrameworkError::to_wire`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WireError {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+/// A lifecycle hook panicked while the actor was running via
+/// [`ResourceActor::run_resilient`](crate::ResourceActor::run_resilient).
+///
+/// Delivered to the caller wrapped in [`FrameworkError::EntityError`].
+#[derive(Debug, thiserror::Error)]
+#[error("Hook panicked: {0}")]
+pub struct HookPanic(pub String);
@@ -0,0 +1,32 @@
+//! # Resource Events
+//!
+//! A [`ResourceEvent`] records one change to an actor's store as the resulting state, not as
+//! the request that caused it. A log of events can be persisted and later replayed with
+//! [`ResourceActor::from_events`](crate::ResourceActor::from_events) to rebuild the store,
+//! e.g. after a restart.
+//!
+//! Events carry full entities so replay never re-runs hooks: validation and side effects
+//! (reserving stock, calling other actors) happened when the change was first made, and
+//! must not happen again.
+
+use crate::entity::ActorEntity;
+
+/// One change to an actor's store.
+#[derive(Clone, Debug)]
+pub enum ResourceEvent<T: ActorEntity> {
+    /// An entity was created with this state.
+    Created { id: T::Id, entity: T },
+    /// An existing entity now has this state.
+    Updated { id: T::Id, entity: T },
+    /// An entity was removed.
+    Deleted { id: T::Id },
+}
+
+impl<T: ActorEntity> ResourceEvent<T> {
+    /// Returns the id of the entity the event is about.
+    pub fn id(&self) -> &T::Id {
+        match self {
+            Self::Created { id, .. } | Self::Updated { id, .. } | Self::Deleted { id } => id,
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! # Bounded Stores
+//!
+//! With [`ResourceActor::with_capacity_limit`](crate::ResourceActor::with_capacity_limit),
+//! the actor holds at most `max` entities, which makes it usable as a bounded cache: a create
+//! that takes the store past the limit evicts the entity the [`EvictionPolicy`] picks, after
+//! calling its `on_delete`. Each eviction is logged ("Evicted"), counted in
+//! [`ActorStats::evictions`](crate::ActorStats::evictions) and, when auditing, sent as an
+//! [`AuditEntry`](crate::AuditEntry) with operation `Evict`, just before the entry of the
+//! create that caused it.
+//!
+//! Under [`EvictionPolicy::Lru`] a request addressing one entity by id (a `get`, `update`,
+//! action, ...) counts as a use; listing, `get_many` and counting do not.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// Which entity a full store evicts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EvictionPolicy {
+    /// The least recently used one.
+    Lru,
+    /// The least recently created one, however often it is used.
+    Fifo,
+}
+
+/// The stored ids in eviction order.
+pub(crate) struct CapacityLimit<Id> {
+    max: usize,
+    policy: EvictionPolicy,
+    /// Position of each stored id in `order`.
+    stamps: HashMap<Id, u64>,
+    /// Ids by last use (LRU) or creation (FIFO), next to evict first.
+    order: BTreeMap<u64, Id>,
+    next_stamp: u64,
+}
+
+impl<Id: Eq + Hash + Clone> CapacityLimit<Id> {
+    /// A limit of `max` entities, at least one.
+    pub(crate) fn new(max: usize, policy: EvictionPolicy) -> Self {
+        Self {
+            max: max.max(1),
+            policy,
+            stamps: HashMap::new(),
+            order: BTreeMap::new(),
+            next_stamp: 0,
+        }
+    }
+
+    pub(crate) fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Records a newly stored id as the last to evict.
+    pub(crate) fn insert(&mut self, id: Id) {
+        self.remove(&id);
+        let stamp = self.next_stamp;
+        self.next_stamp += 1;
+        self.stamps.insert(id.clone(), stamp);
+        self.order.insert(stamp, id);
+    }
+
+    /// Records a use of a stored id; only moves it under [`EvictionPolicy::Lru`].
+    pub(crate) fn touch(&mut self, id: &Id) {
+        if self.policy == EvictionPolicy::Lru && self.stamps.contains_key(id) {
+            self.insert(id.clone());
+        }
+    }
+
+    /// Forgets an id removed from the store.
+    pub(crate) fn remove(&mut self, id: &Id) {
+        if let Some(stamp) = self.stamps.remove(id) {
+            self.order.remove(&stamp);
+        }
+    }
+
+    /// Takes the id to evict next.
+    pub(crate) fn pop(&mut self) -> Option<Id> {
+        let (_, id) = self.order.pop_first()?;
+        self.stamps.remove(&id);
+        Some(id)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.stamps.clear();
+        self.order.clear();
+    }
+}
@@ -0,0 +1,73 @@
+//! # JSON Merge Patches
+//!
+//! Requires the `json-patch` feature. A [`JsonPatch`] changes an entity from an untyped JSON
+//! object instead of its [`ActorEntity::Update`](crate::ActorEntity::Update) DTO, for callers
+//! such as a generic admin UI that cannot know every entity's update type.
+//!
+//! The patch follows JSON Merge Patch (RFC 7396): the entity is serialized, each field of
+//! the patch replaces the field of the same name (objects are merged recursively, `null`
+//! removes a field), and the result is deserialized back into the entity and checked with
+//! [`ActorEntity::validate`](crate::ActorEntity::validate). The typed `Update` path stays
+//! the default: a patch bypasses `on_update`, and a field it sets wrongly is only caught at
+//! run time.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+/// A JSON merge patch for an entity of type `T`.
+///
+/// Built where `T: Serialize + DeserializeOwned` is known, so the actor, which only sees
+/// `T: ActorEntity`, can apply it.
+pub struct JsonPatch<T> {
+    patch: Value,
+    apply: fn(&T, &Value) -> Result<T, serde_json::Error>,
+}
+
+impl<T: Serialize + DeserializeOwned> JsonPatch<T> {
+    pub fn new(patch: Value) -> Self {
+        Self {
+            patch,
+            apply: |entity, patch| {
+                let mut value = serde_json::to_value(entity)?;
+                merge(&mut value, patch);
+                serde_json::from_value(value)
+            },
+        }
+    }
+}
+
+impl<T> JsonPatch<T> {
+    /// Returns `entity` with the patch merged in, or the error that made the result
+    /// unreadable as a `T` (e.g. a field of the wrong type).
+    pub fn apply(&self, entity: &T) -> Result<T, serde_json::Error> {
+        (self.apply)(entity, &self.patch)
+    }
+}
+
+impl<T> std::fmt::Debug for JsonPatch<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("JsonPatch").field(&self.patch).finish()
+    }
+}
+
+/// Merges `patch` into `target` as described by RFC 7396.
+fn merge(target: &mut Value, patch: &Value) {
+    let Value::Object(fields) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Default::default());
+    }
+    let Value::Object(target) = target else {
+        unreachable!("target was just made an object")
+    };
+    for (name, value) in fields {
+        if value.is_null() {
+            target.remove(name);
+        } else {
+            merge(target.entry(name.as_str()).or_insert(Value::Null), value);
+        }
+    }
+}
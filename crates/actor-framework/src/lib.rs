@@ -91,10 +91,11 @@
 //!     fn from_create_params(id: u32, params: UserCreate) -> Result<Self, Self::Error> {
 //!         Ok(Self { id, name: params.name })
 //!     }
+//!     fn id(&self) -> &u32 { &self.id }
 //!
-//!     async fn on_update(&mut self, update: UserUpdate, _ctx: &Self::Context) -> Result<(), Self::Error> {
+//!     async fn on_update(&mut self, update: UserUpdate, _ctx: &Self::Context) -> Result<bool, Self::Error> {
 //!         if let Some(name) = update.name { self.name = name; }
-//!         Ok(())
+//!         Ok(true)
 //!     }
 //!
 //!     async fn handle_action(&mut self, _: UserAction, _: &Self::Context) -> Result<(), Self::Error> {
@@ -142,7 +143,8 @@
 //!     type Id = u32; type Create = UserCreate; type Update = UserUpdate; type Action = UserAction;
 //!     type ActionResult = (); type Context = (); type Error = UserError;
 //!     fn from_create_params(id: u32, _: UserCreate) -> Result<Self, Self::Error> { Ok(Self { id }) }
-//!     async fn on_update(&mut self, _: UserUpdate, _: &()) -> Result<(), Self::Error> { Ok(()) }
+//!     fn id(&self) -> &u32 { &self.id }
+//!     async fn on_update(&mut self, _: UserUpdate, _: &()) -> Result<bool, Self::Error> { Ok(true) }
 //!     async fn handle_action(&mut self, _: UserAction, _: &()) -> Result<(), Self::Error> { Ok(()) }
 //! }
 //!
@@ -160,7 +162,8 @@
 //! #     type Id = u32; type Create = ProductCreate; type Update = ProductUpdate; type Action = ProductAction;
 //! #     type ActionResult = (); type Context = (); type Error = ProductError;
 //! #     fn from_create_params(id: u32, _: ProductCreate) -> Result<Self, Self::Error> { Ok(Self { id }) }
-//! #     async fn on_update(&mut self, _: ProductUpdate, _: &()) -> Result<(), Self::Error> { Ok(()) }
+//! #     fn id(&self) -> &u32 { &self.id }
+//! #     async fn on_update(&mut self, _: ProductUpdate, _: &()) -> Result<bool, Self::Error> { Ok(true) }
 //! #     async fn handle_action(&mut self, _: ProductAction, _: &()) -> Result<(), Self::Error> { Ok(()) }
 //! # }
 //!
@@ -182,7 +185,8 @@
 //!     type ActionResult = (); type Context = OrderContext; type Error = OrderError;
 //!
 //!     fn from_create_params(id: u32, _: OrderCreate) -> Result<Self, Self::Error> { Ok(Self { id }) }
-//!     async fn on_update(&mut self, _: OrderUpdate, _: &OrderContext) -> Result<(), Self::Error> { Ok(()) }
+//!     fn id(&self) -> &u32 { &self.id }
+//!     async fn on_update(&mut self, _: OrderUpdate, _: &OrderContext) -> Result<bool, Self::Error> { Ok(true) }
 //!     async fn handle_action(&mut self, _: OrderAction, _: &OrderContext) -> Result<(), Self::Error> { Ok(()) }
 //!     // In a real app, on_create would use the context to validate user/product
 //! }
@@ -228,18 +232,60 @@
 //! The framework provides a **MockClient** type that implements the same `ResourceClient<T>` API as the real client but operates entirely in‑memory. It lets you write fast, deterministic unit tests for client logic (e.g. `OrderClient`) without spawning any actors. See the [`mock`] module for the full API and usage patterns.
 
 pub mod actor;
+pub mod audit;
+pub mod cached;
 pub mod client;
 pub mod client_trait;
+pub mod clock;
 pub mod entity;
 pub mod error;
+pub mod event;
+pub mod eviction;
+#[cfg(feature = "json-patch")]
+pub mod json_patch;
 pub mod message;
 pub mod mock;
+pub mod priority;
+pub mod request_context;
+pub mod saga;
+pub mod schedule;
+pub mod sharded;
+pub mod stats;
 pub mod tracing;
+pub mod validate;
 
 // Re-export core types for convenience
 pub use actor::ResourceActor;
-pub use client::ResourceClient;
+pub use audit::AuditEntry;
+pub use cached::CachedClient;
+pub use client::{EntityStream, Pipeline, Reply, ResourceClient, CAPACITY_WARNING_INTERVAL};
 pub use client_trait::ActorClient;
-pub use entity::ActorEntity;
-pub use error::FrameworkError;
-pub use message::{ResourceRequest, Response};
+pub use clock::{HasClock, SystemClock, TestClock};
+pub use entity::{ActorEntity, Project, StopReason, TypedAction};
+pub use error::{ErrorCode, FrameworkError, HookPanic, WireError};
+pub use event::ResourceEvent;
+pub use eviction::EvictionPolicy;
+#[cfg(feature = "json-patch")]
+pub use json_patch::JsonPatch;
+pub use message::{
+    Expected, Predicate, ProjectionResponse, RequestKind, ResourceRequest, Response,
+};
+pub use priority::PriorityClient;
+pub use request_context::RequestContext;
+pub use saga::Saga;
+pub use sharded::ShardedClient;
+pub use stats::ActorStats;
+pub use validate::{Validate, ValidationError};
+
+/// Derive macro generating the `ActorEntity` boilerplate (requires the `derive` feature).
+///
+/// See the `actor-framework-derive` crate for the supported `#[actor(...)]` attributes.
+#[cfg(feature = "derive")]
+pub use actor_framework_derive::ActorEntity;
+
+/// Implementation details used by the generated code of the derive macro and
+/// [`define_client!`]. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use async_trait::async_trait;
+}
@@ -3,9 +3,15 @@
 //! This module defines the generic message types used for communication between
 //! the `ResourceClient` and `ResourceActor`.
 
-use crate::entity::ActorEntity;
+use crate::entity::{ActorEntity, Project};
 use crate::error::FrameworkError;
+#[cfg(feature = "json-patch")]
+use crate::json_patch::JsonPatch;
+use crate::request_context::RequestContext;
+use crate::stats::ActorStats;
+use std::collections::HashMap;
 use tokio::sync::oneshot;
+use tokio::time::Instant;
 
 /// Type alias for the one-shot response channel used by actors.
 pub type Response<T> = oneshot::Sender<Result<T, FrameworkError>>;
@@ -22,10 +28,46 @@ pub type Response<T> = oneshot::Sender<Result<T, FrameworkError>>;
 /// plus a custom `Action` variant for resource-specific logic that doesn't fit the CRUD model.
 ///
 /// - **Create**: Lifecycle start. Uses [`ActorEntity::Create`] to initialize a new resource.
+///   `CreateReturning` does the same but responds with the stored entity instead of its id,
+///   and `CreateCancelSafe` undoes the create if its caller is gone by the time it replies.
 /// - **Get (Read)**: Retrieval. Fetches the current state of the resource by ID.
-/// - **Update**: State mutation. Uses [`ActorEntity::Update`] to modify an existing resource.
-/// - **Delete**: Lifecycle end. Removes the resource.
-/// - **Action**: Extensibility. Executes a custom [`ActorEntity::Action`].
+///   `GetProjection` sends back only its [`Project::Projection`](crate::entity::Project),
+///   `GetMap` fetches several resources keyed by ID, and `GetIncludingDeleted` also returns a
+///   soft-deleted tombstone (see [`ActorEntity::is_deleted`]).
+/// - **Update**: State mutation. Uses [`ActorEntity::Update`] to modify an existing resource,
+///   optionally only if it still equals an [`Expected`] snapshot (optimistic locking).
+/// - **Replace**: Full overwrite (PUT semantics). Swaps an existing resource for a complete new value
+///   and calls `on_replace`.
+/// - **PatchJson** (`json-patch` feature only): Untyped update. Merges a [`JsonPatch`] into an
+///   existing resource and validates the result (see [`json_patch`](crate::json_patch)).
+/// - **Delete**: Lifecycle end. Removes the resource, or keeps it as a tombstone if it supports
+///   soft-delete (see [`ActorEntity::mark_deleted`]). `DeleteReturning` also responds with the
+///   deleted resource, e.g. for an audit record or an undo.
+/// - **Action**: Extensibility. Executes a custom [`ActorEntity::Action`]. `ActionReturning` also
+///   responds with the entity as the action left it, saving a `Get` afterwards.
+/// - **NotifyAction**: Fire-and-forget action. Executes an [`ActorEntity::Action`] without replying.
+/// - **ActionMany**: Batched actions. Executes several actions in one actor turn, with a result per item.
+/// - **ActionAtomic**: All-or-nothing batch. Like `ActionMany`, but a failing action undoes the others.
+/// - **List / Count**: Collection reads. Return every resource (or just how many) held by the actor.
+///   `ListPaginated` returns one page of them, for consumers that must bound memory, and
+///   `FindWhere` only those matching a [`Predicate`].
+/// - **Pipeline**: Contiguous execution. Runs several requests back to back with no other client's
+///   request in between (see [`Pipeline`](crate::client::Pipeline)).
+/// - **WithContext**: Per-request data. Handles the wrapped request with a [`RequestContext`] visible
+///   to its hooks (see [`request_context`](crate::request_context)).
+/// - **WithDeadline**: Scheduling. Handles the wrapped request ahead of queued requests with a
+///   later or no deadline, if the actor schedules by deadline (see [`schedule`](crate::schedule)).
+/// - **NotifyCount**: Waiting. Replies once the actor holds exactly `target` resources, instead
+///   of the caller polling `Count`.
+/// - **Ping**: Liveness. Replies immediately, confirming the actor's loop is running.
+/// - **Stats**: Observability. Returns the actor's runtime counters ([`ActorStats`]).
+/// - **Pause / Resume**: Control. Stop and restart handling requests from the normal channel,
+///   which hold there meanwhile (see [`PriorityClient::pause`](crate::PriorityClient::pause)).
+/// - **BeginDraining**: Control. Makes the actor reject every later write with
+///   [`FrameworkError::ShuttingDown`] while still serving reads.
+/// - **Shutdown**: Control. Stops the actor once `on_stop` has run, without draining its queue
+///   (see [`PriorityClient`](crate::PriorityClient)).
+/// - **Inspect** (`testing` feature only): Returns the stored resource as-is, bypassing every hook.
 ///
 /// # Entity Interaction
 /// This type is generic over `T: ActorEntity`. It uses the associated types defined in the [`ActorEntity`] trait
@@ -37,20 +79,409 @@ pub enum ResourceRequest<T: ActorEntity> {
         params: T::Create,
         respond_to: Response<T::Id>,
     },
+    CreateReturning {
+        params: T::Create,
+        respond_to: Response<T>,
+    },
+    CreateCancelSafe {
+        params: T::Create,
+        respond_to: Response<T::Id>,
+    },
     Get {
         id: T::Id,
         respond_to: Response<Option<T>>,
     },
+    GetProjection {
+        id: T::Id,
+        respond_to: ProjectionResponse<T>,
+    },
+    GetIncludingDeleted {
+        id: T::Id,
+        respond_to: Response<Option<T>>,
+    },
+    GetMap {
+        ids: Vec<T::Id>,
+        respond_to: Response<HashMap<T::Id, T>>,
+    },
     Update {
         id: T::Id,
         update: T::Update,
+        expected: Option<Expected<T>>,
+        respond_to: Response<T>,
+    },
+    Replace {
+        id: T::Id,
+        entity: T,
         respond_to: Response<T>,
     },
     #[allow(dead_code)]
-    Delete { id: T::Id, respond_to: Response<()> },
+    Delete {
+        id: T::Id,
+        respond_to: Response<()>,
+    },
+    DeleteReturning {
+        id: T::Id,
+        respond_to: Response<T>,
+    },
     Action {
         id: T::Id,
         action: T::Action,
         respond_to: Response<T::ActionResult>,
     },
+    ActionReturning {
+        id: T::Id,
+        action: T::Action,
+        respond_to: Response<(T::ActionResult, T)>,
+    },
+    NotifyAction {
+        id: T::Id,
+        action: T::Action,
+    },
+    ActionMany {
+        items: Vec<(T::Id, T::Action)>,
+        respond_to: Response<Vec<Result<T::ActionResult, FrameworkError>>>,
+    },
+    ActionAtomic {
+        items: Vec<(T::Id, T::Action)>,
+        respond_to: Response<Vec<T::ActionResult>>,
+    },
+    List {
+        respond_to: Response<Vec<T>>,
+    },
+    ListPaginated {
+        offset: usize,
+        limit: usize,
+        respond_to: Response<Vec<T>>,
+    },
+    FindWhere {
+        predicate: Predicate<T>,
+        respond_to: Response<Vec<T>>,
+    },
+    Count {
+        respond_to: Response<usize>,
+    },
+    Pipeline {
+        requests: Vec<ResourceRequest<T>>,
+    },
+    WithContext {
+        context: RequestContext,
+        request: Box<ResourceRequest<T>>,
+    },
+    WithDeadline {
+        deadline: Instant,
+        request: Box<ResourceRequest<T>>,
+    },
+    NotifyCount {
+        target: usize,
+        respond_to: Response<()>,
+    },
+    Ping {
+        respond_to: Response<()>,
+    },
+    Stats {
+        respond_to: Response<ActorStats>,
+    },
+    Pause {
+        respond_to: Response<()>,
+    },
+    Resume {
+        respond_to: Response<()>,
+    },
+    BeginDraining {
+        respond_to: Response<()>,
+    },
+    Shutdown {
+        respond_to: Response<()>,
+    },
+    #[cfg(feature = "testing")]
+    Inspect {
+        id: T::Id,
+        respond_to: Response<T>,
+    },
+    #[cfg(feature = "json-patch")]
+    PatchJson {
+        id: T::Id,
+        patch: JsonPatch<T>,
+        respond_to: Response<T>,
+    },
+}
+
+/// The entity state a conditional `Update` expects to find.
+///
+/// The actor applies the update only if the stored entity equals `snapshot`, and otherwise
+/// answers [`FrameworkError::VersionConflict`]. Built from a `T: PartialEq`, so entities that
+/// can't be compared can't be updated conditionally.
+pub struct Expected<T> {
+    snapshot: T,
+    eq: fn(&T, &T) -> bool,
+}
+
+impl<T: PartialEq> Expected<T> {
+    pub fn new(snapshot: T) -> Self {
+        Self {
+            snapshot,
+            eq: T::eq,
+        }
+    }
+}
+
+impl<T> Expected<T> {
+    /// Returns `true` if `current` still equals the expected snapshot.
+    pub fn matches(&self, current: &T) -> bool {
+        (self.eq)(current, &self.snapshot)
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Expected<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Expected").field(&self.snapshot).finish()
+    }
+}
+
+/// The reply channel of a `GetProjection` request.
+///
+/// The actor only sees `T: ActorEntity`, so the projection is built by this callback, created
+/// where `T: Project` is known, from the stored entity (or the error) the actor hands it.
+pub struct ProjectionResponse<T>(SendProjection<T>);
+
+/// Projects the entity (if any) and sends it to the caller.
+type SendProjection<T> = Box<dyn FnOnce(Result<Option<&T>, FrameworkError>) + Send>;
+
+/// The caller's end of a [`ProjectionResponse`].
+type ProjectionReceiver<P> = oneshot::Receiver<Result<Option<P>, FrameworkError>>;
+
+impl<T: Project> ProjectionResponse<T> {
+    /// Returns the response and the receiver its projection arrives on.
+    pub fn channel() -> (Self, ProjectionReceiver<T::Projection>) {
+        let (respond_to, response) = oneshot::channel();
+        let send = move |entity: Result<Option<&T>, FrameworkError>| {
+            let _ = respond_to.send(entity.map(|entity| entity.map(T::project)));
+        };
+        (Self(Box::new(send)), response)
+    }
+}
+
+impl<T> ProjectionResponse<T> {
+    /// Projects `entity` and sends it to the caller.
+    pub fn send(self, entity: Result<Option<&T>, FrameworkError>) {
+        (self.0)(entity)
+    }
+}
+
+impl<T> std::fmt::Debug for ProjectionResponse<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProjectionResponse(..)")
+    }
+}
+
+/// A read filter run by the actor for a `FindWhere` request.
+///
+/// It runs on the actor's task, holding up every other request, so it must not block.
+pub struct Predicate<T>(Box<dyn Fn(&T) -> bool + Send>);
+
+impl<T> Predicate<T> {
+    pub fn new(predicate: impl Fn(&T) -> bool + Send + 'static) -> Self {
+        Self(Box::new(predicate))
+    }
+
+    /// Returns `true` if `entity` matches.
+    pub fn matches(&self, entity: &T) -> bool {
+        (self.0)(entity)
+    }
+}
+
+impl<T> std::fmt::Debug for Predicate<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Predicate(..)")
+    }
+}
+
+/// The kind of a [`ResourceRequest`] without its payload, e.g. for request logs.
+///
+/// A `WithContext` or `WithDeadline` request reports the kind of the request it wraps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RequestKind {
+    Create,
+    CreateReturning,
+    CreateCancelSafe,
+    Get,
+    GetProjection,
+    GetIncludingDeleted,
+    GetMap,
+    Update,
+    Replace,
+    Delete,
+    DeleteReturning,
+    Action,
+    ActionReturning,
+    NotifyAction,
+    ActionMany,
+    ActionAtomic,
+    List,
+    ListPaginated,
+    FindWhere,
+    Count,
+    Pipeline,
+    NotifyCount,
+    Ping,
+    Stats,
+    Pause,
+    Resume,
+    BeginDraining,
+    Shutdown,
+    #[cfg(feature = "testing")]
+    Inspect,
+    #[cfg(feature = "json-patch")]
+    PatchJson,
+    /// Not a request: an entity evicted from a bounded store, reported in the
+    /// [audit trail](crate::audit) like the write that caused it.
+    Evict,
+}
+
+impl RequestKind {
+    /// Returns `true` for requests that create, change or delete entities.
+    ///
+    /// A `Pipeline` is not a write itself; each request it contains is classified on its own.
+    pub fn is_write(self) -> bool {
+        match self {
+            Self::Create
+            | Self::CreateReturning
+            | Self::CreateCancelSafe
+            | Self::Update
+            | Self::Replace
+            | Self::Delete
+            | Self::DeleteReturning
+            | Self::Action
+            | Self::ActionReturning
+            | Self::NotifyAction
+            | Self::ActionMany
+            | Self::ActionAtomic
+            | Self::Evict => true,
+            #[cfg(feature = "json-patch")]
+            Self::PatchJson => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: ActorEntity> ResourceRequest<T> {
+    /// Returns the kind of this request.
+    pub fn kind(&self) -> RequestKind {
+        match self {
+            Self::Create { .. } => RequestKind::Create,
+            Self::CreateReturning { .. } => RequestKind::CreateReturning,
+            Self::CreateCancelSafe { .. } => RequestKind::CreateCancelSafe,
+            Self::Get { .. } => RequestKind::Get,
+            Self::GetProjection { .. } => RequestKind::GetProjection,
+            Self::GetIncludingDeleted { .. } => RequestKind::GetIncludingDeleted,
+            Self::GetMap { .. } => RequestKind::GetMap,
+            Self::Update { .. } => RequestKind::Update,
+            Self::Replace { .. } => RequestKind::Replace,
+            Self::Delete { .. } => RequestKind::Delete,
+            Self::DeleteReturning { .. } => RequestKind::DeleteReturning,
+            Self::Action { .. } => RequestKind::Action,
+            Self::ActionReturning { .. } => RequestKind::ActionReturning,
+            Self::NotifyAction { .. } => RequestKind::NotifyAction,
+            Self::ActionMany { .. } => RequestKind::ActionMany,
+            Self::ActionAtomic { .. } => RequestKind::ActionAtomic,
+            Self::List { .. } => RequestKind::List,
+            Self::ListPaginated { .. } => RequestKind::ListPaginated,
+            Self::FindWhere { .. } => RequestKind::FindWhere,
+            Self::Count { .. } => RequestKind::Count,
+            Self::Pipeline { .. } => RequestKind::Pipeline,
+            Self::WithContext { request, .. } | Self::WithDeadline { request, .. } => {
+                request.kind()
+            }
+            Self::NotifyCount { .. } => RequestKind::NotifyCount,
+            Self::Ping { .. } => RequestKind::Ping,
+            Self::Stats { .. } => RequestKind::Stats,
+            Self::Pause { .. } => RequestKind::Pause,
+            Self::Resume { .. } => RequestKind::Resume,
+            Self::BeginDraining { .. } => RequestKind::BeginDraining,
+            Self::Shutdown { .. } => RequestKind::Shutdown,
+            #[cfg(feature = "testing")]
+            Self::Inspect { .. } => RequestKind::Inspect,
+            #[cfg(feature = "json-patch")]
+            Self::PatchJson { .. } => RequestKind::PatchJson,
+        }
+    }
+
+    /// Answers the request with `make_error()` instead of handling it.
+    ///
+    /// Each request of a pipeline gets its own error; a `NotifyAction` is dropped silently.
+    pub(crate) fn reject(self, make_error: &dyn Fn() -> FrameworkError) {
+        match self {
+            Self::Create { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+            Self::CreateReturning { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+            Self::CreateCancelSafe { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+            Self::Get { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+            Self::GetProjection { respond_to, .. } => respond_to.send(Err(make_error())),
+            Self::GetIncludingDeleted { respond_to, .. } => {
+                drop(respond_to.send(Err(make_error())))
+            }
+            Self::GetMap { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+            Self::Update { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+            Self::Replace { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+            Self::Delete { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+            Self::DeleteReturning { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+            Self::Action { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+            Self::ActionReturning { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+            Self::NotifyAction { .. } => {}
+            Self::ActionMany { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+            Self::ActionAtomic { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+            Self::List { respond_to } => drop(respond_to.send(Err(make_error()))),
+            Self::ListPaginated { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+            Self::FindWhere { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+            Self::Count { respond_to } => drop(respond_to.send(Err(make_error()))),
+            Self::Pipeline { requests } => {
+                for request in requests {
+                    request.reject(make_error);
+                }
+            }
+            Self::WithContext { request, .. } | Self::WithDeadline { request, .. } => {
+                request.reject(make_error)
+            }
+            Self::NotifyCount { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+            Self::Ping { respond_to } => drop(respond_to.send(Err(make_error()))),
+            Self::Stats { respond_to } => drop(respond_to.send(Err(make_error()))),
+            Self::Pause { respond_to } => drop(respond_to.send(Err(make_error()))),
+            Self::Resume { respond_to } => drop(respond_to.send(Err(make_error()))),
+            Self::BeginDraining { respond_to } => drop(respond_to.send(Err(make_error()))),
+            Self::Shutdown { respond_to } => drop(respond_to.send(Err(make_error()))),
+            #[cfg(feature = "testing")]
+            Self::Inspect { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+            #[cfg(feature = "json-patch")]
+            Self::PatchJson { respond_to, .. } => drop(respond_to.send(Err(make_error()))),
+        }
+    }
+
+    /// Returns the deadline this request was sent with, if any.
+    pub fn deadline(&self) -> Option<Instant> {
+        match self {
+            Self::WithDeadline { deadline, .. } => Some(*deadline),
+            _ => None,
+        }
+    }
+
+    /// Returns the id of the entity this request targets, if it targets exactly one.
+    pub fn id(&self) -> Option<&T::Id> {
+        match self {
+            Self::Get { id, .. }
+            | Self::GetProjection { id, .. }
+            | Self::GetIncludingDeleted { id, .. }
+            | Self::Update { id, .. }
+            | Self::Replace { id, .. }
+            | Self::Delete { id, .. }
+            | Self::DeleteReturning { id, .. }
+            | Self::Action { id, .. }
+            | Self::ActionReturning { id, .. }
+            | Self::NotifyAction { id, .. } => Some(id),
+            #[cfg(feature = "testing")]
+            Self::Inspect { id, .. } => Some(id),
+            #[cfg(feature = "json-patch")]
+            Self::PatchJson { id, .. } => Some(id),
+            Self::WithContext { request, .. } | Self::WithDeadline { request, .. } => request.id(),
+            _ => None,
+        }
+    }
 }
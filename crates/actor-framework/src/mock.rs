@@ -42,7 +42,8 @@
 //!     fn from_create_params(id: u32, params: UserCreate) -> Result<Self, Self::Error> {
 //!         Ok(Self { id, email: params.email })
 //!     }
-//!     async fn on_update(&mut self, _: UserUpdate, _: &()) -> Result<(), Self::Error> { Ok(()) }
+//!     fn id(&self) -> &u32 { &self.id }
+//!     async fn on_update(&mut self, _: UserUpdate, _: &()) -> Result<bool, Self::Error> { Ok(true) }
 //!     async fn handle_action(&mut self, _: UserAction, _: &()) -> Result<(), Self::Error> { Ok(()) }
 //! }
 //!
@@ -100,7 +101,8 @@
 //!     fn from_create_params(id: u32, params: ProductCreate) -> Result<Self, Self::Error> {
 //!         Ok(Self { id, stock: params.stock })
 //!     }
-//!     async fn on_update(&mut self, _: ProductUpdate, _: &()) -> Result<(), Self::Error> { Ok(()) }
+//!     fn id(&self) -> &u32 { &self.id }
+//!     async fn on_update(&mut self, _: ProductUpdate, _: &()) -> Result<bool, Self::Error> { Ok(true) }
 //!     async fn handle_action(&mut self, action: ProductAction, _: &()) -> Result<u32, Self::Error> {
 //!         match action { ProductAction::CheckStock => Ok(self.stock) }
 //!     }
@@ -159,7 +161,8 @@
 //!     type Id = u32; type Create = UserCreate; type Update = UserUpdate;
 //!     type Action = UserAction; type ActionResult = (); type Context = (); type Error = UserError;
 //!     fn from_create_params(id: u32, _: UserCreate) -> Result<Self, Self::Error> { Ok(Self { id }) }
-//!     async fn on_update(&mut self, _: UserUpdate, _: &()) -> Result<(), Self::Error> { Ok(()) }
+//!     fn id(&self) -> &u32 { &self.id }
+//!     async fn on_update(&mut self, _: UserUpdate, _: &()) -> Result<bool, Self::Error> { Ok(true) }
 //!     async fn handle_action(&mut self, _: UserAction, _: &()) -> Result<(), Self::Error> { Ok(()) }
 //! }
 //!
@@ -173,44 +176,49 @@
 //!         .return_err(FrameworkError::ActorClosed);
 //!
 //!     // Verify your code handles it gracefully
-//!     let result = client.get(1).await;
+//!     let result = client.get(1u32).await;
 //!     assert!(matches!(result, Err(FrameworkError::ActorClosed)));
 //! }
 //! ```
 //!
+//! To simulate a *slow* actor instead, chain `.after(Duration)` before `return_ok`/`return_err`;
+//! the mock sleeps for that long before responding, which makes client timeout handling testable.
+//!
 //! ## Advanced: Test-Only Actions
 //!
 //! <details>
 //! <summary><b>How to use Feature Flags for Testing</b></summary>
 //!
-//! Sometimes you need to inspect internal actor state for testing. Use a Cargo **feature flag** (`testing`)
-//! instead of `#[cfg(test)]` so it works with integration tests.
+//! Sometimes you need to inspect internal actor state for testing. The framework's `testing` feature
+//! adds `ResourceRequest::Inspect` and [`ResourceClient::inspect`], which return the stored entity
+//! without running any hook. Enable it for tests only, via a dev-dependency:
 //!
 //! ```toml
-//! [features]
-//! testing = []
+//! [dev-dependencies]
+//! actor-framework = { path = "../actor-framework", features = ["testing"] }
 //! ```
 //!
-//! Then guard your test-only actions:
-//!
 //! ```rust,ignore
-//! pub enum ProductAction {
-//!     #[cfg(feature = "testing")]
-//!     GetInternalState,
-//! }
+//! let product = product_client.inner().inspect(product_id).await?;
+//! assert_eq!(product.quantity, 3);
 //! ```
+//!
+//! A feature flag (rather than `#[cfg(test)]`) is what makes this visible to integration tests.
 //! </details>
 //!
 //! ## Mocking Utilities
 //!
 //! Use [`create_mock_client`] to get a client and a receiver, or use the fluent [`MockClient`] API.
+//! For Pattern 1 tests against a real actor, [`spawn_actor`] starts one and returns an [`ActorHandle`].
 
+use crate::actor::ResourceActor;
 use crate::client::ResourceClient;
 use crate::entity::ActorEntity;
 use crate::error::FrameworkError;
-use crate::message::ResourceRequest;
+use crate::message::{RequestKind, ResourceRequest};
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::sync::mpsc;
 
 // =============================================================================
@@ -242,8 +250,20 @@ enum Expectation<T: ActorEntity> {
         id: T::Id,
         response: Result<T::ActionResult, FrameworkError>,
     },
+    List {
+        response: Result<Vec<T>, FrameworkError>,
+    },
+    Count {
+        response: Result<usize, FrameworkError>,
+    },
 }
 
+/// A queued expectation together with an optional delay before responding.
+type Expectations<T> = Arc<Mutex<VecDeque<(Expectation<T>, Option<Duration>)>>>;
+
+/// Every request received so far, as its kind and target id (empty if it has none).
+type RequestLog = Arc<Mutex<Vec<(RequestKind, String)>>>;
+
 /// A mock client with expectation tracking for fluent testing.
 ///
 /// # Example
@@ -258,7 +278,8 @@ enum Expectation<T: ActorEntity> {
 /// ```
 pub struct MockClient<T: ActorEntity> {
     client: ResourceClient<T>,
-    expectations: Arc<Mutex<VecDeque<Expectation<T>>>>,
+    expectations: Expectations<T>,
+    request_log: RequestLog,
     _handle: tokio::task::JoinHandle<()>,
 }
 
@@ -286,15 +307,27 @@ where
     /// Creates a new mock client with no expectations.
     pub fn new() -> Self {
         let (sender, mut receiver) = mpsc::channel::<ResourceRequest<T>>(100);
-        let expectations = Arc::new(Mutex::new(VecDeque::new()));
+        let expectations: Expectations<T> = Arc::new(Mutex::new(VecDeque::new()));
         let expectations_clone = expectations.clone();
+        let request_log: RequestLog = Arc::default();
+        let request_log_clone = request_log.clone();
 
         // Spawn background task to handle requests
         let handle = tokio::spawn(async move {
             while let Some(request) = receiver.recv().await {
-                let mut exps = expectations_clone.lock().unwrap();
-                let expectation = exps.pop_front();
-                drop(exps); // Release lock before async operations
+                let id = request.id().map(ToString::to_string).unwrap_or_default();
+                request_log_clone.lock().unwrap().push((request.kind(), id));
+
+                // The lock guard is a temporary, released before any async operations
+                let (expectation, delay) = match expectations_clone.lock().unwrap().pop_front() {
+                    Some((expectation, delay)) => (Some(expectation), delay),
+                    None => (None, None),
+                };
+
+                // Simulate a slow actor (responses to later requests are delayed too)
+                if let Some(delay) = delay {
+                    tokio::time::sleep(delay).await;
+                }
 
                 match (request, expectation) {
                     (
@@ -316,6 +349,7 @@ where
                         ResourceRequest::Update {
                             id: _,
                             update: _,
+                            expected: _,
                             respond_to,
                         },
                         Some(Expectation::Update { id: _, response }),
@@ -338,6 +372,18 @@ where
                     ) => {
                         let _ = respond_to.send(response);
                     }
+                    (
+                        ResourceRequest::List { respond_to },
+                        Some(Expectation::List { response }),
+                    ) => {
+                        let _ = respond_to.send(response);
+                    }
+                    (
+                        ResourceRequest::Count { respond_to },
+                        Some(Expectation::Count { response }),
+                    ) => {
+                        let _ = respond_to.send(response);
+                    }
                     _ => {
                         panic!("Unexpected request or expectation mismatch");
                     }
@@ -348,6 +394,7 @@ where
         Self {
             client: ResourceClient::new(sender),
             expectations,
+            request_log,
             _handle: handle,
         }
     }
@@ -361,6 +408,7 @@ where
     pub fn expect_get(&mut self, id: T::Id) -> GetExpectationBuilder<T> {
         GetExpectationBuilder {
             id,
+            delay: None,
             expectations: self.expectations.clone(),
         }
     }
@@ -368,6 +416,7 @@ where
     /// Expects a `create` operation.
     pub fn expect_create(&mut self) -> CreateExpectationBuilder<T> {
         CreateExpectationBuilder {
+            delay: None,
             expectations: self.expectations.clone(),
         }
     }
@@ -376,15 +425,63 @@ where
     pub fn expect_action(&mut self, id: T::Id) -> ActionExpectationBuilder<T> {
         ActionExpectationBuilder {
             id,
+            delay: None,
+            expectations: self.expectations.clone(),
+        }
+    }
+
+    /// Expects a `list` operation.
+    pub fn expect_list(&mut self) -> ListExpectationBuilder<T> {
+        ListExpectationBuilder {
+            delay: None,
+            expectations: self.expectations.clone(),
+        }
+    }
+
+    /// Expects a `count` operation.
+    pub fn expect_count(&mut self) -> CountExpectationBuilder<T> {
+        CountExpectationBuilder {
+            delay: None,
             expectations: self.expectations.clone(),
         }
     }
 
     /// Verifies that all expectations were met.
+    ///
+    /// # Panics
+    /// Panics if any expectations remain. Use [`MockClient::verify_result`] to check
+    /// without aborting (e.g. when aggregating several mocks in a test helper).
     pub fn verify(&self) {
+        self.verify_result().unwrap();
+    }
+
+    /// Returns every request received so far, in arrival order, as its kind and target id.
+    ///
+    /// The id is empty for requests without one (e.g. `Create`, `List`). Unlike
+    /// [`MockClient::verify`], this catches requests issued in the wrong order or against the
+    /// wrong id, and includes requests that did not match any expectation.
+    pub fn request_log(&self) -> Vec<(RequestKind, String)> {
+        self.request_log.lock().unwrap().clone()
+    }
+
+    /// Like [`MockClient::request_log`], but also clears the log, so a test can assert on
+    /// one phase at a time.
+    pub fn drain_requests(&self) -> Vec<(RequestKind, String)> {
+        std::mem::take(&mut *self.request_log.lock().unwrap())
+    }
+
+    /// Verifies that all expectations were met, returning an error instead of panicking.
+    ///
+    /// The error message contains the number of unmet expectations.
+    pub fn verify_result(&self) -> Result<(), String> {
         let exps = self.expectations.lock().unwrap();
-        if !exps.is_empty() {
-            panic!("Not all expectations were met. {} remaining", exps.len());
+        if exps.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Not all expectations were met. {} remaining",
+                exps.len()
+            ))
         }
     }
 }
@@ -392,73 +489,206 @@ where
 /// Builder for `get` expectations.
 pub struct GetExpectationBuilder<T: ActorEntity> {
     id: T::Id,
-    expectations: Arc<Mutex<VecDeque<Expectation<T>>>>,
+    delay: Option<Duration>,
+    expectations: Expectations<T>,
 }
 
 impl<T: ActorEntity> GetExpectationBuilder<T> {
+    /// Waits for `delay` before sending the response, simulating a slow actor.
+    pub fn after(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
     /// Sets the expectation to return a successful result.
     pub fn return_ok(self, value: Option<T>) {
         let mut exps = self.expectations.lock().unwrap();
-        exps.push_back(Expectation::Get {
-            id: self.id,
-            response: Ok(value),
-        });
+        exps.push_back((
+            Expectation::Get {
+                id: self.id,
+                response: Ok(value),
+            },
+            self.delay,
+        ));
     }
 
     /// Sets the expectation to return an error.
     pub fn return_err(self, error: FrameworkError) {
         let mut exps = self.expectations.lock().unwrap();
-        exps.push_back(Expectation::Get {
-            id: self.id,
-            response: Err(error),
-        });
+        exps.push_back((
+            Expectation::Get {
+                id: self.id,
+                response: Err(error),
+            },
+            self.delay,
+        ));
     }
 }
 
 /// Builder for `create` expectations.
 pub struct CreateExpectationBuilder<T: ActorEntity> {
-    expectations: Arc<Mutex<VecDeque<Expectation<T>>>>,
+    delay: Option<Duration>,
+    expectations: Expectations<T>,
 }
 
 impl<T: ActorEntity> CreateExpectationBuilder<T> {
+    /// Waits for `delay` before sending the response, simulating a slow actor.
+    pub fn after(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
     /// Sets the expectation to return a successful result.
     pub fn return_ok(self, id: T::Id) {
         let mut exps = self.expectations.lock().unwrap();
-        exps.push_back(Expectation::Create { response: Ok(id) });
+        exps.push_back((Expectation::Create { response: Ok(id) }, self.delay));
     }
 
     /// Sets the expectation to return an error.
     pub fn return_err(self, error: FrameworkError) {
         let mut exps = self.expectations.lock().unwrap();
-        exps.push_back(Expectation::Create {
-            response: Err(error),
-        });
+        exps.push_back((
+            Expectation::Create {
+                response: Err(error),
+            },
+            self.delay,
+        ));
+    }
+
+    /// Expects one `create` per response and answers them in order, e.g.
+    /// `returns([Err(FrameworkError::Full), Ok(1)])` to test a retry.
+    pub fn returns(self, responses: impl IntoIterator<Item = Result<T::Id, FrameworkError>>) {
+        let mut exps = self.expectations.lock().unwrap();
+        for response in responses {
+            exps.push_back((Expectation::Create { response }, self.delay));
+        }
     }
 }
 
 /// Builder for `action` expectations.
 pub struct ActionExpectationBuilder<T: ActorEntity> {
     id: T::Id,
-    expectations: Arc<Mutex<VecDeque<Expectation<T>>>>,
+    delay: Option<Duration>,
+    expectations: Expectations<T>,
 }
 
 impl<T: ActorEntity> ActionExpectationBuilder<T> {
+    /// Waits for `delay` before sending the response, simulating a slow actor.
+    pub fn after(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
     /// Sets the expectation to return a successful result.
     pub fn return_ok(self, result: T::ActionResult) {
         let mut exps = self.expectations.lock().unwrap();
-        exps.push_back(Expectation::Action {
-            id: self.id,
-            response: Ok(result),
-        });
+        exps.push_back((
+            Expectation::Action {
+                id: self.id,
+                response: Ok(result),
+            },
+            self.delay,
+        ));
     }
 
     /// Sets the expectation to return an error.
     pub fn return_err(self, error: FrameworkError) {
         let mut exps = self.expectations.lock().unwrap();
-        exps.push_back(Expectation::Action {
-            id: self.id,
-            response: Err(error),
-        });
+        exps.push_back((
+            Expectation::Action {
+                id: self.id,
+                response: Err(error),
+            },
+            self.delay,
+        ));
+    }
+
+    /// Expects one action on this id per response and answers them in order, e.g.
+    /// `returns([Err(FrameworkError::Full), Ok(result)])` to test a retry.
+    pub fn returns(
+        self,
+        responses: impl IntoIterator<Item = Result<T::ActionResult, FrameworkError>>,
+    ) {
+        let mut exps = self.expectations.lock().unwrap();
+        for response in responses {
+            let expectation = Expectation::Action {
+                id: self.id.clone(),
+                response,
+            };
+            exps.push_back((expectation, self.delay));
+        }
+    }
+}
+
+/// Builder for `list` expectations.
+pub struct ListExpectationBuilder<T: ActorEntity> {
+    delay: Option<Duration>,
+    expectations: Expectations<T>,
+}
+
+impl<T: ActorEntity> ListExpectationBuilder<T> {
+    /// Waits for `delay` before sending the response, simulating a slow actor.
+    pub fn after(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Sets the expectation to return a successful result.
+    pub fn return_ok(self, items: Vec<T>) {
+        let mut exps = self.expectations.lock().unwrap();
+        exps.push_back((
+            Expectation::List {
+                response: Ok(items),
+            },
+            self.delay,
+        ));
+    }
+
+    /// Sets the expectation to return an error.
+    pub fn return_err(self, error: FrameworkError) {
+        let mut exps = self.expectations.lock().unwrap();
+        exps.push_back((
+            Expectation::List {
+                response: Err(error),
+            },
+            self.delay,
+        ));
+    }
+}
+
+/// Builder for `count` expectations.
+pub struct CountExpectationBuilder<T: ActorEntity> {
+    delay: Option<Duration>,
+    expectations: Expectations<T>,
+}
+
+impl<T: ActorEntity> CountExpectationBuilder<T> {
+    /// Waits for `delay` before sending the response, simulating a slow actor.
+    pub fn after(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Sets the expectation to return a successful result.
+    pub fn return_ok(self, count: usize) {
+        let mut exps = self.expectations.lock().unwrap();
+        exps.push_back((
+            Expectation::Count {
+                response: Ok(count),
+            },
+            self.delay,
+        ));
+    }
+
+    /// Sets the expectation to return an error.
+    pub fn return_err(self, error: FrameworkError) {
+        let mut exps = self.expectations.lock().unwrap();
+        exps.push_back((
+            Expectation::Count {
+                response: Err(error),
+            },
+            self.delay,
+        ));
     }
 }
 
@@ -528,6 +758,64 @@ pub async fn expect_action<T: ActorEntity>(
     }
 }
 
+// =============================================================================
+// ACTOR HARNESS
+// =============================================================================
+
+/// Handle to an actor started by [`spawn_actor`].
+///
+/// Call [`ActorHandle::shutdown`] after dropping every client to wait for the actor
+/// to finish. If the handle is dropped instead, the actor task is aborted so tests
+/// never leak running actors.
+pub struct ActorHandle {
+    handle: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl ActorHandle {
+    /// Waits for the actor's run loop to exit.
+    ///
+    /// The loop only exits once all clients are dropped, so drop them first or this
+    /// waits forever. Returns an error if the actor task panicked.
+    pub async fn shutdown(mut self) -> Result<(), tokio::task::JoinError> {
+        match self.handle.take() {
+            Some(handle) => handle.await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for ActorHandle {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+/// Creates and spawns a real `ResourceActor<T>` with a default context.
+///
+/// Reduces a single-actor (Pattern 1) test to:
+///
+/// ```rust,ignore
+/// let (client, handle) = spawn_actor::<Product>(10);
+/// // ... exercise the client ...
+/// drop(client);
+/// handle.shutdown().await.unwrap();
+/// ```
+pub fn spawn_actor<T: ActorEntity>(buffer_size: usize) -> (ResourceClient<T>, ActorHandle)
+where
+    T::Context: Default,
+{
+    let (actor, client) = ResourceActor::<T>::new(buffer_size);
+    let handle = tokio::spawn(actor.run(T::Context::default()));
+    (
+        client,
+        ActorHandle {
+            handle: Some(handle),
+        },
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -574,12 +862,16 @@ mod tests {
                 email: params.email,
             })
         }
+
+        fn id(&self) -> &u32 {
+            &self.id
+        }
         async fn on_update(
             &mut self,
             _update: UserUpdate,
             _ctx: &Self::Context,
-        ) -> Result<(), Self::Error> {
-            Ok(())
+        ) -> Result<bool, Self::Error> {
+            Ok(true)
         }
 
         async fn handle_action(
@@ -624,6 +916,25 @@ mod tests {
         assert!(matches!(result, Ok(id) if id == 1));
     }
 
+    #[tokio::test]
+    async fn test_returns_answers_each_response_in_turn() {
+        let mut mock = MockClient::<User>::new();
+        mock.expect_create()
+            .returns([Err(FrameworkError::Full), Ok(7)]);
+
+        let client = mock.client();
+        let create = || {
+            client.create(UserCreate {
+                name: "Test".to_string(),
+                email: "test@example.com".to_string(),
+            })
+        };
+        assert!(matches!(create().await, Err(FrameworkError::Full)));
+        assert_eq!(create().await.unwrap(), 7);
+
+        mock.verify();
+    }
+
     #[tokio::test]
     async fn test_mock_client_with_expectations() {
         // Create mock with fluent expectation API
@@ -644,11 +955,111 @@ mod tests {
         let id = client.create(user).await.unwrap();
         assert_eq!(id, 1);
 
-        let fetched = client.get(1).await.unwrap();
+        let fetched = client.get(1u32).await.unwrap();
         assert!(fetched.is_some());
         assert_eq!(fetched.unwrap().email, "test@example.com");
 
         // Verify all expectations were met
         mock.verify();
     }
+
+    #[tokio::test]
+    async fn test_verify_result_reports_unmet_expectations() {
+        let mut mock = MockClient::<User>::new();
+        mock.expect_create().return_ok(1);
+        mock.expect_get(1).return_ok(None);
+
+        let err = mock.verify_result().unwrap_err();
+        assert!(err.contains("2 remaining"));
+
+        mock.client()
+            .create(UserCreate {
+                name: "Test".to_string(),
+                email: "test@example.com".to_string(),
+            })
+            .await
+            .unwrap();
+        mock.client().get(1u32).await.unwrap();
+        assert!(mock.verify_result().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_request_log_records_order_and_ids() {
+        let mut mock = MockClient::<User>::new();
+        mock.expect_get(7).return_ok(None);
+        mock.expect_create().return_ok(1);
+        mock.expect_count().return_ok(1);
+        let client = mock.client();
+
+        client.get(7u32).await.unwrap();
+        client
+            .create(UserCreate {
+                name: "Test".to_string(),
+                email: "test@example.com".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            mock.drain_requests(),
+            vec![
+                (RequestKind::Get, "7".to_string()),
+                (RequestKind::Create, String::new()),
+            ]
+        );
+
+        client.count().await.unwrap();
+        assert_eq!(
+            mock.request_log(),
+            vec![(RequestKind::Count, String::new())]
+        );
+        mock.verify();
+    }
+
+    #[tokio::test]
+    async fn test_expectation_after_delays_response() {
+        let mut mock = MockClient::<User>::new();
+        mock.expect_get(1)
+            .after(Duration::from_millis(100))
+            .return_ok(None);
+
+        let client = mock.client();
+        let result = tokio::time::timeout(Duration::from_millis(10), client.get(1u32)).await;
+        assert!(result.is_err(), "Response should arrive after the timeout");
+
+        mock.verify();
+    }
+
+    #[tokio::test]
+    async fn test_mock_client_list_and_count() {
+        let mut mock = MockClient::<User>::new();
+        mock.expect_list().return_ok(vec![
+            User::new(1, "a@example.com"),
+            User::new(2, "b@example.com"),
+        ]);
+        mock.expect_count().return_ok(2);
+
+        let client = mock.client();
+        let users = client.list().await.unwrap();
+        assert_eq!(users.len(), 2);
+        assert_eq!(client.count().await.unwrap(), 2);
+
+        mock.verify();
+    }
+
+    #[tokio::test]
+    async fn test_spawn_actor_harness() {
+        let (client, handle) = spawn_actor::<User>(10);
+
+        let id = client
+            .create(UserCreate {
+                name: "Test".to_string(),
+                email: "test@example.com".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(client.get(id).await.unwrap().unwrap().name, "Test");
+
+        drop(client);
+        handle.shutdown().await.unwrap();
+    }
 }
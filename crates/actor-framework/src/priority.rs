@@ -0,0 +1,95 @@
+//! # Priority Client
+//!
+//! Control-plane handle for a `ResourceActor`.
+//!
+//! Requests sent through a [`ResourceClient`](crate::ResourceClient) queue up behind each
+//! other, so under load a health check or a shutdown may wait for thousands of data-plane
+//! requests. Every actor also reads a small second channel, and always reads it first: a
+//! request sent through a [`PriorityClient`] is handled as soon as the actor finishes the
+//! request (or pipeline) it is currently running.
+
+use crate::entity::ActorEntity;
+use crate::error::FrameworkError;
+use crate::message::ResourceRequest;
+use crate::stats::ActorStats;
+use tokio::sync::{mpsc, oneshot};
+
+/// A handle for control requests that jump ahead of the actor's normal queue.
+///
+/// Obtained from [`ResourceActor::priority_client`](crate::ResourceActor::priority_client).
+/// Unlike a `ResourceClient`, it does **not** keep the actor alive: the actor still stops
+/// once every `ResourceClient` is dropped.
+#[derive(Clone)]
+pub struct PriorityClient<T: ActorEntity> {
+    sender: mpsc::Sender<ResourceRequest<T>>,
+}
+
+impl<T: ActorEntity> std::fmt::Debug for PriorityClient<T> {
+    /// Formats as `PriorityClient<User> { closed: false }`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(&format!("PriorityClient<{}>", T::entity_name()))
+            .field("closed", &self.sender.is_closed())
+            .finish()
+    }
+}
+
+impl<T: ActorEntity> PriorityClient<T> {
+    pub(crate) fn new(sender: mpsc::Sender<ResourceRequest<T>>) -> Self {
+        Self { sender }
+    }
+
+    /// Returns the actor's runtime counters; doubles as a health ping that is not delayed by
+    /// a request backlog.
+    pub async fn stats(&self) -> Result<ActorStats, FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.sender
+            .send(ResourceRequest::Stats { respond_to })
+            .await
+            .map_err(|_| FrameworkError::ActorClosed)?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Pauses the actor: once the request it is running finishes, it stops handling requests
+    /// from its `ResourceClient`s until [`resume`](Self::resume), e.g. for a maintenance window.
+    ///
+    /// Requests sent meanwhile are not lost but held, in order, in the actor's channel. That
+    /// channel holds at most the `buffer_size` the actor was created with; once it is full,
+    /// every further send waits for room, so callers are slowed down (backpressure) rather than
+    /// failed, unless they give up on their own (e.g. through a timeout). Size the buffer for
+    /// the traffic expected during a pause. TTL expiry is also paused, and the actor does not
+    /// notice its last `ResourceClient` being dropped until it resumes. Requests sent through
+    /// a `PriorityClient` are still handled, so pausing twice or a `stats` call is fine.
+    pub async fn pause(&self) -> Result<(), FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.sender
+            .send(ResourceRequest::Pause { respond_to })
+            .await
+            .map_err(|_| FrameworkError::ActorClosed)?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Resumes a paused actor, which then handles the held requests in the order they were
+    /// sent. Resuming an actor that is not paused does nothing.
+    pub async fn resume(&self) -> Result<(), FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.sender
+            .send(ResourceRequest::Resume { respond_to })
+            .await
+            .map_err(|_| FrameworkError::ActorClosed)?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+
+    /// Stops the actor without waiting for its backlog, returning once `on_stop` has run.
+    ///
+    /// Requests still queued on the normal channel are dropped unprocessed (their callers
+    /// get `FrameworkError::ActorDropped`), and later requests fail with
+    /// `FrameworkError::ActorClosed`.
+    pub async fn shutdown(&self) -> Result<(), FrameworkError> {
+        let (respond_to, response) = oneshot::channel();
+        self.sender
+            .send(ResourceRequest::Shutdown { respond_to })
+            .await
+            .map_err(|_| FrameworkError::ActorClosed)?;
+        response.await.map_err(|_| FrameworkError::ActorDropped)?
+    }
+}
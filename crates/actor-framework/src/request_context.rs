@@ -0,0 +1,79 @@
+//! # Request Context
+//!
+//! An actor's `Context` is fixed when `run` is called, which suits dependencies (other clients,
+//! a clock) but not data that varies per call, such as a tenant id or the acting principal.
+//! A [`RequestContext`] carries such a value *with one request*: the actor makes it visible to
+//! every hook that runs for that request, and to nothing else.
+//!
+//! ## Usage
+//!
+//! Attach a value on the client side, then read it inside a hook with [`current`]:
+//!
+//! ```rust,ignore
+//! #[derive(Clone)]
+//! struct ActingUser(UserId);
+//!
+//! // Every request sent through this client carries the principal
+//! let as_alice = order_client.with_request_context(ActingUser(alice));
+//! as_alice.create(params).await?;
+//!
+//! // In Order::on_create
+//! if let Some(ActingUser(user)) = request_context::current::<ActingUser>() {
+//!     if user != self.user_id {
+//!         return Err(OrderError::Forbidden);
+//!     }
+//! }
+//! ```
+//!
+//! It stays opt-in: requests sent without a context see `current()` return `None`, and
+//! entities that never call it are unaffected. The value is type-erased, so asking for a
+//! different type than was attached also yields `None`.
+//!
+//! The type is not an associated `ActorEntity::RequestContext` because associated type
+//! defaults are unstable: every entity would have to declare one, even those that never use
+//! it, and the hooks would all need another parameter.
+
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+tokio::task_local! {
+    static REQUEST_CONTEXT: RequestContext;
+}
+
+/// A per-request value delivered to entity hooks alongside the actor's static `Context`.
+#[derive(Clone)]
+pub struct RequestContext(Arc<dyn Any + Send + Sync>);
+
+impl RequestContext {
+    /// Wraps `value` so it can travel with a request.
+    pub fn new<C: Any + Send + Sync>(value: C) -> Self {
+        Self(Arc::new(value))
+    }
+
+    /// Returns the carried value if it is a `C`.
+    pub fn get<C: Any>(&self) -> Option<&C> {
+        self.0.downcast_ref()
+    }
+}
+
+impl fmt::Debug for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RequestContext { .. }")
+    }
+}
+
+/// Returns the request context of the request currently being handled, if it carries a `C`.
+///
+/// Only meaningful inside entity hooks; elsewhere it returns `None`.
+pub fn current<C: Any + Clone>() -> Option<C> {
+    REQUEST_CONTEXT
+        .try_with(|context| context.get::<C>().cloned())
+        .ok()
+        .flatten()
+}
+
+/// Runs `future` with `context` visible through [`current`].
+pub(crate) async fn scope<F: std::future::Future>(context: RequestContext, future: F) -> F::Output {
+    REQUEST_CONTEXT.scope(context, future).await
+}
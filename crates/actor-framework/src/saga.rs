@@ -0,0 +1,105 @@
+//! # Saga
+//!
+//! A single actor handles its requests one at a time, but nothing makes a workflow that spans
+//! several actors atomic. The saga pattern makes it *eventually* consistent instead: each
+//! forward step is paired with a compensation that undoes it, and when a step fails the
+//! compensations of the steps that already succeeded run in reverse order.
+//!
+//! [`Saga`] turns that hand-written rollback into reusable machinery. Each forward step
+//! receives the output of the step before it, and each compensation the output of its own
+//! step, so a step can undo exactly what it did (e.g. delete the order it created):
+//!
+//! ```rust,ignore
+//! let order_id = Saga::new()
+//!     .step(
+//!         |()| product_client.reserve_stock(product_id.clone(), order_id, quantity),
+//!         |()| async { let _ = product_client.release_stock(product_id, order_id).await; },
+//!     )
+//!     .step(
+//!         |()| order_client.inner().create(params),
+//!         |id| async move { let _ = order_client.inner().delete(id).await; },
+//!     )
+//!     .run()
+//!     .await?;
+//! ```
+//!
+//! Compensations cannot fail the saga: they return `()`, so log (or retry) inside them. A
+//! compensation is only awaited if its own step succeeded. A step that needs an earlier
+//! output than the previous one has it passed along, e.g. as part of a tuple.
+
+use std::future::Future;
+use std::pin::Pin;
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// The compensations of the completed steps, in the order the steps ran.
+type Compensations<'a> = Vec<BoxFuture<'a, ()>>;
+
+/// An ordered list of forward steps, each with a compensation that undoes it, producing a `T`.
+///
+/// Nothing runs until [`Saga::run`] is awaited.
+#[must_use = "a saga does nothing until `run` is awaited"]
+pub struct Saga<'a, T, E> {
+    /// Runs every step so far, yielding the last output and the compensations to run should a
+    /// later step fail. On failure the completed steps are already compensated.
+    steps: BoxFuture<'a, Result<(T, Compensations<'a>), E>>,
+}
+
+impl<'a, E: Send + 'a> Default for Saga<'a, (), E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, E: Send + 'a> Saga<'a, (), E> {
+    /// Creates a saga without steps; the first step receives `()`.
+    pub fn new() -> Self {
+        Self {
+            steps: Box::pin(async { Ok(((), Vec::new())) }),
+        }
+    }
+}
+
+impl<'a, T: Send + 'a, E: Send + 'a> Saga<'a, T, E> {
+    /// Appends a step: `forward` runs with the previous step's output once every earlier step
+    /// succeeded, and `compensation` runs with a copy of `forward`'s output if a later step
+    /// fails.
+    pub fn step<U, F, C>(
+        self,
+        forward: impl FnOnce(T) -> F + Send + 'a,
+        compensation: impl FnOnce(U) -> C + Send + 'a,
+    ) -> Saga<'a, U, E>
+    where
+        U: Clone + Send + 'a,
+        F: Future<Output = Result<U, E>> + Send + 'a,
+        C: Future<Output = ()> + Send + 'a,
+    {
+        let previous = self.steps;
+        Saga {
+            steps: Box::pin(async move {
+                let (input, mut completed) = previous.await?;
+                match forward(input).await {
+                    Ok(output) => {
+                        completed.push(Box::pin(compensation(output.clone())));
+                        Ok((output, completed))
+                    }
+                    Err(e) => {
+                        for compensation in completed.into_iter().rev() {
+                            compensation.await;
+                        }
+                        Err(e)
+                    }
+                }
+            }),
+        }
+    }
+
+    /// Runs the steps in order, stopping at the first failure, and returns the last step's
+    /// output.
+    ///
+    /// On failure the compensations of the completed steps run in reverse order, then the
+    /// failing step's error is returned.
+    pub async fn run(self) -> Result<T, E> {
+        self.steps.await.map(|(output, _)| output)
+    }
+}
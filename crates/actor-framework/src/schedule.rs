@@ -0,0 +1,88 @@
+//! # Deadline Scheduling
+//!
+//! With [`ResourceActor::with_deadline_scheduling`](crate::ResourceActor::with_deadline_scheduling),
+//! the actor reads up to `window` queued requests ahead into a `DeadlineQueue` and handles
+//! them nearest deadline first, so a time-critical request (sent through
+//! [`ResourceClient::with_deadline`](crate::ResourceClient::with_deadline)) does not sit
+//! behind cheaper ones. Requests without a deadline follow all those with one, in arrival
+//! order; with no deadlines in the window the order is plain FIFO. This is the one case where
+//! requests sent sequentially from one client may be handled out of order: a request with a
+//! nearer deadline overtakes earlier ones still in the window.
+//!
+//! Nothing bounds how often a request is overtaken. A request without a deadline waits for
+//! every request with one, including those that arrive after it, so a steady stream of
+//! deadlines starves it.
+
+use crate::entity::ActorEntity;
+use crate::message::ResourceRequest;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use tokio::time::Instant;
+
+/// Requests read ahead of the channel, ordered by deadline.
+pub(crate) struct DeadlineQueue<T: ActorEntity> {
+    heap: BinaryHeap<Reverse<Scheduled<T>>>,
+    window: usize,
+    /// Arrival counter, breaking ties in arrival order.
+    next_seq: u64,
+}
+
+/// A queued request with its scheduling key.
+struct Scheduled<T: ActorEntity> {
+    /// `(no deadline, deadline, arrival)`: deadlines first, earliest first, then FIFO.
+    key: (bool, Option<Instant>, u64),
+    request: ResourceRequest<T>,
+}
+
+impl<T: ActorEntity> PartialEq for Scheduled<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<T: ActorEntity> Eq for Scheduled<T> {}
+
+impl<T: ActorEntity> PartialOrd for Scheduled<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: ActorEntity> Ord for Scheduled<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+impl<T: ActorEntity> DeadlineQueue<T> {
+    /// A queue holding at most `window` requests.
+    pub(crate) fn new(window: usize) -> Self {
+        Self {
+            heap: BinaryHeap::with_capacity(window),
+            window,
+            next_seq: 0,
+        }
+    }
+
+    /// Whether no more requests should be read ahead.
+    pub(crate) fn is_full(&self) -> bool {
+        self.heap.len() >= self.window
+    }
+
+    pub(crate) fn push(&mut self, request: ResourceRequest<T>) {
+        let deadline = request.deadline();
+        let key = (deadline.is_none(), deadline, self.next_seq);
+        self.next_seq += 1;
+        self.heap.push(Reverse(Scheduled { key, request }));
+    }
+
+    /// Drops every queued request; their callers get `FrameworkError::ActorDropped`.
+    pub(crate) fn clear(&mut self) {
+        self.heap.clear();
+    }
+
+    /// Takes the request with the nearest deadline, or the oldest one if none has a deadline.
+    pub(crate) fn pop(&mut self) -> Option<ResourceRequest<T>> {
+        self.heap.pop().map(|Reverse(scheduled)| scheduled.request)
+    }
+}
@@ -0,0 +1,160 @@
+//! # Sharded Client
+//!
+//! A single `ResourceActor` processes every request for its entity type sequentially, which
+//! becomes a bottleneck for hot resources. This module spreads one resource type across `N`
+//! independent actors (shards) and routes each request to the shard that owns the id.
+//!
+//! ## Routing
+//!
+//! - **By id** (`get`, `update`, `delete`, `perform_action`): `hash(id) % N` selects the shard,
+//!   so requests for independent ids are processed in parallel.
+//! - **Create**: shards are picked round-robin. Each shard only accepts ids from the shared
+//!   generator that hash to itself, so the new id always routes back to the shard holding it.
+//! - **Cross-shard** (`list`, `count`): fan out to every shard and merge the results. These are
+//!   *not* a consistent snapshot; shards answer independently.
+
+use crate::actor::ResourceActor;
+use crate::client::ResourceClient;
+use crate::entity::ActorEntity;
+use crate::error::FrameworkError;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A client that routes requests across `N` actor shards by id.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let counter = AtomicU32::new(0);
+/// let products = ShardedClient::<Product>::new(4, 32, move || {
+///     counter.fetch_add(1, Ordering::Relaxed) + 1
+/// });
+/// let id = products.create(params).await?;
+/// let product = products.get(id).await?;
+/// ```
+pub struct ShardedClient<T: ActorEntity> {
+    shards: Vec<ResourceClient<T>>,
+    next_shard: Arc<AtomicUsize>,
+}
+
+impl<T: ActorEntity> Clone for ShardedClient<T> {
+    fn clone(&self) -> Self {
+        Self {
+            shards: self.shards.clone(),
+            next_shard: self.next_shard.clone(),
+        }
+    }
+}
+
+impl<T: ActorEntity> ShardedClient<T>
+where
+    T::Context: Default,
+{
+    /// Spawns `n` actors and returns a client routing across them.
+    ///
+    /// `next_id_fn` is shared by all shards and must return unique values. A shard skips
+    /// values whose id does not hash to it, so generated ids are unique but not contiguous.
+    ///
+    /// # Panics
+    /// Panics if `n` is zero.
+    pub fn new(
+        n: usize,
+        buffer_size: usize,
+        next_id_fn: impl Fn() -> u32 + Send + Sync + 'static,
+    ) -> Self {
+        assert!(n > 0, "ShardedClient requires at least one shard");
+        let next_id_fn = Arc::new(next_id_fn);
+
+        let shards = (0..n)
+            .map(|shard| {
+                let next_id_fn = next_id_fn.clone();
+                let (actor, client) =
+                    ResourceActor::<T>::with_id_generator(buffer_size, move || loop {
+                        let raw = next_id_fn();
+                        if shard_for(&T::Id::from(raw), n) == shard {
+                            return raw;
+                        }
+                    });
+                tokio::spawn(actor.run(T::Context::default()));
+                client
+            })
+            .collect();
+
+        Self {
+            shards,
+            next_shard: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+impl<T: ActorEntity> ShardedClient<T> {
+    /// Returns the number of shards.
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns the client for the shard that owns `id`.
+    pub fn shard(&self, id: &T::Id) -> &ResourceClient<T> {
+        &self.shards[shard_for(id, self.shards.len())]
+    }
+
+    pub async fn create(&self, params: T::Create) -> Result<T::Id, FrameworkError> {
+        let shard = self.next_shard.fetch_add(1, Ordering::Relaxed) % self.shards.len();
+        self.shards[shard].create(params).await
+    }
+
+    pub async fn get(&self, id: impl Into<T::Id>) -> Result<Option<T>, FrameworkError> {
+        let id = id.into();
+        self.shard(&id).get(id).await
+    }
+
+    pub async fn update(
+        &self,
+        id: impl Into<T::Id>,
+        update: T::Update,
+    ) -> Result<T, FrameworkError> {
+        let id = id.into();
+        self.shard(&id).update(id, update).await
+    }
+
+    pub async fn delete(&self, id: impl Into<T::Id>) -> Result<(), FrameworkError> {
+        let id = id.into();
+        self.shard(&id).delete(id).await
+    }
+
+    pub async fn perform_action(
+        &self,
+        id: impl Into<T::Id>,
+        action: T::Action,
+    ) -> Result<T::ActionResult, FrameworkError> {
+        let id = id.into();
+        self.shard(&id).perform_action(id, action).await
+    }
+
+    /// Lists entities from every shard (fan out, then merge).
+    pub async fn list(&self) -> Result<Vec<T>, FrameworkError> {
+        let mut items = Vec::new();
+        for shard in &self.shards {
+            items.extend(shard.list().await?);
+        }
+        Ok(items)
+    }
+
+    /// Sums the entity counts of every shard (fan out, then merge).
+    pub async fn count(&self) -> Result<usize, FrameworkError> {
+        let mut total = 0;
+        for shard in &self.shards {
+            total += shard.count().await?;
+        }
+        Ok(total)
+    }
+}
+
+/// Maps an id to its owning shard index.
+fn shard_for<Id: Hash>(id: &Id, n: usize) -> usize {
+    let mut hasher = DefaultHasher::new();
+    id.hash(&mut hasher);
+    (hasher.finish() % n as u64) as usize
+}
@@ -0,0 +1,50 @@
+//! # Actor Statistics
+//!
+//! Runtime counters kept by every `ResourceActor` and read with
+//! [`ResourceClient::stats`](crate::ResourceClient::stats).
+//!
+//! The split between idle and busy time tells apart the two ways an actor can disappoint:
+//!
+//! - **Overloaded**: `busy_time` dominates and callers wait on a full channel. Shard the
+//!   resource or make the hooks cheaper.
+//! - **Starved**: `idle_time` dominates. The actor is fine; the bottleneck is upstream.
+
+use std::time::Duration;
+
+/// A snapshot of one actor's runtime counters since it started.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ActorStats {
+    /// Requests taken off the channel (including the `Stats` request itself).
+    pub messages: u64,
+    /// Operations that failed, e.g. `NotFound` or a hook error. Each item of an `ActionMany`
+    /// counts on its own, so this can exceed `messages`.
+    pub errors: u64,
+    /// Time spent waiting in `recv` for the next request or expiry tick.
+    pub idle_time: Duration,
+    /// Time spent handling requests and expiring entities, hooks included.
+    pub busy_time: Duration,
+    /// Entities evicted to stay within a capacity limit (see [`eviction`](crate::eviction)).
+    pub evictions: u64,
+}
+
+impl ActorStats {
+    /// Fraction of the measured time spent busy, from `0.0` (always waiting) to `1.0`
+    /// (never waiting). Returns `0.0` before anything has been measured.
+    pub fn utilization(&self) -> f64 {
+        let total = self.idle_time + self.busy_time;
+        if total.is_zero() {
+            0.0
+        } else {
+            self.busy_time.as_secs_f64() / total.as_secs_f64()
+        }
+    }
+
+    /// Failed operations per request taken off the channel. Returns `0.0` before any request.
+    pub fn error_rate(&self) -> f64 {
+        if self.messages == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.messages as f64
+        }
+    }
+}
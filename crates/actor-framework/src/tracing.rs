@@ -116,3 +116,17 @@ pub fn setup_tracing() {
         .compact() // Compact format shows spans inline (e.g., "order_processing:create_order")
         .init();
 }
+
+/// Installs a subscriber for tests: output goes through the test harness (shown only for
+/// failing tests) and is filtered by `RUST_LOG`, so tests are silent by default.
+///
+/// Unlike [`setup_tracing`], it can be called from every test: if a subscriber is already
+/// installed, it does nothing.
+pub fn setup_test_tracing() {
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .with_target(false)
+        .compact()
+        .with_test_writer()
+        .try_init();
+}
@@ -0,0 +1,70 @@
+//! # Input Validation
+//!
+//! A payload implementing [`Validate`] checks its own fields, independently of any actor, so
+//! an HTTP handler can reject a bad payload before it is sent and list every problem at once.
+//! An entity whose `Create` payload implements it can run the same check in
+//! `from_create_params`, and a [`ResourceClient::validating`](crate::ResourceClient::validating)
+//! client runs it before sending a create at all.
+
+use std::fmt::Display;
+
+/// A payload that can check its own fields.
+pub trait Validate {
+    /// Returns every field that is invalid, or `Ok(())` if none is.
+    fn validate(&self) -> Result<(), ValidationError>;
+}
+
+/// The invalid fields of a payload, each with a message saying why.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationError {
+    pub fields: Vec<FieldError>,
+}
+
+/// One invalid field of a payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl ValidationError {
+    /// Records that `field` is invalid.
+    pub fn add(&mut self, field: &'static str, message: impl Into<String>) {
+        self.fields.push(FieldError {
+            field,
+            message: message.into(),
+        });
+    }
+
+    /// Returns the message recorded for `field`, if it is invalid.
+    pub fn message(&self, field: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|error| error.field == field)
+            .map(|error| error.message.as_str())
+    }
+
+    /// `Ok(())` if no field was recorded, and `Err(self)` otherwise.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.fields.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+/// Lists the fields as `field: message`, separated by `; `.
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, error) in self.fields.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}: {}", error.field, error.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
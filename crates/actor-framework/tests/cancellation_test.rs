@@ -0,0 +1,135 @@
+use actor_framework::{ActorEntity, ResourceActor};
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+// --- Test Entity ---
+
+/// Created with the time its `on_create` takes; `on_delete` counts its calls in the context.
+#[derive(Clone, Debug)]
+struct Slow {
+    id: u32,
+    delay: Duration,
+}
+
+#[async_trait]
+impl ActorEntity for Slow {
+    type Id = u32;
+    type Create = Duration;
+    type Update = ();
+    type Action = ();
+    type ActionResult = ();
+    type Context = Arc<AtomicUsize>;
+    type Error = std::convert::Infallible;
+
+    fn from_create_params(id: u32, delay: Duration) -> Result<Self, Self::Error> {
+        Ok(Self { id, delay })
+    }
+
+    fn id(&self) -> &u32 {
+        &self.id
+    }
+
+    async fn on_create(&mut self, _ctx: &Self::Context) -> Result<(), Self::Error> {
+        tokio::time::sleep(self.delay).await;
+        Ok(())
+    }
+
+    async fn on_update(&mut self, _update: (), _ctx: &Self::Context) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    async fn handle_action(
+        &mut self,
+        _action: (),
+        _ctx: &Self::Context,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn on_delete(&self, deletes: &Self::Context) -> Result<(), Self::Error> {
+        deletes.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+// --- Tests ---
+
+/// Polls `request` once, which enqueues it, then drops it before the actor replies.
+async fn send_then_drop(request: impl Future) {
+    tokio::select! {
+        biased;
+        _ = request => panic!("request completed without the actor running"),
+        _ = std::future::ready(()) => {}
+    }
+}
+
+#[tokio::test]
+async fn test_dropped_create_still_creates() {
+    let deletes = Arc::new(AtomicUsize::new(0));
+    let (mut actor, client) = ResourceActor::<Slow>::new(10);
+
+    send_then_drop(client.create(Duration::ZERO)).await;
+    assert!(actor.step(&deletes).await);
+    tokio::spawn(actor.run(deletes));
+
+    // The create ran; its id is lost with the dropped future
+    assert_eq!(client.count().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_dropped_get_leaves_state_unchanged() {
+    let deletes = Arc::new(AtomicUsize::new(0));
+    let (mut actor, client) = ResourceActor::<Slow>::new(10);
+    let create = tokio::spawn({
+        let client = client.clone();
+        async move { client.create(Duration::ZERO).await }
+    });
+    tokio::task::yield_now().await;
+    assert!(actor.step(&deletes).await);
+    let id = create.await.unwrap().unwrap();
+
+    send_then_drop(client.get(id)).await;
+    assert!(actor.step(&deletes).await);
+    tokio::spawn(actor.run(deletes));
+
+    assert!(client.get(id).await.unwrap().is_some());
+    assert_eq!(client.count().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_create_cancel_safe_skips_abandoned_create() {
+    let deletes = Arc::new(AtomicUsize::new(0));
+    let (mut actor, client) = ResourceActor::<Slow>::new(10);
+
+    send_then_drop(client.create_cancel_safe(Duration::ZERO)).await;
+    assert!(actor.step(&deletes).await);
+    tokio::spawn(actor.run(deletes.clone()));
+
+    assert_eq!(client.count().await.unwrap(), 0);
+    assert_eq!(deletes.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_create_cancel_safe_undoes_create_abandoned_mid_run() {
+    let deletes = Arc::new(AtomicUsize::new(0));
+    let (actor, client) = ResourceActor::<Slow>::new(10);
+    tokio::spawn(actor.run(deletes.clone()));
+
+    // The caller gives up while on_create is still running
+    let abandoned = tokio::time::timeout(
+        Duration::from_millis(20),
+        client.create_cancel_safe(Duration::from_millis(200)),
+    )
+    .await;
+    assert!(abandoned.is_err());
+
+    // Queued behind the create, so answered once it has been undone
+    assert_eq!(client.count().await.unwrap(), 0);
+    assert_eq!(deletes.load(Ordering::SeqCst), 1);
+
+    let id = client.create_cancel_safe(Duration::ZERO).await.unwrap();
+    assert!(client.get(id).await.unwrap().is_some());
+}
@@ -0,0 +1,99 @@
+use actor_framework::{ActorEntity, HasClock, ResourceActor, TestClock};
+use async_trait::async_trait;
+use std::time::{Duration, Instant};
+
+// --- Test Entity ---
+
+/// A session that reports itself expired once `ttl` has passed on the context's clock.
+#[derive(Clone, Debug)]
+struct Session {
+    id: u32,
+    created_at: Option<Instant>,
+    ttl: Duration,
+}
+
+#[derive(Debug)]
+struct SessionCreate {
+    ttl: Duration,
+}
+
+#[derive(Debug)]
+enum SessionAction {
+    IsExpired,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Session error")]
+struct SessionError;
+
+#[async_trait]
+impl ActorEntity for Session {
+    type Id = u32;
+    type Create = SessionCreate;
+    type Update = ();
+    type Action = SessionAction;
+    type ActionResult = bool;
+    type Context = TestClock;
+    type Error = SessionError;
+
+    fn from_create_params(id: u32, params: SessionCreate) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id,
+            created_at: None,
+            ttl: params.ttl,
+        })
+    }
+
+    fn id(&self) -> &u32 {
+        &self.id
+    }
+
+    async fn on_create(&mut self, ctx: &TestClock) -> Result<(), Self::Error> {
+        self.created_at = Some(ctx.now());
+        Ok(())
+    }
+
+    async fn on_update(&mut self, _: (), _ctx: &TestClock) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    async fn handle_action(
+        &mut self,
+        action: SessionAction,
+        ctx: &TestClock,
+    ) -> Result<bool, Self::Error> {
+        match action {
+            SessionAction::IsExpired => {
+                let created_at = self.created_at.ok_or(SessionError)?;
+                Ok(ctx.now().duration_since(created_at) >= self.ttl)
+            }
+        }
+    }
+}
+
+// --- Test ---
+
+#[tokio::test]
+async fn test_entity_reads_time_from_test_clock() {
+    let clock = TestClock::new();
+    let (actor, client) = ResourceActor::<Session>::new(10);
+    tokio::spawn(actor.run(clock.clone()));
+
+    let id = client
+        .create(SessionCreate {
+            ttl: Duration::from_secs(15 * 60),
+        })
+        .await
+        .unwrap();
+    assert!(!client
+        .perform_action(id, SessionAction::IsExpired)
+        .await
+        .unwrap());
+
+    // Fifteen minutes pass instantly
+    clock.advance(Duration::from_secs(15 * 60));
+    assert!(client
+        .perform_action(id, SessionAction::IsExpired)
+        .await
+        .unwrap());
+}
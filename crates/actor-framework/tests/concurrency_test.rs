@@ -0,0 +1,142 @@
+use actor_framework::{ActorEntity, ResourceActor};
+use async_trait::async_trait;
+
+// --- Test Entity ---
+
+#[derive(Clone, Debug)]
+struct Stock {
+    id: u32,
+    quantity: u32,
+}
+
+#[derive(Debug)]
+struct StockCreate {
+    quantity: u32,
+}
+
+#[derive(Debug)]
+enum StockAction {
+    Reserve(u32),
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Insufficient stock")]
+struct StockError;
+
+#[async_trait]
+impl ActorEntity for Stock {
+    type Id = u32;
+    type Create = StockCreate;
+    type Update = ();
+    type Action = StockAction;
+    type ActionResult = ();
+    type Context = ();
+    type Error = StockError;
+
+    fn from_create_params(id: u32, params: StockCreate) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id,
+            quantity: params.quantity,
+        })
+    }
+
+    fn id(&self) -> &u32 {
+        &self.id
+    }
+
+    async fn on_update(&mut self, _update: (), _ctx: &Self::Context) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    async fn handle_action(
+        &mut self,
+        action: StockAction,
+        _ctx: &Self::Context,
+    ) -> Result<(), Self::Error> {
+        match action {
+            StockAction::Reserve(quantity) => {
+                // Check-then-act: only safe because the actor processes messages sequentially
+                if self.quantity < quantity {
+                    return Err(StockError);
+                }
+                tokio::task::yield_now().await;
+                self.quantity -= quantity;
+                Ok(())
+            }
+        }
+    }
+}
+
+// --- Test ---
+
+/// Proves the sequential-processing invariant under contention: 100 concurrent reservations
+/// against 100 units all succeed, and the 101st fails. If the actor ever processed messages
+/// for one entity in parallel, the check-then-act in `Reserve` would oversell.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_actions_are_serialized() {
+    let (actor, client) = ResourceActor::<Stock>::new(16);
+    tokio::spawn(actor.run(()));
+    let id = client.create(StockCreate { quantity: 100 }).await.unwrap();
+
+    let handles: Vec<_> = (0..100)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.perform_action(id, StockAction::Reserve(1)).await })
+        })
+        .collect();
+
+    let mut successful = 0;
+    for handle in handles {
+        if handle.await.unwrap().is_ok() {
+            successful += 1;
+        }
+    }
+    assert_eq!(
+        successful, 100,
+        "Every reservation within stock must succeed"
+    );
+
+    let result = client.perform_action(id, StockAction::Reserve(1)).await;
+    assert!(result.is_err(), "The 101st reservation must fail");
+    assert_eq!(client.get(id).await.unwrap().unwrap().quantity, 0);
+}
+
+/// A pipeline runs without interleaving: two reads in one pipeline always agree, even while
+/// other tasks keep reserving stock between pipelines.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_pipeline_runs_contiguously() {
+    let (actor, client) = ResourceActor::<Stock>::new(16);
+    tokio::spawn(actor.run(()));
+    let id = client.create(StockCreate { quantity: 1000 }).await.unwrap();
+
+    let reservers: Vec<_> = (0..100)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.perform_action(id, StockAction::Reserve(1)).await })
+        })
+        .collect();
+    let readers: Vec<_> = (0..50)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move {
+                let mut pipeline = client.pipeline();
+                let first = pipeline.get(id);
+                let reserved = pipeline.perform_action(id, StockAction::Reserve(1));
+                let second = pipeline.get(id);
+                pipeline.send().await.unwrap();
+                let first = first.await.unwrap().unwrap().quantity;
+                reserved.await.unwrap();
+                let second = second.await.unwrap().unwrap().quantity;
+                assert_eq!(second, first - 1, "another request ran inside the pipeline");
+            })
+        })
+        .collect();
+
+    for handle in reservers {
+        handle.await.unwrap().unwrap();
+    }
+    for handle in readers {
+        handle.await.unwrap();
+    }
+    assert_eq!(client.get(id).await.unwrap().unwrap().quantity, 850);
+}
@@ -0,0 +1,133 @@
+use actor_framework::{ActorEntity, FrameworkError, ResourceActor};
+use async_trait::async_trait;
+use std::error::Error;
+
+// --- Test Entity ---
+
+#[derive(Clone, Debug)]
+struct Account {
+    id: u32,
+    balance: u32,
+    closed: bool,
+}
+
+#[derive(Debug)]
+enum AccountAction {
+    Withdraw(u32),
+    Close,
+}
+
+#[derive(Debug, thiserror::Error)]
+enum AccountError {
+    #[error("Account {0} is closed")]
+    Closed(u32),
+    #[error("Insufficient funds")]
+    InsufficientFunds,
+}
+
+#[async_trait]
+impl ActorEntity for Account {
+    type Id = u32;
+    type Create = u32;
+    type Update = ();
+    type Action = AccountAction;
+    type ActionResult = u32;
+    type Context = ();
+    type Error = AccountError;
+
+    fn from_create_params(id: u32, balance: u32) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id,
+            balance,
+            closed: false,
+        })
+    }
+
+    fn id(&self) -> &u32 {
+        &self.id
+    }
+
+    async fn on_update(&mut self, _update: (), _ctx: &Self::Context) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    async fn handle_action(
+        &mut self,
+        action: AccountAction,
+        _ctx: &Self::Context,
+    ) -> Result<u32, Self::Error> {
+        if self.closed {
+            return Err(AccountError::Closed(self.id));
+        }
+        match action {
+            AccountAction::Withdraw(amount) => {
+                self.balance = self
+                    .balance
+                    .checked_sub(amount)
+                    .ok_or(AccountError::InsufficientFunds)?;
+            }
+            AccountAction::Close => self.closed = true,
+        }
+        Ok(self.balance)
+    }
+
+    /// A closed account is gone as far as callers are concerned.
+    fn into_framework_error(error: AccountError) -> FrameworkError {
+        match error {
+            AccountError::Closed(id) => FrameworkError::NotFound(id.to_string()),
+            error => FrameworkError::entity(Self::entity_name(), error),
+        }
+    }
+}
+
+// --- Test ---
+
+/// An entity maps its own errors to framework errors; unmapped ones stay entity errors.
+#[tokio::test]
+async fn test_entity_maps_its_errors_to_framework_errors() {
+    let (actor, client) = ResourceActor::<Account>::new(8);
+    tokio::spawn(actor.run(()));
+    let id = client.create(10).await.unwrap();
+
+    let overdrawn = client
+        .perform_action(id, AccountAction::Withdraw(20))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&overdrawn, FrameworkError::EntityError { source, .. }
+            if source.downcast_ref::<AccountError>().is_some()),
+        "unmapped error must stay an entity error: {overdrawn:?}"
+    );
+
+    client
+        .perform_action(id, AccountAction::Close)
+        .await
+        .unwrap();
+    let closed = client
+        .perform_action(id, AccountAction::Withdraw(1))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&closed, FrameworkError::NotFound(found) if *found == id.to_string()),
+        "closed account must read as not found: {closed:?}"
+    );
+}
+
+/// The entity's error is the source of the framework error, for cause-chain reporting.
+#[tokio::test]
+async fn test_entity_error_is_the_source() {
+    let (actor, client) = ResourceActor::<Account>::new(8);
+    tokio::spawn(actor.run(()));
+    let id = client.create(10).await.unwrap();
+
+    let error = client
+        .perform_action(id, AccountAction::Withdraw(20))
+        .await
+        .unwrap_err();
+    let source = error.source().expect("an entity error has a source");
+    assert!(matches!(
+        source.downcast_ref::<AccountError>(),
+        Some(AccountError::InsufficientFunds)
+    ));
+    assert_eq!(source.to_string(), "Insufficient funds");
+}
@@ -0,0 +1,133 @@
+use actor_framework::{ActorEntity, EvictionPolicy, RequestKind, ResourceActor};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+// --- Test Entity ---
+
+/// A cached value; its `on_delete` records the id, so tests can see which entries were evicted.
+#[derive(Clone, Debug)]
+struct Entry {
+    id: u32,
+}
+
+#[async_trait]
+impl ActorEntity for Entry {
+    type Id = u32;
+    type Create = ();
+    type Update = ();
+    type Action = ();
+    type ActionResult = ();
+    type Context = Arc<Mutex<Vec<u32>>>;
+    type Error = std::convert::Infallible;
+
+    fn from_create_params(id: u32, _params: ()) -> Result<Self, Self::Error> {
+        Ok(Self { id })
+    }
+
+    fn id(&self) -> &u32 {
+        &self.id
+    }
+
+    async fn on_update(&mut self, _update: (), _ctx: &Self::Context) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    async fn handle_action(
+        &mut self,
+        _action: (),
+        _ctx: &Self::Context,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn on_delete(&self, deleted: &Self::Context) -> Result<(), Self::Error> {
+        deleted.lock().unwrap().push(self.id);
+        Ok(())
+    }
+}
+
+// --- Tests ---
+
+/// Creates entries 1 to 3 in an actor limited to 3, reads entry 1, then creates entry 4.
+/// Returns the ids still stored and the ids whose `on_delete` ran.
+async fn fill_read_and_overflow(policy: EvictionPolicy) -> (Vec<u32>, Vec<u32>) {
+    let deleted = Arc::new(Mutex::new(Vec::new()));
+    let (actor, client) = ResourceActor::<Entry>::new(10);
+    tokio::spawn(actor.with_capacity_limit(3, policy).run(deleted.clone()));
+
+    for _ in 0..3 {
+        client.create(()).await.unwrap();
+    }
+    client.get(1u32).await.unwrap().unwrap();
+    client.create(()).await.unwrap();
+
+    let mut stored: Vec<u32> = client.list().await.unwrap().iter().map(|e| e.id).collect();
+    stored.sort_unstable();
+    assert_eq!(client.stats().await.unwrap().evictions, 1);
+    let deleted = deleted.lock().unwrap().clone();
+    (stored, deleted)
+}
+
+#[tokio::test]
+async fn test_lru_evicts_least_recently_used() {
+    let (stored, deleted) = fill_read_and_overflow(EvictionPolicy::Lru).await;
+    // Entry 1 was read after entry 2 was created
+    assert_eq!(stored, vec![1, 3, 4]);
+    assert_eq!(deleted, vec![2]);
+}
+
+#[tokio::test]
+async fn test_fifo_evicts_oldest_regardless_of_use() {
+    let (stored, deleted) = fill_read_and_overflow(EvictionPolicy::Fifo).await;
+    assert_eq!(stored, vec![2, 3, 4]);
+    assert_eq!(deleted, vec![1]);
+}
+
+#[tokio::test]
+async fn test_deleted_entities_free_capacity() {
+    let deleted = Arc::new(Mutex::new(Vec::new()));
+    let (actor, client) = ResourceActor::<Entry>::new(10);
+    tokio::spawn(
+        actor
+            .with_capacity_limit(2, EvictionPolicy::Lru)
+            .run(deleted.clone()),
+    );
+
+    client.create(()).await.unwrap();
+    client.create(()).await.unwrap();
+    client.delete(1u32).await.unwrap();
+    client.create(()).await.unwrap();
+
+    assert_eq!(client.count().await.unwrap(), 2);
+    assert_eq!(client.stats().await.unwrap().evictions, 0);
+    assert_eq!(*deleted.lock().unwrap(), vec![1]);
+}
+
+#[tokio::test]
+async fn test_eviction_is_audited() {
+    let (sink, mut entries) = tokio::sync::mpsc::channel(16);
+    let (actor, client) = ResourceActor::<Entry>::new(10);
+    tokio::spawn(
+        actor
+            .with_capacity_limit(1, EvictionPolicy::Fifo)
+            .with_audit(sink)
+            .run(Arc::default()),
+    );
+
+    client.create(()).await.unwrap();
+    client.create(()).await.unwrap();
+    drop(client);
+
+    let mut audited = Vec::new();
+    while let Some(entry) = entries.recv().await {
+        audited.push((entry.operation, entry.id));
+    }
+    assert_eq!(
+        audited,
+        [
+            (RequestKind::Create, Some("1".to_string())),
+            (RequestKind::Evict, Some("1".to_string())),
+            (RequestKind::Create, Some("2".to_string())),
+        ]
+    );
+}
@@ -0,0 +1,94 @@
+use actor_framework::{ActorEntity, ResourceActor};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+// --- Test Entity ---
+
+/// Reads how many turns a competing task has had; the action never awaits, like a cheap `Get`.
+#[derive(Clone, Debug)]
+struct Probe {
+    id: u32,
+}
+
+#[async_trait]
+impl ActorEntity for Probe {
+    type Id = u32;
+    type Create = ();
+    type Update = ();
+    type Action = ();
+    type ActionResult = usize;
+    type Context = Arc<AtomicUsize>;
+    type Error = std::convert::Infallible;
+
+    fn from_create_params(id: u32, _params: ()) -> Result<Self, Self::Error> {
+        Ok(Self { id })
+    }
+
+    fn id(&self) -> &u32 {
+        &self.id
+    }
+
+    async fn on_update(&mut self, _update: (), _ctx: &Self::Context) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    async fn handle_action(
+        &mut self,
+        _action: (),
+        turns: &Self::Context,
+    ) -> Result<usize, Self::Error> {
+        Ok(turns.load(Ordering::SeqCst))
+    }
+}
+
+// --- Tests ---
+
+/// Runs `reads` cheap actions in one pipeline while another task counts its turns on the same
+/// current-thread runtime, returning the turn count seen by the first and the last read.
+async fn turns_during_burst(yield_every: usize, reads: usize) -> (usize, usize) {
+    let turns = Arc::new(AtomicUsize::new(0));
+    let (actor, client) = ResourceActor::<Probe>::new(16);
+    tokio::spawn(actor.with_yield_every(yield_every).run(turns.clone()));
+    let id = client.create(()).await.unwrap();
+
+    let competitor = tokio::spawn({
+        let turns = turns.clone();
+        async move {
+            loop {
+                turns.fetch_add(1, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+            }
+        }
+    });
+
+    let mut pipeline = client.pipeline();
+    let replies: Vec<_> = (0..reads)
+        .map(|_| pipeline.perform_action(id, ()))
+        .collect();
+    pipeline.send().await.unwrap();
+    let mut seen = Vec::new();
+    for reply in replies {
+        seen.push(reply.await.unwrap());
+    }
+    competitor.abort();
+    (seen[0], seen[reads - 1])
+}
+
+/// A burst of requests that never await still lets other tasks run on a current-thread runtime.
+#[tokio::test(flavor = "current_thread")]
+async fn test_actor_yields_during_burst() {
+    let (first, last) = turns_during_burst(8, 64).await;
+    assert!(
+        last >= first + 7,
+        "competing task ran {} times during the burst",
+        last - first
+    );
+}
+
+/// With yielding disabled the same burst runs start to finish without giving up the thread.
+#[tokio::test(flavor = "current_thread")]
+async fn test_actor_without_yielding_runs_burst_uninterrupted() {
+    let (first, last) = turns_during_burst(0, 64).await;
+    assert_eq!(first, last);
+}
@@ -0,0 +1,107 @@
+use actor_framework::{ActorEntity, FrameworkError, ResourceActor};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+// --- Test Entity ---
+
+/// Built like `User::new`: with a placeholder id, unless told to take the generated one.
+#[derive(Clone, Debug)]
+struct Tag {
+    id: u32,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Tag error")]
+struct TagError;
+
+#[async_trait]
+impl ActorEntity for Tag {
+    type Id = u32;
+    /// Whether `from_create_params` keeps the id it is given.
+    type Create = bool;
+    type Update = ();
+    type Action = ();
+    type ActionResult = ();
+    /// How many times `on_create` ran.
+    type Context = Arc<AtomicUsize>;
+    type Error = TagError;
+
+    fn from_create_params(id: u32, keep_id: bool) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: if keep_id { id } else { 0 },
+        })
+    }
+
+    fn id(&self) -> &u32 {
+        &self.id
+    }
+
+    async fn on_create(&mut self, created: &Self::Context) -> Result<(), Self::Error> {
+        created.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn on_update(&mut self, _update: (), _ctx: &Self::Context) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    async fn handle_action(
+        &mut self,
+        _action: (),
+        _ctx: &Self::Context,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+// --- Tests ---
+
+#[tokio::test]
+async fn test_stored_entities_report_their_key() {
+    let (actor, client) = ResourceActor::<Tag>::new(8);
+    tokio::spawn(actor.run(Arc::default()));
+
+    for _ in 0..3 {
+        let id = client.create(true).await.unwrap();
+        assert_eq!(client.get(id).await.unwrap().unwrap().id(), &id);
+    }
+}
+
+#[tokio::test]
+async fn test_replace_rejects_an_entity_with_another_id() {
+    let (actor, client) = ResourceActor::<Tag>::new(8);
+    tokio::spawn(actor.run(Arc::default()));
+    let first = client.create(true).await.unwrap();
+    let second = client.create(true).await.unwrap();
+
+    let result = client.replace(first, Tag { id: second }).await;
+    assert!(
+        matches!(&result, Err(FrameworkError::Validation(e)) if e.message("id").is_some()),
+        "{result:?}"
+    );
+    assert_eq!(client.get(first).await.unwrap().unwrap().id(), &first);
+    assert!(client.replace(first, Tag { id: first }).await.is_ok());
+}
+
+/// In debug builds, an entity that keeps a placeholder id stops the actor at its create,
+/// before `on_create` runs.
+#[cfg(debug_assertions)]
+#[tokio::test]
+async fn test_placeholder_id_fails_fast() {
+    let (actor, client) = ResourceActor::<Tag>::new(8);
+    let created = Arc::new(AtomicUsize::new(0));
+    let handle = tokio::spawn(actor.run(created.clone()));
+
+    assert!(matches!(
+        client.create(false).await,
+        Err(FrameworkError::ActorDropped)
+    ));
+    let panic = handle.await.unwrap_err().into_panic();
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert!(
+        message.contains("Tag entity does not report the id it was created with"),
+        "{message}"
+    );
+    assert_eq!(created.load(Ordering::SeqCst), 0);
+}
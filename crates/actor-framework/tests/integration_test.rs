@@ -1,5 +1,14 @@
-use actor_framework::{ActorEntity, ResourceActor};
+use actor_framework::{
+    ActorEntity, FrameworkError, HookPanic, RequestKind, ResourceActor, ResourceEvent, TypedAction,
+    Validate, ValidationError,
+};
 use async_trait::async_trait;
+use futures::StreamExt;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Store size observed by the most recent `on_stop` call (`usize::MAX` if never called).
+static STOPPED_WITH: AtomicUsize = AtomicUsize::new(usize::MAX);
 
 // --- Test Entity ---
 
@@ -15,6 +24,22 @@ struct SimpleUserCreate {
     name: String,
 }
 
+impl Validate for SimpleUserCreate {
+    fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = ValidationError::default();
+        if self.name.is_empty() {
+            errors.add("name", "must not be empty");
+        }
+        errors.into_result()
+    }
+}
+
+impl From<SimpleUser> for SimpleUserCreate {
+    fn from(user: SimpleUser) -> Self {
+        Self { name: user.name }
+    }
+}
+
 #[derive(Debug)]
 struct SimpleUserUpdate {
     name: Option<String>,
@@ -23,8 +48,8 @@ struct SimpleUserUpdate {
 #[derive(Debug)]
 enum UserAction {
     PromoteToAdmin,
-    #[allow(dead_code)]
     Rename(String),
+    Panic,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -49,15 +74,29 @@ impl ActorEntity for SimpleUser {
         })
     }
 
+    fn id(&self) -> &u32 {
+        &self.id
+    }
+
+    fn validate(&self) -> Result<(), Self::Error> {
+        if self.name.is_empty() {
+            return Err(SimpleUserError);
+        }
+        Ok(())
+    }
+
     async fn on_update(
         &mut self,
         update: SimpleUserUpdate,
         _ctx: &Self::Context,
-    ) -> Result<(), Self::Error> {
-        if let Some(name) = update.name {
-            self.name = name;
+    ) -> Result<bool, Self::Error> {
+        match update.name {
+            Some(name) if name != self.name => {
+                self.name = name;
+                Ok(true)
+            }
+            _ => Ok(false),
         }
-        Ok(())
     }
 
     async fn handle_action(
@@ -78,8 +117,14 @@ impl ActorEntity for SimpleUser {
                 self.name = new_name;
                 Ok(true)
             }
+            UserAction::Panic => panic!("action exploded"),
         }
     }
+
+    async fn on_stop(store: &HashMap<u32, Self>, _ctx: &Self::Context) -> Result<(), Self::Error> {
+        STOPPED_WITH.store(store.len(), Ordering::SeqCst);
+        Ok(())
+    }
 }
 
 // --- Test ---
@@ -99,18 +144,18 @@ async fn test_framework_full_lifecycle() {
 
     // 2. Perform Action: Promote
     let changed: bool = client
-        .perform_action(id.clone(), UserAction::PromoteToAdmin)
+        .perform_action(id, UserAction::PromoteToAdmin)
         .await
         .unwrap();
     assert!(changed);
 
     // Verify state
-    let user: SimpleUser = client.get(id.clone()).await.unwrap().unwrap();
+    let user: SimpleUser = client.get(id).await.unwrap().unwrap();
     assert!(user.is_admin);
 
     // 3. Perform Action: Promote again (should return false)
     let changed_again: bool = client
-        .perform_action(id.clone(), UserAction::PromoteToAdmin)
+        .perform_action(id, UserAction::PromoteToAdmin)
         .await
         .unwrap();
     assert!(!changed_again);
@@ -119,11 +164,1549 @@ async fn test_framework_full_lifecycle() {
     let update = SimpleUserUpdate {
         name: Some("Bob".into()),
     };
-    let updated_user = client.update(id.clone(), update).await.unwrap();
+    let updated_user = client.update(id, update).await.unwrap();
     assert_eq!(updated_user.name, "Bob");
 
-    // 5. Delete
-    client.delete(id.clone()).await.unwrap();
-    let deleted_user = client.get(id.clone()).await.unwrap();
+    // 5. Replace
+    let replaced = client
+        .replace(
+            id,
+            SimpleUser {
+                id,
+                name: "Dave".into(),
+                is_admin: false,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(replaced.name, "Dave");
+    assert!(!replaced.is_admin);
+
+    // 6. List & Count
+    let users = client.list().await.unwrap();
+    assert_eq!(users, vec![replaced]);
+    assert_eq!(client.count().await.unwrap(), 1);
+
+    // 7. Delete
+    client.delete(id).await.unwrap();
+    let deleted_user = client.get(id).await.unwrap();
     assert!(deleted_user.is_none());
+    assert_eq!(client.count().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_replace_rejects_an_invalid_entity() {
+    let (actor, client) = ResourceActor::new(10);
+    tokio::spawn(actor.run(()));
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Alice".into(),
+        })
+        .await
+        .unwrap();
+
+    let result = client
+        .replace(
+            id,
+            SimpleUser {
+                id,
+                name: String::new(),
+                is_admin: true,
+            },
+        )
+        .await;
+    assert!(
+        matches!(&result, Err(FrameworkError::EntityError { .. })),
+        "{result:?}"
+    );
+    let stored = client.get(id).await.unwrap().unwrap();
+    assert_eq!((stored.name.as_str(), stored.is_admin), ("Alice", false));
+}
+
+#[tokio::test]
+async fn test_on_stop_receives_final_store() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    let handle = tokio::spawn(actor.run(()));
+
+    client
+        .create(SimpleUserCreate {
+            name: "Alice".into(),
+        })
+        .await
+        .unwrap();
+    client
+        .create(SimpleUserCreate { name: "Bob".into() })
+        .await
+        .unwrap();
+
+    // Dropping the last client closes the channel and ends the loop
+    drop(client);
+    handle.await.unwrap();
+
+    assert_eq!(STOPPED_WITH.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_sharded_client_routes_by_id() {
+    use actor_framework::ShardedClient;
+    use std::sync::atomic::AtomicU32;
+
+    let counter = AtomicU32::new(0);
+    let client = ShardedClient::<SimpleUser>::new(4, 10, move || {
+        counter.fetch_add(1, Ordering::Relaxed) + 1
+    });
+
+    let mut ids = Vec::new();
+    for i in 0..20 {
+        let id = client
+            .create(SimpleUserCreate {
+                name: format!("user{i}"),
+            })
+            .await
+            .unwrap();
+        ids.push(id);
+    }
+
+    // Ids are unique across shards and each routes back to its owning shard
+    let mut unique = ids.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), 20);
+    for (i, id) in ids.iter().enumerate() {
+        let user = client.get(*id).await.unwrap().unwrap();
+        assert_eq!(user.name, format!("user{i}"));
+    }
+
+    assert_eq!(client.count().await.unwrap(), 20);
+    assert_eq!(client.list().await.unwrap().len(), 20);
+}
+
+#[tokio::test]
+async fn test_read_cache_serves_until_invalidated() {
+    use std::time::Duration;
+
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+    let cached = client.clone().with_read_cache(Duration::from_secs(60));
+
+    let id = cached
+        .create(SimpleUserCreate {
+            name: "Alice".into(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(cached.get(id).await.unwrap().unwrap().name, "Alice");
+
+    // A write through another client is not seen while the entry is fresh
+    client
+        .update(
+            id,
+            SimpleUserUpdate {
+                name: Some("Bob".into()),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(cached.get(id).await.unwrap().unwrap().name, "Alice");
+
+    // A write through the wrapper invalidates the entry
+    cached
+        .update(
+            id,
+            SimpleUserUpdate {
+                name: Some("Carol".into()),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(cached.get(id).await.unwrap().unwrap().name, "Carol");
+}
+
+/// A read answered before a write through the wrapper finishes must not be cached.
+#[tokio::test]
+async fn test_read_cache_drops_read_overlapping_a_write() {
+    use std::time::Duration;
+
+    let (mut actor, client) = ResourceActor::<SimpleUser>::new(10);
+    let cached = client.with_read_cache(Duration::from_secs(60));
+    let create = tokio::spawn({
+        let cached = cached.clone();
+        async move {
+            cached
+                .create(SimpleUserCreate {
+                    name: "Alice".into(),
+                })
+                .await
+        }
+    });
+    tokio::task::yield_now().await;
+    assert!(actor.step(&()).await);
+    let id = create.await.unwrap().unwrap();
+
+    // The get is queued ahead of the update, so it reads the old name
+    let get = tokio::spawn({
+        let cached = cached.clone();
+        async move { cached.get(id).await }
+    });
+    tokio::task::yield_now().await;
+    let update = tokio::spawn({
+        let cached = cached.clone();
+        async move {
+            cached
+                .update(
+                    id,
+                    SimpleUserUpdate {
+                        name: Some("Bob".into()),
+                    },
+                )
+                .await
+        }
+    });
+    tokio::task::yield_now().await;
+    assert!(actor.step(&()).await);
+    assert!(actor.step(&()).await);
+    update.await.unwrap().unwrap();
+    assert_eq!(get.await.unwrap().unwrap().unwrap().name, "Alice");
+
+    let fresh = tokio::spawn({
+        let cached = cached.clone();
+        async move { cached.get(id).await }
+    });
+    tokio::task::yield_now().await;
+    assert!(
+        actor.step(&()).await,
+        "the old name was served from the cache"
+    );
+    assert_eq!(fresh.await.unwrap().unwrap().unwrap().name, "Bob");
+}
+
+/// A write whose future is dropped after it was sent may still be applied, so a read that
+/// overlapped it must not stay cached.
+#[tokio::test]
+async fn test_read_cache_invalidates_when_a_write_is_dropped() {
+    use actor_framework::mock::{create_mock_client, expect_action, expect_get};
+    use std::time::Duration;
+
+    let (client, mut receiver) = create_mock_client::<SimpleUser>(10);
+    let cached = client.with_read_cache(Duration::from_secs(60));
+    let alice = SimpleUser {
+        id: 1,
+        name: "Alice".to_string(),
+        is_admin: false,
+    };
+
+    // The action is sent, but its reply never awaited
+    let action = tokio::spawn({
+        let cached = cached.clone();
+        async move {
+            cached
+                .perform_action(1u32, UserAction::PromoteToAdmin)
+                .await
+        }
+    });
+    let (_, _, _action_reply) = expect_action(&mut receiver).await.unwrap();
+
+    // A read answered meanwhile with the state before the action
+    let read = tokio::spawn({
+        let cached = cached.clone();
+        async move { cached.get(1u32).await }
+    });
+    let (_, respond_to) = expect_get(&mut receiver).await.unwrap();
+    respond_to.send(Ok(Some(alice.clone()))).unwrap();
+    read.await.unwrap().unwrap();
+
+    action.abort();
+    assert!(action.await.unwrap_err().is_cancelled());
+
+    // The next read goes to the actor instead of the cache
+    let read = tokio::spawn({
+        let cached = cached.clone();
+        async move { cached.get(1u32).await }
+    });
+    let (_, respond_to) = tokio::time::timeout(Duration::from_secs(1), expect_get(&mut receiver))
+        .await
+        .expect("the read was served from the cache")
+        .unwrap();
+    let admin = SimpleUser {
+        is_admin: true,
+        ..alice
+    };
+    respond_to.send(Ok(Some(admin))).unwrap();
+    assert!(read.await.unwrap().unwrap().unwrap().is_admin);
+}
+
+#[tokio::test]
+async fn test_client_debug_reports_entity_and_closed_state() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    assert_eq!(
+        format!("{client:?}"),
+        "ResourceClient<SimpleUser> { closed: false }"
+    );
+
+    drop(actor);
+    assert_eq!(
+        format!("{client:?}"),
+        "ResourceClient<SimpleUser> { closed: true }"
+    );
+}
+
+#[test]
+fn test_same_actor_compares_channels() {
+    let (_actor, client) = ResourceActor::<SimpleUser>::new(10);
+    let (_other_actor, other) = ResourceActor::<SimpleUser>::new(10);
+
+    assert!(client.same_actor(&client.clone()));
+    assert!(client.same_actor(&client.with_request_context(7u32)));
+    assert!(!client.same_actor(&other));
+}
+
+#[tokio::test]
+async fn test_create_returning_returns_stored_entity() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+
+    let user = client
+        .create_returning(SimpleUserCreate {
+            name: "Alice".into(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(user.id, 1);
+    assert_eq!(user.name, "Alice");
+    assert_eq!(client.get(user.id).await.unwrap(), Some(user));
+}
+
+#[tokio::test]
+async fn test_create_from_ignores_id_and_server_set_fields() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+
+    let template = SimpleUser {
+        id: 42,
+        name: "Alice".into(),
+        is_admin: true,
+    };
+    let id = client.create_from(template).await.unwrap();
+    assert_eq!(id, 1);
+    let stored = client.get(id).await.unwrap().unwrap();
+    assert_eq!(stored.name, "Alice");
+    assert!(!stored.is_admin);
+}
+
+#[tokio::test]
+async fn test_start_spawns_actor_with_generator() {
+    let mut next = 100;
+    let (client, handle) = ResourceActor::<SimpleUser>::start(
+        10,
+        move || {
+            next += 1;
+            next
+        },
+        (),
+    );
+
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(id, 101);
+
+    drop(client);
+    handle.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_perform_action_returning_returns_updated_entity() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let (promoted, user) = client
+        .perform_action_returning(id, UserAction::PromoteToAdmin)
+        .await
+        .unwrap();
+    assert!(promoted);
+    assert!(user.is_admin);
+    assert_eq!(user, client.get(id).await.unwrap().unwrap());
+    assert!(matches!(
+        client
+            .perform_action_returning(id + 1, UserAction::PromoteToAdmin)
+            .await,
+        Err(FrameworkError::NotFound(_))
+    ));
+}
+
+/// `UserAction::PromoteToAdmin` as a typed action.
+struct Promote;
+
+impl From<Promote> for UserAction {
+    fn from(_: Promote) -> Self {
+        UserAction::PromoteToAdmin
+    }
+}
+
+impl TypedAction<SimpleUser> for Promote {
+    type Output = bool;
+
+    fn extract(result: bool) -> Result<bool, bool> {
+        Ok(result)
+    }
+}
+
+#[tokio::test]
+async fn test_typed_action_returns_output() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let promoted: bool = client.typed_action(id, Promote).await.unwrap();
+    assert!(promoted);
+    assert!(client.get(id).await.unwrap().unwrap().is_admin);
+    assert!(matches!(
+        client.typed_action(id + 1, Promote).await,
+        Err(FrameworkError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_delete_returning_returns_removed_entity() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let deleted = client.delete_returning(id).await.unwrap();
+    assert_eq!(deleted.name, "Alice");
+    assert!(client.get(id).await.unwrap().is_none());
+    assert!(matches!(
+        client.delete_returning(id).await,
+        Err(FrameworkError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_exhausted_counter_does_not_reuse_ids() {
+    let (actor, client) = ResourceActor::<SimpleUser>::with_counter(10, u32::MAX - 1);
+    tokio::spawn(actor.run(()));
+    let create = |name: &str| {
+        client.create(SimpleUserCreate {
+            name: name.to_string(),
+        })
+    };
+
+    assert_eq!(create("Alice").await.unwrap(), u32::MAX - 1);
+    assert_eq!(create("Bob").await.unwrap(), u32::MAX);
+    assert!(matches!(
+        create("Carol").await,
+        Err(FrameworkError::IdExhausted)
+    ));
+
+    assert_eq!(client.count().await.unwrap(), 2);
+    assert_eq!(
+        client.get(u32::MAX - 1).await.unwrap().unwrap().name,
+        "Alice"
+    );
+}
+
+#[tokio::test]
+async fn test_create_before_times_out_when_channel_full() {
+    use tokio::time::{Duration, Instant};
+
+    // The actor is never run, so the single buffer slot stays occupied
+    let (_actor, client) = ResourceActor::<SimpleUser>::new(1);
+    let blocker = client.clone();
+    tokio::spawn(async move {
+        let _ = blocker
+            .create(SimpleUserCreate {
+                name: "Blocker".into(),
+            })
+            .await;
+    });
+    tokio::task::yield_now().await;
+
+    let deadline = Instant::now() + Duration::from_millis(20);
+    let result = client
+        .create_before(
+            SimpleUserCreate {
+                name: "Late".into(),
+            },
+            deadline,
+        )
+        .await;
+    assert!(matches!(result, Err(FrameworkError::Timeout)));
+}
+
+#[tokio::test]
+async fn test_active_clients_counts_clones() {
+    let (_actor, client) = ResourceActor::<SimpleUser>::new(10);
+    assert_eq!(client.active_clients(), 1);
+
+    let other = client.clone();
+    assert_eq!(client.active_clients(), 2);
+
+    drop(other);
+    assert_eq!(client.active_clients(), 1);
+}
+
+#[tokio::test]
+async fn test_run_resilient_survives_panicking_action() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run_resilient(()));
+
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let err = client
+        .perform_action(id, UserAction::Panic)
+        .await
+        .unwrap_err();
+    match err {
+        FrameworkError::EntityError { source, .. } => {
+            let panic = source.downcast_ref::<HookPanic>().expect("HookPanic");
+            assert_eq!(panic.0, "action exploded");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    // The actor keeps serving requests after the panic
+    assert!(client
+        .perform_action(id, UserAction::PromoteToAdmin)
+        .await
+        .unwrap());
+    assert_eq!(client.get(id).await.unwrap().unwrap().name, "Alice");
+}
+
+#[tokio::test]
+async fn test_run_supervised_restarts_with_empty_store() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    let handle = tokio::spawn(actor.run_supervised((), 1));
+
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+
+    // The crash drops the in-flight request and restarts with an empty store
+    let err = client
+        .perform_action(id, UserAction::Panic)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, FrameworkError::ActorDropped));
+    assert!(client.get(id).await.unwrap().is_none());
+
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Bob".to_string(),
+        })
+        .await
+        .unwrap();
+
+    // The restart cap is reached: the actor stops for good
+    let err = client
+        .perform_action(id, UserAction::Panic)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, FrameworkError::ActorDropped));
+    handle.await.unwrap();
+    assert!(matches!(
+        client.count().await,
+        Err(FrameworkError::ActorClosed)
+    ));
+}
+
+#[tokio::test]
+async fn test_perform_action_many_returns_per_item_results() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+
+    let alice = client
+        .create(SimpleUserCreate {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let results = client
+        .perform_action_many(vec![
+            (alice, UserAction::PromoteToAdmin),
+            (999, UserAction::PromoteToAdmin),
+            (alice, UserAction::PromoteToAdmin),
+        ])
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), 3);
+    assert!(results[0].as_ref().unwrap());
+    assert!(matches!(results[1], Err(FrameworkError::NotFound(_))));
+    assert!(!results[2].as_ref().unwrap());
+}
+
+#[tokio::test]
+async fn test_try_update_returns_none_for_missing_entity() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let updated = client
+        .try_update(
+            id,
+            SimpleUserUpdate {
+                name: Some("Alicia".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.unwrap().name, "Alicia");
+
+    let missing = client
+        .try_update(999u32, SimpleUserUpdate { name: None })
+        .await
+        .unwrap();
+    assert!(missing.is_none());
+}
+
+/// Counts `INFO` events seen by the thread-local subscriber.
+struct InfoCounter(std::sync::Arc<AtomicUsize>);
+
+impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for InfoCounter {
+    fn on_event(
+        &self,
+        event: &tracing::Event<'_>,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        if *event.metadata().level() == tracing::Level::INFO {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Each recorded event's message and the name of the span it was emitted in.
+type EventLog = std::sync::Arc<std::sync::Mutex<Vec<(String, Option<String>)>>>;
+
+/// Records each event's message with the name of the span it was emitted in.
+struct EventSpans(EventLog);
+
+impl<S> tracing_subscriber::Layer<S> for EventSpans
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: tracing_subscriber::layer::Context<'_, S>) {
+        struct Message(String);
+        impl tracing::field::Visit for Message {
+            fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "message" {
+                    self.0 = format!("{value:?}");
+                }
+            }
+        }
+        let mut message = Message(String::new());
+        event.record(&mut message);
+        let span = ctx.event_span(event).map(|span| span.name().to_string());
+        self.0.lock().unwrap().push((message.0, span));
+    }
+}
+
+#[tokio::test]
+async fn test_requests_are_handled_inside_a_span() {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let events = EventLog::default();
+    let subscriber = tracing_subscriber::registry().with(EventSpans(events.clone()));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+    client
+        .update(
+            id,
+            SimpleUserUpdate {
+                name: Some("Alicia".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+    let events = events.lock().unwrap();
+    let span_of = |message: &str| {
+        events
+            .iter()
+            .find(|(m, _)| m == message)
+            .and_then(|(_, span)| span.clone())
+    };
+    assert_eq!(span_of("Updated").as_deref(), Some("handle"));
+    assert_eq!(span_of("Actor started"), None);
+}
+
+#[tokio::test]
+async fn test_unchanged_update_is_not_logged_as_updated() {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let events = EventLog::default();
+    let subscriber = tracing_subscriber::registry().with(EventSpans(events.clone()));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+    let rename = |name: &str| SimpleUserUpdate {
+        name: Some(name.to_string()),
+    };
+    client.update(id, rename("Alice")).await.unwrap();
+
+    let count = |message: &str| {
+        let events = events.lock().unwrap();
+        events.iter().filter(|(m, _)| m == message).count()
+    };
+    assert_eq!(count("Updated"), 0);
+    assert_eq!(count("update no-op"), 1);
+
+    client.update(id, rename("Alicia")).await.unwrap();
+    assert_eq!(count("Updated"), 1);
+}
+
+#[tokio::test]
+async fn test_blocked_sends_warn_once_at_capacity() {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let events = EventLog::default();
+    let subscriber = tracing_subscriber::registry().with(EventSpans(events.clone()));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    // The actor never runs, so the first create fills the channel and the rest block
+    let (_actor, client) = ResourceActor::<SimpleUser>::new(1);
+    for name in ["Alice", "Bob", "Carol"] {
+        let client = client.clone();
+        tokio::spawn(async move {
+            let _ = client
+                .create(SimpleUserCreate {
+                    name: name.to_string(),
+                })
+                .await;
+        });
+    }
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+
+    let events = events.lock().unwrap();
+    let warnings = events
+        .iter()
+        .filter(|(m, _)| m == "channel at capacity, caller will block")
+        .count();
+    assert_eq!(warnings, 1);
+}
+
+#[tokio::test]
+async fn test_quiet_actor_logs_no_per_operation_info() {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let infos = std::sync::Arc::new(AtomicUsize::new(0));
+    let subscriber = tracing_subscriber::registry().with(InfoCounter(infos.clone()));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    // The default current-thread runtime keeps the actor on this thread's subscriber
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.quiet().run(()));
+
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+    client
+        .update(
+            id,
+            SimpleUserUpdate {
+                name: Some("Alicia".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+    client
+        .perform_action(id, UserAction::PromoteToAdmin)
+        .await
+        .unwrap();
+    client.delete(id).await.unwrap();
+
+    // Only "Actor started" was logged at info
+    assert_eq!(infos.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_wait_until_polls_count() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+    let poll = std::time::Duration::from_millis(5);
+    let timeout = std::time::Duration::from_millis(100);
+
+    let writer = client.clone();
+    tokio::spawn(async move {
+        for name in ["a", "b", "c"] {
+            writer
+                .create(SimpleUserCreate {
+                    name: name.to_string(),
+                })
+                .await
+                .unwrap();
+        }
+    });
+
+    assert_eq!(
+        client.wait_until(|n| n == 3, poll, timeout).await.unwrap(),
+        3
+    );
+    assert!(matches!(
+        client.wait_until_empty(poll, timeout).await,
+        Err(FrameworkError::Timeout)
+    ));
+}
+
+#[tokio::test]
+async fn test_wait_for_count_is_answered_by_the_actor() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+    // Already true: answered at once
+    client.wait_for_count(0).await.unwrap();
+
+    let waiters: Vec<_> = [2, 2, 1]
+        .into_iter()
+        .map(|target| {
+            let client = client.clone();
+            tokio::spawn(async move { client.wait_for_count(target).await })
+        })
+        .collect();
+    tokio::task::yield_now().await;
+
+    let mut ids = Vec::new();
+    for name in ["a", "b"] {
+        let id = client
+            .create(SimpleUserCreate {
+                name: name.to_string(),
+            })
+            .await
+            .unwrap();
+        ids.push(id);
+    }
+    for waiter in waiters {
+        waiter.await.unwrap().unwrap();
+    }
+
+    let drained = tokio::spawn({
+        let client = client.clone();
+        async move { client.wait_for_count(0).await }
+    });
+    client.count().await.unwrap();
+    assert!(!drained.is_finished());
+
+    for id in ids {
+        client.delete(id).await.unwrap();
+    }
+    drained.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn test_stats_split_idle_and_busy_time() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+    // The actor is starved of work meanwhile
+    tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+    client.get(id).await.unwrap();
+
+    let stats = client.stats().await.unwrap();
+    assert_eq!(stats.messages, 3);
+    assert!(stats.idle_time >= std::time::Duration::from_millis(30));
+    assert!(stats.utilization() < 0.5);
+}
+
+#[tokio::test]
+async fn test_stats_count_failed_operations() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+    client.delete(999u32).await.unwrap_err();
+    let results = client
+        .perform_action_many(vec![
+            (id, UserAction::PromoteToAdmin),
+            (999, UserAction::PromoteToAdmin),
+        ])
+        .await
+        .unwrap();
+    assert!(results[1].is_err());
+
+    let stats = client.stats().await.unwrap();
+    assert_eq!(stats.errors, 2);
+    assert_eq!(stats.messages, 4);
+    assert_eq!(stats.error_rate(), 0.5);
+}
+
+#[tokio::test]
+async fn test_try_get_fails_fast_and_cache_falls_back_to_stale_entry() {
+    use actor_framework::mock::{create_mock_client, expect_get};
+
+    // A single-slot channel that we drain by hand
+    let (client, mut receiver) = create_mock_client::<SimpleUser>(1);
+    let cached = client.clone().with_read_cache(std::time::Duration::ZERO);
+    let alice = SimpleUser {
+        id: 1,
+        name: "Alice".to_string(),
+        is_admin: false,
+    };
+
+    // Populate the cache (the zero TTL makes the entry stale immediately)
+    let reader = cached.clone();
+    let read = tokio::spawn(async move { reader.get(1u32).await });
+    let (_, respond_to) = expect_get(&mut receiver).await.unwrap();
+    respond_to.send(Ok(Some(alice.clone()))).unwrap();
+    read.await.unwrap().unwrap();
+
+    // Occupy the only slot; nobody receives it
+    let blocker = client.clone();
+    tokio::spawn(async move { blocker.count().await });
+    tokio::task::yield_now().await;
+
+    assert!(matches!(
+        client.try_get(1u32).await,
+        Err(FrameworkError::Full)
+    ));
+    assert_eq!(cached.try_get(1u32).await.unwrap(), Some(alice));
+    assert!(matches!(
+        cached.try_get(2u32).await,
+        Err(FrameworkError::Full)
+    ));
+}
+
+#[tokio::test]
+async fn test_pipeline_replies_per_request() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+
+    // The default generator starts at 1, so the created user is id 1
+    let mut pipeline = client.pipeline();
+    let created = pipeline.create(SimpleUserCreate {
+        name: "Alice".to_string(),
+    });
+    let promoted = pipeline.perform_action(1u32, UserAction::PromoteToAdmin);
+    let fetched = pipeline.get(1u32);
+    let missing = pipeline.delete(999u32);
+    pipeline.send().await.unwrap();
+
+    assert_eq!(created.await.unwrap(), 1);
+    assert!(promoted.await.unwrap());
+    assert!(fetched.await.unwrap().unwrap().is_admin);
+    assert!(matches!(missing.await, Err(FrameworkError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn test_stream_yields_every_entity_in_chunks() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+    for i in 0..5 {
+        client
+            .create(SimpleUserCreate {
+                name: format!("user{i}"),
+            })
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(client.list_paginated(0, 2).await.unwrap().len(), 2);
+    assert_eq!(client.list_paginated(4, 2).await.unwrap().len(), 1);
+    assert!(client.list_paginated(5, 2).await.unwrap().is_empty());
+
+    let mut ids = Vec::new();
+    let mut entities = client.stream(2);
+    while let Some(user) = entities.next().await {
+        ids.push(user.unwrap().id);
+    }
+    ids.sort();
+    assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+    assert!(entities.next().await.is_none());
+
+    // Combinators from `StreamExt` work on it too
+    let mut names: Vec<String> = client
+        .stream(3)
+        .map(|user| user.unwrap().name)
+        .collect()
+        .await;
+    names.sort();
+    assert_eq!(names.len(), 5);
+}
+
+#[tokio::test]
+async fn test_perform_action_detached_runs_without_reply() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+
+    client
+        .perform_action_detached(id, UserAction::PromoteToAdmin)
+        .await
+        .unwrap();
+    // A failing detached action is only logged
+    client
+        .perform_action_detached(999u32, UserAction::PromoteToAdmin)
+        .await
+        .unwrap();
+
+    // Handled in send order, so the get observes the promotion
+    assert!(client.get(id).await.unwrap().unwrap().is_admin);
+}
+
+#[tokio::test]
+async fn test_from_events_replays_store_in_order() {
+    let user = |id, name: &str, is_admin| SimpleUser {
+        id,
+        name: name.to_string(),
+        is_admin,
+    };
+    let events = vec![
+        ResourceEvent::Created {
+            id: 1,
+            entity: user(1, "Alice", false),
+        },
+        ResourceEvent::Created {
+            id: 2,
+            entity: user(2, "Bob", false),
+        },
+        ResourceEvent::Updated {
+            id: 1,
+            entity: user(1, "Alice", true),
+        },
+        ResourceEvent::Deleted { id: 2 },
+    ];
+    let mut next = 2;
+    let (actor, client) = ResourceActor::<SimpleUser>::from_events(
+        10,
+        move || {
+            next += 1;
+            next
+        },
+        events,
+    );
+    tokio::spawn(actor.run(()));
+
+    assert_eq!(
+        client.get(1u32).await.unwrap(),
+        Some(user(1, "Alice", true))
+    );
+    assert_eq!(client.get(2u32).await.unwrap(), None);
+    // New entities continue after the replayed ids
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Carol".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(id, 3);
+    assert_eq!(client.count().await.unwrap(), 2);
+}
+
+#[test]
+fn test_framework_error_to_wire_maps_stable_codes() {
+    use actor_framework::{ErrorCode, WireError};
+
+    fn assert_serializable<T: serde::Serialize + serde::de::DeserializeOwned>() {}
+    assert_serializable::<WireError>();
+
+    assert_eq!(
+        FrameworkError::NotFound("user_7".to_string()).to_wire(),
+        WireError {
+            code: ErrorCode::NotFound,
+            message: "Item not found: user_7".to_string(),
+        }
+    );
+    assert_eq!(FrameworkError::Full.to_wire().code, ErrorCode::Full);
+    assert_eq!(FrameworkError::Timeout.to_wire().code, ErrorCode::Timeout);
+
+    let wire = FrameworkError::entity("User", HookPanic("boom".to_string())).to_wire();
+    assert_eq!(wire.code, ErrorCode::Entity);
+    assert_eq!(wire.message, "User entity error: Hook panicked: boom");
+}
+
+#[test]
+fn test_error_code_http_status() {
+    use actor_framework::ErrorCode;
+
+    assert_eq!(ErrorCode::NotFound.http_status(), 404);
+    assert_eq!(ErrorCode::Full.http_status(), 503);
+    assert_eq!(ErrorCode::Timeout.http_status(), 504);
+    assert_eq!(ErrorCode::VersionConflict.http_status(), 409);
+    assert_eq!(ErrorCode::Validation.http_status(), 400);
+    assert_eq!(
+        FrameworkError::ActorClosed.to_wire().code.http_status(),
+        503
+    );
+}
+
+#[tokio::test]
+async fn test_ready_waits_for_running_actor() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    let waiter = client.clone();
+    let ready = tokio::spawn(async move { waiter.ready().await });
+
+    tokio::task::yield_now().await;
+    assert!(!ready.is_finished(), "ready resolved before the actor ran");
+    tokio::spawn(actor.run(()));
+    ready.await.unwrap().unwrap();
+
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    drop(actor);
+    assert!(matches!(
+        client.ready().await,
+        Err(FrameworkError::ActorClosed)
+    ));
+}
+
+#[tokio::test]
+async fn test_max_batch_rejects_oversized_batches_whole() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.with_max_batch(2).run(()));
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let result = client
+        .perform_action_many(vec![
+            (id, UserAction::PromoteToAdmin),
+            (id, UserAction::Rename("Bob".to_string())),
+            (id, UserAction::Rename("Carol".to_string())),
+        ])
+        .await;
+    assert!(matches!(
+        result,
+        Err(FrameworkError::BatchTooLarge { size: 3, max: 2 })
+    ));
+
+    let mut pipeline = client.pipeline();
+    let promoted = pipeline.perform_action(id, UserAction::PromoteToAdmin);
+    let fetched = pipeline.get(id);
+    let deleted = pipeline.delete(id);
+    pipeline.send().await.unwrap();
+    assert!(matches!(
+        promoted.await,
+        Err(FrameworkError::BatchTooLarge { size: 3, max: 2 })
+    ));
+    assert!(fetched.await.is_err());
+    assert!(deleted.await.is_err());
+
+    // Nothing ran, and batches within the limit still do
+    let user = client.get(id).await.unwrap().unwrap();
+    assert!(!user.is_admin);
+    let results = client
+        .perform_action_many(vec![(id, UserAction::PromoteToAdmin)])
+        .await
+        .unwrap();
+    assert!(results[0].as_ref().unwrap());
+}
+
+#[tokio::test]
+async fn test_update_if_unchanged_detects_conflict() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+    let id: u32 = client
+        .create(SimpleUserCreate {
+            name: "Alice".into(),
+        })
+        .await
+        .unwrap();
+    let read = client.get(id).await.unwrap().unwrap();
+
+    // Another writer changes the entity after our read
+    client
+        .update(
+            id,
+            SimpleUserUpdate {
+                name: Some("Bob".into()),
+            },
+        )
+        .await
+        .unwrap();
+
+    let stale = client
+        .update_if_unchanged(
+            id,
+            read,
+            SimpleUserUpdate {
+                name: Some("Carol".into()),
+            },
+        )
+        .await;
+    assert!(matches!(stale, Err(FrameworkError::VersionConflict(_))));
+    assert_eq!(client.get(id).await.unwrap().unwrap().name, "Bob");
+
+    let fresh = client.get(id).await.unwrap().unwrap();
+    let updated = client
+        .update_if_unchanged(
+            id,
+            fresh,
+            SimpleUserUpdate {
+                name: Some("Carol".into()),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.name, "Carol");
+}
+
+#[tokio::test]
+async fn test_step_processes_requests_in_queue_order() {
+    let (mut actor, client) = ResourceActor::<SimpleUser>::new(10);
+    assert!(!actor.step(&()).await);
+
+    let creator = client.clone();
+    let created = tokio::spawn(async move {
+        creator
+            .create(SimpleUserCreate {
+                name: "Alice".into(),
+            })
+            .await
+    });
+    tokio::task::yield_now().await;
+    assert!(actor.step(&()).await);
+    let id: u32 = created.await.unwrap().unwrap();
+
+    // Two clients race to rename the user; Carol's request is queued first
+    let (carol, bob) = (client.clone(), client.clone());
+    let first = tokio::spawn(async move {
+        carol
+            .update(
+                id,
+                SimpleUserUpdate {
+                    name: Some("Carol".into()),
+                },
+            )
+            .await
+    });
+    tokio::task::yield_now().await;
+    let second = tokio::spawn(async move {
+        bob.update(
+            id,
+            SimpleUserUpdate {
+                name: Some("Bob".into()),
+            },
+        )
+        .await
+    });
+    tokio::task::yield_now().await;
+
+    assert!(actor.step(&()).await);
+    assert_eq!(first.await.unwrap().unwrap().name, "Carol");
+    assert!(!second.is_finished());
+    assert!(actor.step(&()).await);
+    assert_eq!(second.await.unwrap().unwrap().name, "Bob");
+    assert!(!actor.step(&()).await);
+}
+
+#[tokio::test]
+async fn test_find_where_filters_on_the_actor() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+    for name in ["Alice", "Bob", "Anna"] {
+        client
+            .create(SimpleUserCreate { name: name.into() })
+            .await
+            .unwrap();
+    }
+
+    let mut names: Vec<String> = client
+        .find_where(|user: &SimpleUser| user.name.starts_with('A'))
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|user| user.name)
+        .collect();
+    names.sort();
+    assert_eq!(names, ["Alice", "Anna"]);
+    assert!(client.find_where(|_| false).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_perform_action_atomic_rolls_back_on_failure() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+    for name in ["Alice", "Bob"] {
+        client
+            .create(SimpleUserCreate { name: name.into() })
+            .await
+            .unwrap();
+    }
+
+    let failed = client
+        .perform_action_atomic(vec![
+            (1, UserAction::Rename("Carol".into())),
+            (2, UserAction::PromoteToAdmin),
+            (99, UserAction::PromoteToAdmin),
+        ])
+        .await;
+    assert!(matches!(failed, Err(FrameworkError::NotFound(_))));
+    assert_eq!(client.get(1u32).await.unwrap().unwrap().name, "Alice");
+    assert!(!client.get(2u32).await.unwrap().unwrap().is_admin);
+
+    let results = client
+        .perform_action_atomic(vec![
+            (1, UserAction::Rename("Carol".into())),
+            (2, UserAction::PromoteToAdmin),
+            (2, UserAction::PromoteToAdmin),
+        ])
+        .await
+        .unwrap();
+    assert_eq!(results, [true, true, false]);
+    assert_eq!(client.get(1u32).await.unwrap().unwrap().name, "Carol");
+    assert!(client.get(2u32).await.unwrap().unwrap().is_admin);
+}
+
+#[tokio::test]
+async fn test_draining_rejects_writes_but_serves_reads() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    let handle = tokio::spawn(actor.run(()));
+    let id: u32 = client
+        .create(SimpleUserCreate {
+            name: "Alice".into(),
+        })
+        .await
+        .unwrap();
+
+    client.begin_draining().await.unwrap();
+
+    let create = client.create(SimpleUserCreate { name: "Bob".into() }).await;
+    assert!(matches!(create, Err(FrameworkError::ShuttingDown)));
+    let action = client.perform_action(id, UserAction::PromoteToAdmin).await;
+    assert!(matches!(action, Err(FrameworkError::ShuttingDown)));
+    assert!(!client.get(id).await.unwrap().unwrap().is_admin);
+    assert_eq!(client.count().await.unwrap(), 1);
+
+    drop(client);
+    handle.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_get_map_keys_found_entities_by_id() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+    for name in ["Alice", "Bob", "Carol"] {
+        client
+            .create(SimpleUserCreate { name: name.into() })
+            .await
+            .unwrap();
+    }
+
+    let users = client.get_map(vec![3, 1, 99]).await.unwrap();
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[&1].name, "Alice");
+    assert_eq!(users[&3].name, "Carol");
+    assert!(!users.contains_key(&99));
+}
+
+#[tokio::test]
+async fn test_deadline_scheduling_handles_nearest_deadline_first() {
+    use tokio::time::{Duration, Instant};
+
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    let mut actor = actor.with_deadline_scheduling(8);
+    let now = Instant::now();
+    let senders = [
+        ("Background", client.clone()),
+        ("Later", client.with_deadline(now + Duration::from_secs(60))),
+        ("Sooner", client.with_deadline(now + Duration::from_secs(1))),
+    ];
+    let mut tasks = Vec::new();
+    for (name, sender) in senders {
+        tasks.push(tokio::spawn(async move {
+            sender.create(SimpleUserCreate { name: name.into() }).await
+        }));
+        tokio::task::yield_now().await;
+    }
+
+    while actor.step(&()).await {}
+    let mut ids = Vec::new();
+    for task in tasks {
+        ids.push(task.await.unwrap().unwrap());
+    }
+    // Ids are assigned in handling order: both deadlines first, nearest first
+    assert_eq!(ids, vec![3, 2, 1]);
+}
+
+#[tokio::test]
+async fn test_pipeline_is_scheduled_by_its_client_deadline() {
+    use tokio::time::{Duration, Instant};
+
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    let mut actor = actor.with_deadline_scheduling(8);
+    let background = tokio::spawn({
+        let client = client.clone();
+        async move {
+            client
+                .create(SimpleUserCreate {
+                    name: "Background".into(),
+                })
+                .await
+        }
+    });
+    tokio::task::yield_now().await;
+    let mut pipeline = client
+        .with_deadline(Instant::now() + Duration::from_secs(1))
+        .pipeline();
+    let urgent = pipeline.create(SimpleUserCreate {
+        name: "Urgent".into(),
+    });
+    pipeline.send().await.unwrap();
+
+    while actor.step(&()).await {}
+    // Ids are assigned in handling order: the pipeline first
+    assert_eq!(urgent.await.unwrap(), 1);
+    assert_eq!(background.await.unwrap().unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_validating_pipeline_rejects_invalid_create_without_sending_it() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+
+    let mut pipeline = client.validating().pipeline();
+    let invalid = pipeline.create(SimpleUserCreate {
+        name: String::new(),
+    });
+    let valid = pipeline.create(SimpleUserCreate {
+        name: "Alice".to_string(),
+    });
+    pipeline.send().await.unwrap();
+
+    assert!(matches!(invalid.await, Err(FrameworkError::Validation(_))));
+    assert_eq!(valid.await.unwrap(), 1);
+    assert_eq!(client.count().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_audit_records_each_read_and_write() {
+    let (sink, mut entries) = tokio::sync::mpsc::channel(16);
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.with_audit(sink).run(()));
+
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+    client.get(id).await.unwrap();
+    client.list().await.unwrap();
+    client.stats().await.unwrap();
+    let mut pipeline = client.pipeline();
+    let promoted = pipeline.perform_action(id, UserAction::PromoteToAdmin);
+    let missing = pipeline.delete(id + 1);
+    pipeline.send().await.unwrap();
+    promoted.await.unwrap();
+    assert!(missing.await.is_err());
+    drop(client);
+
+    let mut audited = Vec::new();
+    while let Some(entry) = entries.recv().await {
+        assert_eq!(entry.entity_type, "SimpleUser");
+        audited.push((entry.operation, entry.id));
+    }
+    let id = Some(id.to_string());
+    assert_eq!(
+        audited,
+        [
+            (RequestKind::Create, id.clone()),
+            (RequestKind::Get, id.clone()),
+            (RequestKind::List, None),
+            (RequestKind::Action, id),
+            (RequestKind::Delete, Some("2".to_string())),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_validating_client_rejects_invalid_create_without_sending() {
+    let (actor, client) = ResourceActor::<SimpleUser>::new(10);
+    tokio::spawn(actor.run(()));
+    let client = client.validating();
+
+    let invalid = client
+        .create(SimpleUserCreate {
+            name: String::new(),
+        })
+        .await;
+    let Err(FrameworkError::Validation(error)) = invalid else {
+        panic!("empty name must fail validation: {invalid:?}");
+    };
+    assert_eq!(error.message("name"), Some("must not be empty"));
+    assert!(client
+        .create_returning(SimpleUserCreate {
+            name: String::new(),
+        })
+        .await
+        .is_err());
+    // Only the stats request itself reached the actor
+    assert_eq!(client.stats().await.unwrap().messages, 1);
+
+    let id = client
+        .create(SimpleUserCreate {
+            name: "Alice".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(client.get(id).await.unwrap().unwrap().name, "Alice");
 }
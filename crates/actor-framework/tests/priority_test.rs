@@ -0,0 +1,178 @@
+use actor_framework::{ActorEntity, FrameworkError, ResourceActor, StopReason};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// --- Test Entity ---
+
+/// Records the store size and reason seen by the stop hook.
+type StopLog = Arc<Mutex<Option<(usize, StopReason)>>>;
+
+/// Every action takes a while, so a few of them build a backlog.
+#[derive(Clone, Debug)]
+struct Job {
+    id: u32,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Job error")]
+struct JobError;
+
+#[async_trait]
+impl ActorEntity for Job {
+    type Id = u32;
+    type Create = ();
+    type Update = ();
+    type Action = ();
+    type ActionResult = ();
+    type Context = StopLog;
+    type Error = JobError;
+
+    fn from_create_params(id: u32, _params: ()) -> Result<Self, Self::Error> {
+        Ok(Self { id })
+    }
+
+    fn id(&self) -> &u32 {
+        &self.id
+    }
+
+    async fn on_update(&mut self, _update: (), _ctx: &Self::Context) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    async fn handle_action(
+        &mut self,
+        _action: (),
+        _ctx: &Self::Context,
+    ) -> Result<(), Self::Error> {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        Ok(())
+    }
+
+    async fn on_stop_with_reason(
+        store: &HashMap<u32, Self>,
+        reason: StopReason,
+        stop_log: &StopLog,
+    ) -> Result<(), Self::Error> {
+        *stop_log.lock().unwrap() = Some((store.len(), reason));
+        Ok(())
+    }
+}
+
+// --- Tests ---
+
+#[tokio::test]
+async fn test_priority_request_jumps_backlog() {
+    let (actor, client) = ResourceActor::<Job>::new(64);
+    let priority = actor.priority_client();
+    tokio::spawn(actor.run(StopLog::default()));
+    let id = client.create(()).await.unwrap();
+
+    for _ in 0..20 {
+        client.perform_action_detached(id, ()).await.unwrap();
+    }
+    let stats = priority.stats().await.unwrap();
+    // Only the create and the action already running were handled first
+    assert!(
+        stats.messages < 5,
+        "handled {} messages first",
+        stats.messages
+    );
+}
+
+#[tokio::test]
+async fn test_priority_shutdown_skips_backlog_and_runs_on_stop() {
+    let (actor, client) = ResourceActor::<Job>::new(64);
+    let priority = actor.priority_client();
+    let stop_log = StopLog::default();
+    let handle = tokio::spawn(actor.run(stop_log.clone()));
+    let id = client.create(()).await.unwrap();
+
+    for _ in 0..20 {
+        client.perform_action_detached(id, ()).await.unwrap();
+    }
+    tokio::time::timeout(Duration::from_millis(200), priority.shutdown())
+        .await
+        .expect("shutdown waited for the backlog")
+        .unwrap();
+    assert_eq!(*stop_log.lock().unwrap(), Some((1, StopReason::Shutdown)));
+
+    handle.await.unwrap();
+    assert!(matches!(
+        client.get(id).await,
+        Err(FrameworkError::ActorClosed)
+    ));
+}
+
+#[tokio::test]
+async fn test_priority_client_does_not_keep_actor_alive() {
+    let (actor, client) = ResourceActor::<Job>::new(8);
+    let priority = actor.priority_client();
+    let stop_log = StopLog::default();
+    let handle = tokio::spawn(actor.run(stop_log.clone()));
+
+    let clone = client.clone();
+    drop(clone);
+    assert!(
+        !handle.is_finished(),
+        "dropping one clone must not stop the actor"
+    );
+    drop(client);
+    handle.await.unwrap();
+    assert_eq!(
+        *stop_log.lock().unwrap(),
+        Some((0, StopReason::AllClientsDropped))
+    );
+    assert!(matches!(
+        priority.stats().await,
+        Err(FrameworkError::ActorClosed)
+    ));
+}
+
+#[tokio::test]
+async fn test_pause_holds_requests_until_resume() {
+    let (actor, client) = ResourceActor::<Job>::new(8);
+    let priority = actor.priority_client();
+    tokio::spawn(actor.run(StopLog::default()));
+    priority.pause().await.unwrap();
+
+    let pending = tokio::spawn({
+        let client = client.clone();
+        async move { client.create(()).await }
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!pending.is_finished(), "a paused actor handled a create");
+    // Control requests are still served: the pause and this stats call
+    assert_eq!(priority.stats().await.unwrap().messages, 2);
+
+    priority.resume().await.unwrap();
+    pending.await.unwrap().unwrap();
+    assert_eq!(client.count().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_pause_applies_backpressure_once_the_channel_is_full() {
+    let (actor, client) = ResourceActor::<Job>::new(1);
+    let priority = actor.priority_client();
+    tokio::spawn(actor.run(StopLog::default()));
+    priority.pause().await.unwrap();
+
+    let first = tokio::spawn({
+        let client = client.clone();
+        async move { client.create(()).await }
+    });
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(
+        client.sender().capacity(),
+        0,
+        "the held create fills the channel"
+    );
+    // A further send waits for room instead of failing
+    let blocked = tokio::time::timeout(Duration::from_millis(50), client.count()).await;
+    assert!(blocked.is_err(), "a send to a full channel returned");
+
+    priority.resume().await.unwrap();
+    first.await.unwrap().unwrap();
+    assert_eq!(client.count().await.unwrap(), 1);
+}
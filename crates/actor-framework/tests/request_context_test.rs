@@ -0,0 +1,105 @@
+use actor_framework::{request_context, ActorEntity, ResourceActor};
+use async_trait::async_trait;
+
+// --- Test Entity ---
+
+#[derive(Clone, Debug, PartialEq)]
+struct Principal(String);
+
+/// Records who performed the latest change, as seen through the request context.
+#[derive(Clone, Debug)]
+struct Document {
+    id: u32,
+    last_editor: Option<Principal>,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Document error")]
+struct DocumentError;
+
+#[async_trait]
+impl ActorEntity for Document {
+    type Id = u32;
+    type Create = ();
+    type Update = ();
+    type Action = ();
+    type ActionResult = Option<Principal>;
+    type Context = ();
+    type Error = DocumentError;
+
+    fn from_create_params(id: u32, _params: ()) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id,
+            last_editor: request_context::current::<Principal>(),
+        })
+    }
+
+    fn id(&self) -> &u32 {
+        &self.id
+    }
+
+    async fn on_update(&mut self, _update: (), _ctx: &Self::Context) -> Result<bool, Self::Error> {
+        self.last_editor = request_context::current::<Principal>();
+        Ok(true)
+    }
+
+    async fn handle_action(
+        &mut self,
+        _action: (),
+        _ctx: &Self::Context,
+    ) -> Result<Option<Principal>, Self::Error> {
+        Ok(request_context::current::<Principal>())
+    }
+}
+
+// --- Tests ---
+
+#[tokio::test]
+async fn test_request_context_reaches_hooks() {
+    let (actor, client) = ResourceActor::<Document>::new(10);
+    tokio::spawn(actor.run(()));
+    let alice = Principal("alice".to_string());
+    let as_alice = client.with_request_context(alice.clone());
+
+    let id = as_alice.create(()).await.unwrap();
+    let document = client.get(id).await.unwrap().unwrap();
+    assert_eq!(document.last_editor, Some(alice.clone()));
+
+    // Requests without a context see none, even right after one that had it
+    let document = client.update(id, ()).await.unwrap();
+    assert_eq!(document.last_editor, None);
+    assert_eq!(client.perform_action(id, ()).await.unwrap(), None);
+
+    let document = as_alice.update(id, ()).await.unwrap();
+    assert_eq!(document.last_editor, Some(alice));
+}
+
+#[tokio::test]
+async fn test_request_context_of_another_type_is_not_visible() {
+    let (actor, client) = ResourceActor::<Document>::new(10);
+    tokio::spawn(actor.run(()));
+
+    let id = client
+        .with_request_context("alice")
+        .create(())
+        .await
+        .unwrap();
+    let document = client.get(id).await.unwrap().unwrap();
+    assert_eq!(document.last_editor, None);
+}
+
+#[tokio::test]
+async fn test_pipeline_carries_request_context() {
+    let (actor, client) = ResourceActor::<Document>::new(10);
+    tokio::spawn(actor.run(()));
+    let id = client.create(()).await.unwrap();
+    let bob = Principal("bob".to_string());
+
+    let mut pipeline = client.with_request_context(bob.clone()).pipeline();
+    let updated = pipeline.update(id, ());
+    let acted = pipeline.perform_action(id, ());
+    pipeline.send().await.unwrap();
+
+    assert_eq!(updated.await.unwrap().last_editor, Some(bob.clone()));
+    assert_eq!(acted.await.unwrap(), Some(bob));
+}
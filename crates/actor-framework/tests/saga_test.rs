@@ -0,0 +1,87 @@
+use actor_framework::Saga;
+use std::sync::{Arc, Mutex};
+
+/// Names of the steps and compensations in the order they ran.
+type Log = Arc<Mutex<Vec<&'static str>>>;
+
+async fn record(log: &Log, entry: &'static str) {
+    log.lock().unwrap().push(entry);
+}
+
+async fn succeed(log: &Log, entry: &'static str) -> Result<(), String> {
+    record(log, entry).await;
+    Ok(())
+}
+
+async fn fail(log: &Log, entry: &'static str) -> Result<(), String> {
+    record(log, entry).await;
+    Err(format!("{entry} failed"))
+}
+
+#[tokio::test]
+async fn test_saga_runs_every_step_without_compensating() {
+    let log = Log::default();
+    Saga::new()
+        .step(|()| succeed(&log, "reserve"), |()| record(&log, "release"))
+        .step(|()| succeed(&log, "create"), |()| record(&log, "cancel"))
+        .run()
+        .await
+        .unwrap();
+
+    assert_eq!(*log.lock().unwrap(), vec!["reserve", "create"]);
+}
+
+#[tokio::test]
+async fn test_saga_compensates_completed_steps_in_reverse() {
+    let log = Log::default();
+    let result = Saga::new()
+        .step(
+            |()| succeed(&log, "validate"),
+            |()| record(&log, "unvalidate"),
+        )
+        .step(|()| succeed(&log, "reserve"), |()| record(&log, "release"))
+        .step(|()| fail(&log, "create"), |()| record(&log, "cancel"))
+        .step(|()| succeed(&log, "notify"), |()| record(&log, "unnotify"))
+        .run()
+        .await;
+
+    assert_eq!(result, Err("create failed".to_string()));
+    // The failed step is not compensated, and later steps never run
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec!["validate", "reserve", "create", "release", "unvalidate"]
+    );
+}
+
+#[tokio::test]
+async fn test_saga_passes_outputs_to_later_steps_and_compensations() {
+    let undone = Arc::new(Mutex::new(Vec::new()));
+    let result = Saga::new()
+        .step(|()| async { Ok::<_, String>(7) }, {
+            let undone = undone.clone();
+            move |id: u32| async move { undone.lock().unwrap().push(id) }
+        })
+        .step(
+            |id| async move { Ok(format!("order {id}")) },
+            |_: String| async {},
+        )
+        .run()
+        .await;
+    assert_eq!(result, Ok("order 7".to_string()));
+    assert!(undone.lock().unwrap().is_empty());
+
+    let result = Saga::new()
+        .step(|()| async { Ok(7) }, {
+            let undone = undone.clone();
+            move |id: u32| async move { undone.lock().unwrap().push(id) }
+        })
+        .step(
+            |id| async move { Err::<(), _>(format!("cannot ship order {id}")) },
+            |()| async {},
+        )
+        .run()
+        .await;
+    assert_eq!(result, Err("cannot ship order 7".to_string()));
+    // The compensation of the first step received its output
+    assert_eq!(*undone.lock().unwrap(), vec![7]);
+}
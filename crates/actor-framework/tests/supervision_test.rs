@@ -0,0 +1,141 @@
+use actor_framework::{ActorEntity, FrameworkError, ResourceActor, StopReason};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+// --- Test Entity ---
+
+/// What the start and stop hooks saw.
+#[derive(Clone, Debug, Default)]
+struct Logs {
+    /// The store size at each start.
+    started: Arc<Mutex<Vec<usize>>>,
+    /// The store size and reason seen by the stop hook.
+    stopped: Arc<Mutex<Option<(usize, StopReason)>>>,
+}
+
+/// Its only action crashes the actor.
+#[derive(Clone, Debug)]
+struct Worker {
+    id: u32,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Worker error")]
+struct WorkerError;
+
+#[async_trait]
+impl ActorEntity for Worker {
+    type Id = u32;
+    type Create = ();
+    type Update = ();
+    type Action = ();
+    type ActionResult = ();
+    type Context = Logs;
+    type Error = WorkerError;
+
+    fn from_create_params(id: u32, _params: ()) -> Result<Self, Self::Error> {
+        Ok(Self { id })
+    }
+
+    fn id(&self) -> &u32 {
+        &self.id
+    }
+
+    async fn on_update(&mut self, _update: (), _ctx: &Self::Context) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    async fn handle_action(
+        &mut self,
+        _action: (),
+        _ctx: &Self::Context,
+    ) -> Result<(), Self::Error> {
+        panic!("worker crashed")
+    }
+
+    async fn on_start(store: &HashMap<u32, Self>, logs: &Logs) -> Result<(), Self::Error> {
+        logs.started.lock().unwrap().push(store.len());
+        Ok(())
+    }
+
+    async fn on_stop_with_reason(
+        store: &HashMap<u32, Self>,
+        reason: StopReason,
+        logs: &Logs,
+    ) -> Result<(), Self::Error> {
+        *logs.stopped.lock().unwrap() = Some((store.len(), reason));
+        Ok(())
+    }
+}
+
+// --- Tests ---
+
+#[tokio::test]
+async fn test_giving_up_runs_on_stop_with_crashed_reason() {
+    let (actor, client) = ResourceActor::<Worker>::new(8);
+    let logs = Logs::default();
+    let handle = tokio::spawn(actor.run_supervised(logs.clone(), 0));
+
+    let id = client.create(()).await.unwrap();
+    assert!(matches!(
+        client.perform_action(id, ()).await,
+        Err(FrameworkError::ActorDropped)
+    ));
+    handle.await.unwrap();
+    // The store is as the crashed run left it
+    assert_eq!(
+        *logs.stopped.lock().unwrap(),
+        Some((1, StopReason::Crashed))
+    );
+    assert!(matches!(
+        client.count().await,
+        Err(FrameworkError::ActorClosed)
+    ));
+}
+
+#[tokio::test]
+async fn test_restart_discards_draining_and_count_waiters() {
+    let (actor, client) = ResourceActor::<Worker>::new(8);
+    tokio::spawn(actor.run_supervised(Logs::default(), 1));
+
+    client.create(()).await.unwrap();
+    let waiter = tokio::spawn({
+        let client = client.clone();
+        async move { client.wait_for_count(5).await }
+    });
+    tokio::task::yield_now().await;
+    client.begin_draining().await.unwrap();
+    assert!(matches!(
+        client.create(()).await,
+        Err(FrameworkError::ShuttingDown)
+    ));
+
+    // Reads are still served while draining, so a crashing one restarts the actor
+    assert!(matches!(
+        client.find_where(|_| panic!("predicate crashed")).await,
+        Err(FrameworkError::ActorDropped)
+    ));
+    assert!(matches!(
+        waiter.await.unwrap(),
+        Err(FrameworkError::ActorDropped)
+    ));
+    assert_eq!(client.create(()).await.unwrap(), 2);
+    assert_eq!(client.count().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_restart_runs_on_start_on_the_empty_store() {
+    let (actor, client) = ResourceActor::<Worker>::new(8);
+    let logs = Logs::default();
+    tokio::spawn(actor.run_supervised(logs.clone(), 1));
+
+    client.create(()).await.unwrap();
+    let id = client.create(()).await.unwrap();
+    assert!(matches!(
+        client.perform_action(id, ()).await,
+        Err(FrameworkError::ActorDropped)
+    ));
+    client.ready().await.unwrap();
+    assert_eq!(*logs.started.lock().unwrap(), vec![0, 0]);
+}
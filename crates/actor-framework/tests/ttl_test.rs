@@ -0,0 +1,130 @@
+use actor_framework::{ActorEntity, ResourceActor, TestClock};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+// --- Test Entity ---
+
+#[derive(Clone, Debug)]
+struct Session {
+    id: u32,
+    ttl: Option<Duration>,
+}
+
+#[derive(Debug)]
+struct SessionCreate {
+    ttl: Option<Duration>,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Session error")]
+struct SessionError;
+
+#[async_trait]
+impl ActorEntity for Session {
+    type Id = u32;
+    type Create = SessionCreate;
+    type Update = ();
+    type Action = ();
+    type ActionResult = ();
+    /// Counts the `on_delete` calls.
+    type Context = Arc<AtomicUsize>;
+    type Error = SessionError;
+
+    fn from_create_params(id: u32, params: SessionCreate) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id,
+            ttl: params.ttl,
+        })
+    }
+
+    fn id(&self) -> &u32 {
+        &self.id
+    }
+
+    async fn on_update(&mut self, _: (), _ctx: &Self::Context) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    async fn on_delete(&self, deleted: &Self::Context) -> Result<(), Self::Error> {
+        deleted.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn handle_action(&mut self, _: (), _ctx: &Self::Context) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+}
+
+// --- Test ---
+
+#[tokio::test]
+async fn test_entities_expire_after_ttl() {
+    let deleted = Arc::new(AtomicUsize::new(0));
+    let (actor, client) = ResourceActor::<Session>::new(10);
+    tokio::spawn(
+        actor
+            .with_expiry_interval(Duration::from_millis(10))
+            .run(deleted.clone()),
+    );
+
+    let ephemeral = client
+        .create(SessionCreate {
+            ttl: Some(Duration::from_millis(50)),
+        })
+        .await
+        .unwrap();
+    let permanent = client.create(SessionCreate { ttl: None }).await.unwrap();
+    assert_eq!(client.count().await.unwrap(), 2);
+
+    client
+        .wait_until(
+            |count| count == 1,
+            Duration::from_millis(10),
+            Duration::from_secs(1),
+        )
+        .await
+        .unwrap();
+
+    assert!(client.get(ephemeral).await.unwrap().is_none());
+    let kept = client.get(permanent).await.unwrap().unwrap();
+    assert_eq!(kept.id, permanent);
+    assert_eq!(deleted.load(Ordering::SeqCst), 1);
+}
+
+/// With a `TestClock`, an entity expires once the clock is advanced past its TTL, and not
+/// before, however many expiry ticks pass.
+#[tokio::test]
+async fn test_ttl_is_measured_on_the_actor_clock() {
+    let clock = TestClock::new();
+    let deleted = Arc::new(AtomicUsize::new(0));
+    let (actor, client) = ResourceActor::<Session>::new(10);
+    tokio::spawn(
+        actor
+            .with_clock(clock.clone())
+            .with_expiry_interval(Duration::from_millis(1))
+            .run(deleted.clone()),
+    );
+
+    let id = client
+        .create(SessionCreate {
+            ttl: Some(Duration::from_secs(15 * 60)),
+        })
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert!(client.get(id).await.unwrap().is_some());
+
+    // Fifteen minutes pass instantly
+    clock.advance(Duration::from_secs(15 * 60));
+    tokio::time::timeout(Duration::from_secs(1), client.wait_for_count(0))
+        .await
+        .expect("the session did not expire")
+        .unwrap();
+    assert_eq!(deleted.load(Ordering::SeqCst), 1);
+}
@@ -21,7 +21,7 @@
 //! use actor_sample::model::{User, UserCreate, UserId};
 //! use actor_sample::user_actor::UserError;
 //!
-//! #[derive(Clone)]
+//! #[derive(Clone, Debug)]
 //! pub struct UserClient {
 //!     inner: ResourceClient<User>,
 //! }
@@ -63,6 +63,21 @@
 //! - `async fn get(&self, id: String) -> Result<Option<User>, UserError>`
 //! - `async fn delete(&self, id: String) -> Result<(), UserError>`
 //!
+//! The wrappers in this module don't write the struct, `new` and the `ActorClient` impl by
+//! hand: [`define_client!`](actor_framework::define_client) generates them, and only the
+//! domain-specific methods are written out:
+//!
+//! ```rust,ignore
+//! define_client! {
+//!     /// Client for interacting with the User actor.
+//!     pub struct UserClient {
+//!         entity: User,
+//!         error: UserError,
+//!         fallback: UserError::ActorCommunicationError,
+//!     }
+//! }
+//! ```
+//!
 //! ## Type-Safe Error Mapping
 //!
 //! Each client maps framework errors to domain-specific error types:
@@ -94,36 +109,49 @@
 //!
 //! ```rust,ignore
 //! impl OrderClient {
-//!     pub async fn create_order(&self, params: OrderCreate) -> Result<String, OrderError> {
-//!         // 1. Validate user exists
-//!         let user = self.user_client.get(params.user_id.clone()).await?
-//!             .ok_or_else(|| OrderError::InvalidUser(params.user_id.clone()))?;
-//!
-//!         // 2. Reserve product stock
-//!         self.product_client.reserve_stock(
-//!             params.product_id.clone(),
-//!             params.quantity
-//!         ).await?;
-//!
-//!         // 3. Create the order
-//!         match self.inner.create(params.clone()).await {
-//!             Ok(id) => Ok(id),
-//!             Err(e) => {
-//!                 // COMPENSATING TRANSACTION: Rollback stock reservation
-//!                 // If we fail to create the order, we must release the stock
-//!                 // so it doesn't get "leaked" (permanently reserved).
-//!                 let _ = self.product_client.release_stock(
-//!                     params.product_id,
-//!                     params.quantity
-//!                 ).await;
-//!                 
-//!                 Err(OrderError::ActorCommunicationError(e.to_string()))
-//!             }
-//!         }
+//!     pub async fn create_order(&self, params: OrderCreate) -> Result<OrderId, OrderError> {
+//!         Saga::new()
+//!             // 1. Create the order; `Order::on_create` validates the user. The
+//!             //    compensation receives the new order's id.
+//!             .step(
+//!                 |()| async { self.inner.create(params).await.map_err(Self::map_error) },
+//!                 |order_id| async move {
+//!                     let _ = self.inner.delete(order_id).await;
+//!                 },
+//!             )
+//!             // 2. Reserve the stock; if a later step fails, it is released again
+//!             .step(
+//!                 |order_id| async move {
+//!                     let unit_prices = self.reserve_stock(order_id.clone(), lines).await?;
+//!                     Ok((order_id, unit_prices))
+//!                 },
+//!                 |(order_id, _)| async move {
+//!                     // COMPENSATING TRANSACTION: release the stock, so it is not "leaked"
+//!                     // (permanently reserved) by a rolled back order
+//!                     self.release_stock(order_id, products).await;
+//!                 },
+//!             )
+//!             // 3. Price the order at the prices the stock was reserved at
+//!             .step(
+//!                 |(order_id, unit_prices)| async move {
+//!                     let action = OrderAction::SetUnitPrices(unit_prices);
+//!                     self.inner
+//!                         .perform_action(order_id.clone(), action)
+//!                         .await
+//!                         .map_err(Self::map_error)?;
+//!                     Ok(order_id)
+//!                 },
+//!                 |_| async {},
+//!             )
+//!             .run()
+//!             .await
 //!     }
 //! }
 //! ```
 //!
+//! [`Saga`](actor_framework::Saga) runs the compensations of completed steps in reverse
+//! whenever a later step fails, so the rollback is not hand-written per workflow.
+//!
 //! This keeps orchestration logic in the **client layer**, while actors remain
 //! focused on managing their own state.
 //!
@@ -2,52 +2,253 @@
 //!
 //! Provides a high‑level API for interacting with the `Order` actor.
 //! It wraps a `ResourceClient<Order>` and handles orchestration logic.
-use crate::model::{Order, OrderId};
-use crate::order_actor::OrderError;
-use actor_framework::ActorClient;
-use actor_framework::{FrameworkError, ResourceClient};
-use async_trait::async_trait;
-use tracing::{debug, info, instrument};
-
-/// Client for interacting with the Order actor.
-///
-/// Orchestration logic (user validation, stock reservation) now happens
-/// in the Order actor's `on_create` hook.
-#[derive(Clone)]
-pub struct OrderClient {
-    inner: ResourceClient<Order>,
+use crate::clients::{ProductClient, UserClient};
+use crate::model::{ActingUser, Money, Order, OrderId, OrderSummary, ProductId, UserId};
+use crate::order_actor::{OrderAction, OrderError, StockReserver};
+use crate::product_actor::ProductError;
+use actor_framework::{define_client, ActorClient, FrameworkError, Saga};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, info_span, instrument, warn, Instrument};
+
+define_client! {
+    /// Client for interacting with the Order actor.
+    ///
+    /// User validation happens in the Order actor's `on_create` hook; the `OrderError` it
+    /// raises (e.g. `InvalidUser`) comes back as-is, and anything else becomes
+    /// `ActorCommunicationError`. Stock is reserved here, through `stock`, by
+    /// [`OrderClient::create_order`].
+    ///
+    /// The User and Product clients serve read-side joins such as
+    /// [`OrderClient::order_summary`]; the Product client also releases the stock of an
+    /// order rolled back by `create_order`.
+    ///
+    /// Each `create_order` holds `placing` for reading until its saga is done, so
+    /// [`OrderClient::begin_draining`] can wait for the orders being placed.
+    pub struct OrderClient {
+        entity: Order,
+        error: OrderError,
+        fallback: OrderError::ActorCommunicationError,
+        fields: {
+            users: UserClient,
+            products: ProductClient,
+            stock: Arc<dyn StockReserver>,
+        },
+        state: { placing: Arc<RwLock<()>> },
+    }
 }
 
 impl OrderClient {
-    pub fn new(inner: ResourceClient<Order>) -> Self {
-        Self { inner }
+    /// Returns a client whose requests are made on behalf of `user`, carried to the Order
+    /// actor as an [`ActingUser`] request context: its [`create_order`](Self::create_order)
+    /// fails with `Forbidden` for an order of another user.
+    pub fn acting_as(&self, user: UserId) -> Self {
+        Self {
+            placing: self.placing.clone(),
+            ..Self::new(
+                self.inner.with_request_context(ActingUser(user)),
+                self.users.clone(),
+                self.products.clone(),
+                self.stock.clone(),
+            )
+        }
+    }
+
+    /// Stops the Order actor from accepting writes, once the `create_order` calls already
+    /// started (by this client or a clone) are done: their later steps write to the order, so
+    /// draining earlier would fail them halfway. `create_order` calls made meanwhile wait, then
+    /// fail with `ShuttingDown`.
+    pub async fn begin_draining(&self) -> Result<(), FrameworkError> {
+        let _placing = self.placing.write().await;
+        self.inner.begin_draining().await
     }
 
+    /// Creates an order and returns its id.
+    ///
+    /// If the Order actor detects a duplicate (see
+    /// [`RecentOrders`](crate::order_actor::RecentOrders)), the id of the existing order is
+    /// returned instead and no stock is reserved.
+    ///
+    /// Runs as a [`Saga`]: once `Order::on_create` validated and stored the order, the stock of
+    /// every line is reserved at once, and the order is priced at the Products' prices. If the
+    /// reservation fails, e.g. with `OutOfStock` naming the short product, the order is deleted
+    /// again; if the pricing fails, the stock is released too. If the Product actor has
+    /// stopped, fails with `DependencyUnavailable` naming it.
     #[instrument(skip(self))]
     pub async fn create_order(
         &self,
         params: crate::model::OrderCreate,
     ) -> Result<OrderId, OrderError> {
         debug!("create_order called");
+        let _placing = self.placing.read().await;
         info!("Sending create_order to actor");
 
-        // Create order - validation happens in Order::on_create
-        self.inner
-            .create(params)
+        let lines: Vec<_> = params
+            .items
+            .iter()
+            .map(|line| (line.product_id.clone(), line.quantity))
+            .collect();
+        let products = lines.iter().map(|(id, _)| id.clone()).collect();
+        Saga::new()
+            // 1. Create the order - validation happens in Order::on_create. Yields whether
+            //    this call created it: a resubmitted order resolves to the order it
+            //    duplicates, whose stock is already reserved and which must not be undone.
+            .step(
+                |()| async {
+                    match self.inner.create(params).await.map_err(Self::map_error) {
+                        Ok(order_id) => Ok((order_id, true)),
+                        Err(OrderError::Duplicate(existing)) => {
+                            debug!(%existing, "Duplicate order");
+                            Ok((existing, false))
+                        }
+                        Err(OrderError::DependencyUnavailable { service }) => {
+                            error!(service, "Order dependency is down");
+                            Err(OrderError::DependencyUnavailable { service })
+                        }
+                        Err(e) => Err(e),
+                    }
+                },
+                |(order_id, created)| async move {
+                    if created {
+                        warn!(%order_id, "Rolling back order");
+                        if let Err(e) = self.inner.delete(order_id.clone()).await {
+                            warn!(%order_id, error = %e, "Could not delete the rolled back order");
+                        }
+                    }
+                },
+            )
+            // 2. Reserve every line's stock at once, yielding the unit prices it was reserved
+            //    at; a duplicate reserves nothing
+            .step(
+                |(order_id, created)| async move {
+                    if !created {
+                        return Ok((order_id, None));
+                    }
+                    let unit_prices = self.reserve_stock(order_id.clone(), lines).await?;
+                    Ok((order_id, Some(unit_prices)))
+                },
+                |(order_id, unit_prices)| async move {
+                    if unit_prices.is_some() {
+                        self.release_stock(order_id, products).await;
+                    }
+                },
+            )
+            // 3. Charge the Product's prices, not the caller's
+            .step(
+                |(order_id, unit_prices)| async move {
+                    if let Some(unit_prices) = unit_prices {
+                        self.inner
+                            .perform_action(
+                                order_id.clone(),
+                                OrderAction::SetUnitPrices(unit_prices),
+                            )
+                            .await
+                            .map_err(Self::map_error)?;
+                    }
+                    Ok(order_id)
+                },
+                |_| async {},
+            )
+            .run()
             .await
-            .map_err(|e| OrderError::ActorCommunicationError(e.to_string()))
     }
-}
 
-#[async_trait]
-impl ActorClient<Order> for OrderClient {
-    type Error = OrderError;
+    /// Reserves the stock of `lines` for order `order_id`, all or nothing, and returns each
+    /// line's unit price. A stock shortfall becomes `OutOfStock`.
+    ///
+    /// Runs inside an `order_reserve_stock` span carrying the order id, so the spans and
+    /// events of the Product calls it makes show which order triggered them.
+    async fn reserve_stock(
+        &self,
+        order_id: OrderId,
+        lines: Vec<(ProductId, u32)>,
+    ) -> Result<Vec<Money>, OrderError> {
+        let span = info_span!("order_reserve_stock", order_id = %order_id);
+        self.stock
+            .reserve(order_id, lines)
+            .instrument(span)
+            .await
+            .map_err(|e| match e {
+                ProductError::InsufficientStock {
+                    product_id,
+                    requested,
+                    available,
+                } => OrderError::OutOfStock {
+                    product_id,
+                    requested,
+                    available,
+                },
+                _ if !self.stock.is_available() => {
+                    error!(service = "product", "Order dependency is down");
+                    OrderError::DependencyUnavailable { service: "product" }
+                }
+                e => e.into(),
+            })
+    }
+
+    /// Releases the stock order `order_id` reserved on `products`; the compensation of the
+    /// reservation step of `create_order`.
+    async fn release_stock(&self, order_id: OrderId, products: Vec<ProductId>) {
+        warn!(%order_id, "Releasing the rolled back order's stock");
+        for product_id in products {
+            if let Err(e) = self
+                .products
+                .release_stock(product_id, order_id.clone())
+                .await
+            {
+                warn!(%order_id, error = %e, "Could not release the rolled back order's stock");
+            }
+        }
+    }
 
-    fn inner(&self) -> &ResourceClient<Order> {
-        &self.inner
+    /// Places a new order for the user and lines of `order`, e.g. to repeat a past one; its
+    /// id, total and status are ignored, so the total is recomputed from the lines.
+    pub async fn create_order_from(&self, order: Order) -> Result<OrderId, OrderError> {
+        self.create_order(order.into()).await
     }
 
-    fn map_error(e: FrameworkError) -> Self::Error {
-        OrderError::ActorCommunicationError(e.to_string())
+    /// Fetches an order together with its user's name and the name of each line's product.
+    ///
+    /// The user and the products are fetched concurrently once the order is found, the
+    /// products in one round-trip. Fails with `NotFound` for an unknown order and
+    /// `DanglingReference` if its user or one of its products no longer exists.
+    #[instrument(skip(self))]
+    pub async fn order_summary(&self, order_id: OrderId) -> Result<OrderSummary, OrderError> {
+        let order = self
+            .get(order_id.clone())
+            .await?
+            .ok_or_else(|| OrderError::NotFound(order_id.to_string()))?;
+
+        let product_ids = order
+            .items
+            .iter()
+            .map(|line| line.product_id.clone())
+            .collect();
+        let (user, products) = tokio::join!(
+            self.users.get(order.user_id.clone()),
+            self.products.inner().get_map(product_ids)
+        );
+        let dangling = |reference: String| OrderError::DanglingReference {
+            order_id: order_id.clone(),
+            reference,
+        };
+        let user = user?.ok_or_else(|| dangling(order.user_id.to_string()))?;
+        let products = products.map_err(ProductClient::map_error)?;
+        let product_names = order
+            .items
+            .iter()
+            .map(|line| {
+                products
+                    .get(&line.product_id)
+                    .map(|product| product.name.clone())
+                    .ok_or_else(|| dangling(line.product_id.to_string()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(OrderSummary {
+            user_name: user.name,
+            product_names,
+            order,
+        })
     }
 }
@@ -2,95 +2,214 @@
 //!
 //! Provides a high‑level API for interacting with the `Product` actor.
 //! It wraps a `ResourceClient<Product>` and exposes domain‑specific methods.
-use crate::model::{Product, ProductId};
-use crate::product_actor::ProductError;
-use actor_framework::ActorClient;
-use actor_framework::{FrameworkError, ResourceClient};
-use async_trait::async_trait;
+use crate::model::{Money, OrderId, Product, ProductId, ProductSummary, ProductUpdate};
+use crate::product_actor::{
+    CheckStock, ListReservations, ProductAction, ProductActionResult, ProductError, ReleaseStock,
+    ReserveStock, ReserveUpTo, SetPrice,
+};
+use actor_framework::{define_client, ActorClient};
 use tracing::{debug, instrument};
 
-/// Client for interacting with the Product actor.
-#[derive(Clone)]
-pub struct ProductClient {
-    inner: ResourceClient<Product>,
+define_client! {
+    /// Client for interacting with the Product actor.
+    ///
+    /// Errors raised by `Product` hooks come back as `ProductError` (e.g. `InsufficientStock`);
+    /// anything else becomes `ActorCommunicationError`.
+    pub struct ProductClient {
+        entity: Product,
+        error: ProductError,
+        fallback: ProductError::ActorCommunicationError,
+    }
 }
 
 impl ProductClient {
-    pub fn new(inner: ResourceClient<Product>) -> Self {
-        Self { inner }
+    /// Check the current stock level for a product.
+    ///
+    /// Returns the quantity available.
+    #[instrument(skip(self))]
+    #[allow(dead_code)]
+    pub async fn check_stock(&self, id: ProductId) -> Result<u32, ProductError> {
+        self.inner
+            .typed_action(id, CheckStock)
+            .await
+            .map_err(Self::map_error)
     }
-}
 
-#[async_trait]
-impl ActorClient<Product> for ProductClient {
-    type Error = ProductError;
+    /// Reserve a specific amount of stock of a product for an order.
+    ///
+    /// Returns the product's unit price at the time of the reservation, or an error if there
+    /// is not enough stock.
+    #[instrument(skip(self))]
+    pub async fn reserve_stock(
+        &self,
+        id: ProductId,
+        order_id: OrderId,
+        quantity: u32,
+    ) -> Result<Money, ProductError> {
+        self.inner
+            .typed_action(id, ReserveStock(order_id, quantity))
+            .await
+            .map_err(Self::map_error)
+    }
 
-    fn inner(&self) -> &ResourceClient<Product> {
-        &self.inner
+    /// Reserves as much of `quantity` as is in stock for an order, e.g. to fulfil it partly
+    /// and backorder the rest.
+    ///
+    /// Returns the quantity reserved, `0` if the product is out of stock; unlike
+    /// [`reserve_stock`](Self::reserve_stock) a shortage is not an error. Release the
+    /// reservation with [`release_stock`](Self::release_stock) as usual.
+    #[instrument(skip(self))]
+    pub async fn reserve_up_to(
+        &self,
+        id: ProductId,
+        order_id: OrderId,
+        quantity: u32,
+    ) -> Result<u32, ProductError> {
+        self.inner
+            .typed_action(id, ReserveUpTo(order_id, quantity))
+            .await
+            .map_err(Self::map_error)
     }
 
-    fn map_error(e: FrameworkError) -> Self::Error {
-        ProductError::ActorCommunicationError(e.to_string())
+    /// Returns the stock an order reserved on a product, e.g. when the order is cancelled.
+    ///
+    /// Returns the quantity released. Fails with `UnknownReservation` if the order holds no
+    /// reservation on this product.
+    #[instrument(skip(self))]
+    pub async fn release_stock(
+        &self,
+        id: ProductId,
+        order_id: OrderId,
+    ) -> Result<u32, ProductError> {
+        self.inner
+            .typed_action(id, ReleaseStock(order_id))
+            .await
+            .map_err(Self::map_error)
     }
-}
 
-impl ProductClient {
-    // Custom create method as it needs specific payload conversion
+    /// Lists the stock reserved on a product, as `(order, quantity)` pairs.
+    #[instrument(skip(self))]
+    pub async fn list_reservations(
+        &self,
+        id: ProductId,
+    ) -> Result<Vec<(OrderId, u32)>, ProductError> {
+        self.inner
+            .typed_action(id, ListReservations)
+            .await
+            .map_err(Self::map_error)
+    }
+
+    /// Changes a product's price through the audited `SetPrice` action.
+    ///
+    /// Returns `(old_price, new_price)`. Fails with `InvalidPrice` unless `new_price` is positive.
+    #[instrument(skip(self))]
+    pub async fn adjust_price(
+        &self,
+        id: ProductId,
+        new_price: Money,
+    ) -> Result<(Money, Money), ProductError> {
+        self.inner
+            .typed_action(id, SetPrice(new_price))
+            .await
+            .map_err(Self::map_error)
+    }
 
     #[instrument(skip(self))]
     pub async fn create_product(
         &self,
         params: crate::model::ProductCreate,
     ) -> Result<ProductId, ProductError> {
+        debug!("Sending request");
+        self.inner.create(params).await.map_err(Self::map_error)
+    }
+
+    /// Creates a new product with the name, price and stock of `product`; its id and
+    /// reservations are ignored.
+    #[instrument(skip(self))]
+    pub async fn create_product_from(&self, product: Product) -> Result<ProductId, ProductError> {
         debug!("Sending request");
         self.inner
-            .create(params)
+            .create_from(product)
             .await
-            .map_err(|e| ProductError::ActorCommunicationError(e.to_string()))
+            .map_err(Self::map_error)
     }
 
-    /// Check the current stock level for a product.
+    /// Fetches a product's price and stock without cloning its name and reservations.
+    #[instrument(skip(self))]
+    pub async fn get_summary(&self, id: ProductId) -> Result<Option<ProductSummary>, ProductError> {
+        debug!("Sending request");
+        self.inner.get_projection(id).await.map_err(Self::map_error)
+    }
+
+    /// Reserves stock of several products for one order, e.g. a cart checkout, atomically.
     ///
-    /// Returns the quantity available.
+    /// Every line is checked and reserved in a single Product actor turn (see
+    /// [`ResourceClient::perform_action_atomic`](actor_framework::ResourceClient::perform_action_atomic)):
+    /// if any product is missing or short, e.g. with `InsufficientStock`, nothing is reserved.
+    /// A product may appear on several lines. Returns each line's unit price at the time of
+    /// the reservation.
     #[instrument(skip(self))]
-    #[allow(dead_code)]
-    pub async fn check_stock(&self, id: ProductId) -> Result<u32, ProductError> {
-        debug!("Checking stock for product {}", id);
-        use crate::product_actor::{ProductAction, ProductActionResult};
-        match self
+    pub async fn reserve_batch(
+        &self,
+        order_id: OrderId,
+        lines: Vec<(ProductId, u32)>,
+    ) -> Result<Vec<Money>, ProductError> {
+        debug!("Reserving {} lines for order {}", lines.len(), order_id);
+        let items = lines
+            .into_iter()
+            .map(|(id, quantity)| (id, ProductAction::ReserveStock(order_id.clone(), quantity)))
+            .collect();
+        let results = self
             .inner
-            .perform_action(id, ProductAction::CheckStock)
+            .perform_action_atomic(items)
             .await
-        {
-            Ok(ProductActionResult::CheckStock(level)) => Ok(level),
-            Ok(_) => unreachable!("CheckStock action must return CheckStock result"),
-            Err(e) => Err(ProductError::ActorCommunicationError(e.to_string())),
-        }
+            .map_err(Self::map_error)?;
+        Ok(results
+            .into_iter()
+            .map(|result| match result {
+                ProductActionResult::ReserveStock(unit_price) => unit_price,
+                other => unreachable!("ReserveStock answered with {other:?}"),
+            })
+            .collect())
     }
 
-    /// Reserve a specific amount of stock for a product.
+    /// Returns the products matching `predicate`, e.g. `find(|p| p.price < Money::from_dollars(10.0))`.
     ///
-    /// Returns `Ok(())` if successful, or an error if insufficient stock.
-    #[instrument(skip(self))]
-    pub async fn reserve_stock(&self, id: ProductId, quantity: u32) -> Result<(), ProductError> {
-        debug!("Reserving {} units for product {}", quantity, id);
-        use crate::product_actor::{ProductAction, ProductActionResult};
-        match self
-            .inner
-            .perform_action(id, ProductAction::ReserveStock(quantity))
+    /// The filter runs inside the Product actor, so only matching products are cloned; it
+    /// must be cheap and must not block.
+    #[instrument(skip(self, predicate))]
+    pub async fn find(
+        &self,
+        predicate: impl Fn(&Product) -> bool + Send + 'static,
+    ) -> Result<Vec<Product>, ProductError> {
+        debug!("Finding products");
+        self.inner
+            .find_where(predicate)
             .await
-        {
-            Ok(ProductActionResult::ReserveStock(())) => Ok(()),
-            Ok(_) => unreachable!("ReserveStock action must return ReserveStock result"),
-            Err(e) => Err(ProductError::ActorCommunicationError(e.to_string())),
-        }
+            .map_err(Self::map_error)
+    }
+
+    /// Adjusts a product's stock by a relative `delta` (positive to restock, negative to
+    /// write off) and returns the updated product.
+    ///
+    /// Unlike setting `ProductUpdate::quantity`, this composes safely with concurrent
+    /// reservations. Fails with `InsufficientStock` if the stock would drop below zero.
+    #[instrument(skip(self))]
+    pub async fn adjust_stock(&self, id: ProductId, delta: i64) -> Result<Product, ProductError> {
+        debug!("Adjusting stock for product {} by {}", id, delta);
+        let update = ProductUpdate {
+            price: None,
+            quantity: None,
+            quantity_delta: Some(delta),
+        };
+        self.inner.update(id, update).await.map_err(Self::map_error)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::product_actor::{ProductAction, ProductActionResult};
+    use crate::product_actor::ProductActionResult;
     use actor_framework::mock::{create_mock_client, expect_action};
 
     #[tokio::test]
@@ -126,8 +245,11 @@ mod tests {
         let product_client = ProductClient::new(client);
 
         // Spawn task to call reserve_stock
-        let reserve_task =
-            tokio::spawn(async move { product_client.reserve_stock(ProductId(1), 5).await });
+        let reserve_task = tokio::spawn(async move {
+            product_client
+                .reserve_stock(ProductId(1), OrderId(9), 5)
+                .await
+        });
 
         // Expect the action request
         let (id, action, responder) = expect_action(&mut receiver)
@@ -136,18 +258,23 @@ mod tests {
 
         assert_eq!(id, ProductId(1));
         match action {
-            ProductAction::ReserveStock(amount) => assert_eq!(amount, 5),
+            ProductAction::ReserveStock(order_id, amount) => {
+                assert_eq!(order_id, OrderId(9));
+                assert_eq!(amount, 5);
+            }
             _ => panic!("Expected ReserveStock action"),
         }
 
         // Respond with success
         responder
-            .send(Ok(ProductActionResult::ReserveStock(())))
+            .send(Ok(ProductActionResult::ReserveStock(Money::from_dollars(
+                10.0,
+            ))))
             .unwrap();
 
         // Verify the result
         let result = reserve_task.await.unwrap();
-        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), Money::from_dollars(10.0));
     }
 
     #[tokio::test]
@@ -156,8 +283,11 @@ mod tests {
         let product_client = ProductClient::new(client);
 
         // Spawn task to call reserve_stock
-        let reserve_task =
-            tokio::spawn(async move { product_client.reserve_stock(ProductId(1), 100).await });
+        let reserve_task = tokio::spawn(async move {
+            product_client
+                .reserve_stock(ProductId(1), OrderId(9), 100)
+                .await
+        });
 
         // Expect the action request
         let (id, action, responder) = expect_action(&mut receiver)
@@ -166,16 +296,17 @@ mod tests {
 
         assert_eq!(id, ProductId(1));
         match action {
-            ProductAction::ReserveStock(amount) => assert_eq!(amount, 100),
+            ProductAction::ReserveStock(_, amount) => assert_eq!(amount, 100),
             _ => panic!("Expected ReserveStock action"),
         }
 
         // Respond with error
         use actor_framework::FrameworkError;
         responder
-            .send(Err(FrameworkError::EntityError(Box::new(
+            .send(Err(FrameworkError::entity(
+                "Product",
                 std::io::Error::other("Stock check failed"),
-            ))))
+            )))
             .unwrap();
 
         // Verify the result is an error
@@ -184,7 +315,7 @@ mod tests {
         match result {
             Err(ProductError::ActorCommunicationError(msg)) => {
                 // Error message comes from the EntityError wrapper
-                assert!(msg.contains("Stock check failed") || msg.contains("Entity error"));
+                assert!(msg.contains("Stock check failed") || msg.contains("Product entity error"));
             }
             _ => panic!("Expected ActorCommunicationError"),
         }
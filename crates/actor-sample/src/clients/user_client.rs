@@ -3,47 +3,49 @@
 //! Provides a high‑level API for interacting with the `User` actor.
 //! It wraps a `ResourceClient<User>` and exposes domain‑specific methods.
 use crate::model::{User, UserCreate, UserId, UserUpdate};
-use crate::user_actor::UserError;
-use actor_framework::ActorClient;
-use actor_framework::{FrameworkError, ResourceClient};
-use async_trait::async_trait;
+use crate::user_actor::{EmailIndex, UserError};
+use actor_framework::{define_client, ActorClient};
 use tracing::{debug, instrument};
 
-/// Client for interacting with the User actor.
-#[derive(Clone)]
-pub struct UserClient {
-    inner: ResourceClient<User>,
-}
-
-impl UserClient {
-    pub fn new(inner: ResourceClient<User>) -> Self {
-        Self { inner }
+define_client! {
+    /// Client for interacting with the User actor.
+    ///
+    /// Errors raised by `User` hooks come back as `UserError` (e.g. `EmailTaken`); anything
+    /// else becomes `ActorCommunicationError`. `emails` is the index the User actor maintains,
+    /// for [`UserClient::find_by_email`].
+    pub struct UserClient {
+        entity: User,
+        error: UserError,
+        fallback: UserError::ActorCommunicationError,
+        fields: { emails: EmailIndex },
     }
 }
 
-#[async_trait]
-impl ActorClient<User> for UserClient {
-    type Error = UserError;
-
-    fn inner(&self) -> &ResourceClient<User> {
-        &self.inner
+impl UserClient {
+    #[instrument(skip(self))]
+    pub async fn create_user(&self, params: UserCreate) -> Result<UserId, UserError> {
+        debug!("Sending request");
+        self.inner.create(params).await.map_err(Self::map_error)
     }
 
-    fn map_error(e: FrameworkError) -> Self::Error {
-        UserError::ActorCommunicationError(e.to_string())
+    /// Creates a new user with the name and email of `user`; its id is ignored.
+    ///
+    /// Emails are unique, so copying a user that is still stored fails with `EmailTaken`;
+    /// this recreates a user that is gone, e.g. from a backup or a fixture.
+    #[instrument(skip(self))]
+    pub async fn create_user_from(&self, user: User) -> Result<UserId, UserError> {
+        debug!("Sending request");
+        self.inner.create_from(user).await.map_err(Self::map_error)
     }
-}
-
-impl UserClient {
-    // Custom create method as it needs specific payload conversion
 
+    /// Creates a user and returns the full stored `User` in a single round-trip.
     #[instrument(skip(self))]
-    pub async fn create_user(&self, params: UserCreate) -> Result<UserId, UserError> {
+    pub async fn create_user_returning(&self, params: UserCreate) -> Result<User, UserError> {
         debug!("Sending request");
         self.inner
-            .create(params)
+            .create_returning(params)
             .await
-            .map_err(|e| UserError::ActorCommunicationError(e.to_string()))
+            .map_err(Self::map_error)
     }
 
     // New method utilizing the generic update
@@ -51,9 +53,17 @@ impl UserClient {
     #[allow(dead_code)]
     pub async fn update_user(&self, id: UserId, update: UserUpdate) -> Result<User, UserError> {
         debug!("Sending request");
-        self.inner
-            .update(id, update)
-            .await
-            .map_err(|e| UserError::ActorCommunicationError(e.to_string()))
+        self.inner.update(id, update).await.map_err(Self::map_error)
+    }
+
+    /// Looks up the user with `email` (compared case-insensitively) through the User actor's
+    /// [`EmailIndex`], without scanning every user.
+    #[instrument(skip(self))]
+    pub async fn find_by_email(&self, email: &str) -> Result<Option<User>, UserError> {
+        debug!("Sending request");
+        match self.emails.get(email) {
+            Some(id) => self.get(id).await,
+            None => Ok(None),
+        }
     }
 }
@@ -0,0 +1,255 @@
+//! # HTTP Gateway
+//!
+//! A REST front end for the actors, behind the `http` feature. Every resource gets the same
+//! routes, each translated to a call on its [`Resource`]:
+//!
+//! | Route | Call | Success |
+//! |-------|------|---------|
+//! | `GET /{resource}` | `list` | `200` with every entity |
+//! | `GET /{resource}/{id}` | `get` | `200` with the entity, `404` if there is none |
+//! | `POST /{resource}` | [`Resource::create`] | `201` with the stored entity |
+//! | `DELETE /{resource}/{id}` | `delete` | `204` |
+//!
+//! where `{resource}` is `users`, `products` or `orders`, and `{id}` is the number of the id
+//! (`1` for `user_1`). Bodies are JSON: a `POST` takes the create payload (`UserCreate`,
+//! `ProductCreate` or `OrderCreate`).
+//!
+//! Creates go through the domain clients, so `POST /orders` runs
+//! [`OrderClient::create_order`] (duplicate detection, rollback) like any other caller; reads
+//! and deletes carry no domain logic and go straight to the [`ResourceClient`].
+//!
+//! A failed call answers with a [`WireError`] and the status of its [`ErrorCode`]: e.g. `404`
+//! for `NotFound`, `503` for `Full` or a stopped actor, `504` for `Timeout`, and `422` for an
+//! entity error such as an order that is out of stock. A domain client's error keeps less
+//! detail: its `NotFound` is `404`, a failure to reach an actor `503`, and the rest `422`.
+//!
+//! ```rust,ignore
+//! let system = OrderSystem::new();
+//! let listener = tokio::net::TcpListener::bind("127.0.0.1:3000").await?;
+//! axum::serve(listener, gateway::router(&system)).await?;
+//! ```
+
+use crate::clients::{OrderClient, ProductClient, UserClient};
+use crate::lifecycle::OrderSystem;
+use crate::model::{Order, OrderCreate, Product, ProductCreate, User, UserCreate};
+use crate::order_actor::OrderError;
+use crate::product_actor::ProductError;
+use crate::user_actor::UserError;
+use actor_framework::{
+    ActorClient, ActorEntity, ErrorCode, FrameworkError, ResourceClient, WireError,
+};
+use async_trait::async_trait;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Display;
+
+/// Routes for the User, Product and Order actors of `system`, under `/users`, `/products`
+/// and `/orders`.
+///
+/// The router holds clients of the actors, so it must be dropped (the server stopped) before
+/// [`OrderSystem::shutdown`], which waits for the actors to stop.
+pub fn router(system: &OrderSystem) -> Router {
+    Router::new()
+        .nest("/users", routes(system.user_client.clone()))
+        .nest("/products", routes(system.product_client.clone()))
+        .nest("/orders", routes(system.order_client.clone()))
+}
+
+/// The routes of one resource, relative to where they are nested.
+pub fn routes<C>(client: C) -> Router
+where
+    C: Resource,
+    <C::Entity as ActorEntity>::Create: DeserializeOwned,
+{
+    Router::new()
+        .route("/", get(list::<C>).post(create::<C>))
+        .route("/{id}", get(fetch::<C>).delete(delete::<C>))
+        .with_state(client)
+}
+
+/// A client the gateway serves one resource through.
+///
+/// Implemented by the domain clients, whose `create` runs their domain logic, and by a bare
+/// [`ResourceClient`], whose `create` is just `create_returning`.
+#[async_trait]
+pub trait Resource: Clone + Send + Sync + 'static {
+    type Entity: ActorEntity + Serialize;
+
+    /// The generic client that serves reads and deletes.
+    fn resource_client(&self) -> &ResourceClient<Self::Entity>;
+
+    /// Creates an entity from `params` and returns it as stored.
+    async fn create(
+        &self,
+        params: <Self::Entity as ActorEntity>::Create,
+    ) -> Result<Self::Entity, ApiError>;
+}
+
+#[async_trait]
+impl<T: ActorEntity + Serialize> Resource for ResourceClient<T> {
+    type Entity = T;
+
+    fn resource_client(&self) -> &ResourceClient<T> {
+        self
+    }
+
+    async fn create(&self, params: T::Create) -> Result<T, ApiError> {
+        Ok(self.create_returning(params).await?)
+    }
+}
+
+#[async_trait]
+impl Resource for UserClient {
+    type Entity = User;
+
+    fn resource_client(&self) -> &ResourceClient<User> {
+        self.inner()
+    }
+
+    async fn create(&self, params: UserCreate) -> Result<User, ApiError> {
+        Ok(self.create_user_returning(params).await?)
+    }
+}
+
+#[async_trait]
+impl Resource for ProductClient {
+    type Entity = Product;
+
+    fn resource_client(&self) -> &ResourceClient<Product> {
+        self.inner()
+    }
+
+    async fn create(&self, params: ProductCreate) -> Result<Product, ApiError> {
+        let id = self.create_product(params).await?;
+        get_or_not_found(self, id).await
+    }
+}
+
+#[async_trait]
+impl Resource for OrderClient {
+    type Entity = Order;
+
+    fn resource_client(&self) -> &ResourceClient<Order> {
+        self.inner()
+    }
+
+    /// Places the order with [`OrderClient::create_order`]; a duplicate answers with the order
+    /// it duplicates.
+    async fn create(&self, params: OrderCreate) -> Result<Order, ApiError> {
+        let id = self.create_order(params).await?;
+        get_or_not_found(self, id).await
+    }
+}
+
+/// Fetches the entity with this id, failing with `NotFound` if there is none.
+async fn get_or_not_found<C: Resource>(
+    client: &C,
+    id: <C::Entity as ActorEntity>::Id,
+) -> Result<C::Entity, ApiError> {
+    match client.resource_client().get(id.clone()).await? {
+        Some(item) => Ok(item),
+        None => Err(FrameworkError::NotFound(id.to_string()).into()),
+    }
+}
+
+/// A failed call, answered as its [`WireError`] with the matching status code.
+#[derive(Debug)]
+pub struct ApiError(pub WireError);
+
+impl ApiError {
+    fn new(code: ErrorCode, error: impl Display) -> Self {
+        Self(WireError {
+            code,
+            message: error.to_string(),
+        })
+    }
+}
+
+impl From<FrameworkError> for ApiError {
+    fn from(error: FrameworkError) -> Self {
+        Self(error.to_wire())
+    }
+}
+
+impl From<UserError> for ApiError {
+    fn from(error: UserError) -> Self {
+        let code = match error {
+            UserError::NotFound(_) => ErrorCode::NotFound,
+            UserError::ActorCommunicationError(_) => ErrorCode::ActorClosed,
+            _ => ErrorCode::Entity,
+        };
+        Self::new(code, error)
+    }
+}
+
+impl From<ProductError> for ApiError {
+    fn from(error: ProductError) -> Self {
+        let code = match error {
+            ProductError::NotFound(_) => ErrorCode::NotFound,
+            ProductError::ActorCommunicationError(_) => ErrorCode::ActorClosed,
+            _ => ErrorCode::Entity,
+        };
+        Self::new(code, error)
+    }
+}
+
+impl From<OrderError> for ApiError {
+    fn from(error: OrderError) -> Self {
+        let code = match error {
+            OrderError::NotFound(_) => ErrorCode::NotFound,
+            OrderError::ActorCommunicationError(_) | OrderError::DependencyUnavailable { .. } => {
+                ErrorCode::ActorClosed
+            }
+            _ => ErrorCode::Entity,
+        };
+        Self::new(code, error)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.0.code.http_status())
+            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (status, Json(self.0)).into_response()
+    }
+}
+
+async fn list<C: Resource>(State(client): State<C>) -> Result<Json<Vec<C::Entity>>, ApiError> {
+    Ok(Json(client.resource_client().list().await?))
+}
+
+async fn fetch<C: Resource>(
+    State(client): State<C>,
+    Path(id): Path<u32>,
+) -> Result<Json<C::Entity>, ApiError> {
+    let id = <C::Entity as ActorEntity>::Id::from(id);
+    Ok(Json(get_or_not_found(&client, id).await?))
+}
+
+async fn create<C>(
+    State(client): State<C>,
+    Json(params): Json<<C::Entity as ActorEntity>::Create>,
+) -> Result<(StatusCode, Json<C::Entity>), ApiError>
+where
+    C: Resource,
+    <C::Entity as ActorEntity>::Create: DeserializeOwned,
+{
+    let item = client.create(params).await?;
+    Ok((StatusCode::CREATED, Json(item)))
+}
+
+async fn delete<C: Resource>(
+    State(client): State<C>,
+    Path(id): Path<u32>,
+) -> Result<StatusCode, ApiError> {
+    client
+        .resource_client()
+        .delete(<C::Entity as ActorEntity>::Id::from(id))
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -3,6 +3,8 @@
 //! This library exposes the core modules of the application for integration testing.
 
 pub mod clients;
+#[cfg(feature = "http")]
+pub mod gateway;
 pub mod lifecycle;
 pub mod model;
 pub mod order_actor;
@@ -28,10 +28,10 @@
 //!         let (order_actor, order_client) = order_actor::new();
 //!
 //!         // 2. Start actors with their dependencies injected
-//!         let user_handle = tokio::spawn(user_actor.run(()));
+//!         let user_handle = tokio::spawn(user_actor.run(EmailIndex::default()));
 //!         let product_handle = tokio::spawn(product_actor.run(()));
 //!         let order_handle = tokio::spawn(
-//!             order_actor.run((user_client.clone(), product_client.clone()))
+//!             order_actor.run((user_client.clone(), RecentOrders::disabled()))
 //!         );
 //!
 //!         Self {
@@ -62,7 +62,7 @@
 //! - **Construction time**: Create actors without dependencies
 //! - **Runtime**: Inject dependencies via `run(context)`
 //!
-//! This pattern allows `Order` to depend on `User` and `Product` without creating
+//! This pattern allows `Order` to depend on `User` without creating
 //! circular references during construction.
 //!
 //! Each actor defines its `Context` associated type:
@@ -73,9 +73,9 @@
 //!     type Context = ();
 //! }
 //!
-//! // Depends on User and Product clients
+//! // Depends on the User client and its duplicate detector
 //! impl ActorEntity for Order {
-//!     type Context = (UserClient, ProductClient);
+//!     type Context = (UserClient, RecentOrders);
 //! }
 //! ```
 //!
@@ -90,8 +90,8 @@
 //!
 //! This ensures no messages are lost and all actors terminate cleanly.
 //!
-//! **With Context Dependencies:** When actors hold clients in their context (e.g., `Order` actor has `UserClient` and
-//! `ProductClient`), those clients are clones and won't prevent shutdown as long as the
+//! **With Context Dependencies:** When actors hold clients in their context (e.g., the `Order` actor has a
+//! `UserClient`), those clients are clones and won't prevent shutdown as long as the
 //! dependency graph is **acyclic**. Each actor shuts down when its own channel closes.
 //!
 //! **For cyclic dependencies**: Use an explicit `Shutdown` action instead of relying on
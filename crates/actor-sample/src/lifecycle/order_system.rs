@@ -5,6 +5,18 @@
 //! high‑level clients for interacting with them. Includes lifecycle management
 //! and graceful shutdown.
 use crate::clients::{OrderClient, ProductClient, UserClient};
+use crate::model::{Order, Product, User};
+use crate::order_actor::RecentOrders;
+use crate::user_actor::EmailIndex;
+use actor_framework::tracing::setup_test_tracing;
+use actor_framework::{
+    ActorClient, ActorEntity, ActorStats, AuditEntry, FrameworkError, PriorityClient,
+    ResourceActor, ResourceClient,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 use tracing::{error, info};
 
 /// The main runtime orchestrator for the actor-based order management system.
@@ -12,7 +24,7 @@ use tracing::{error, info};
 /// `OrderSystem` is responsible for:
 /// - **Lifecycle Management**: Starting and stopping all actors in the system
 /// - **Dependency Wiring**: Connecting actors that depend on each other (e.g., OrderClient needs UserClient)
-/// - **Resource Coordination**: Managing shared resources like ID generators
+/// - **Resource Coordination**: Managing shared resources like ID generators (see [`OrderSystem::builder`])
 ///
 /// # Architecture
 ///
@@ -25,6 +37,7 @@ use tracing::{error, info};
 ///
 /// ```ignore
 /// let system = OrderSystem::new();
+/// system.wait_ready().await?;
 ///
 /// // Use the clients to interact with actors
 /// let user_id = system.user_client.create_user(user_data).await?;
@@ -45,7 +58,7 @@ pub struct OrderSystem {
     pub product_client: ProductClient,
 
     /// Task handles for all running actors (used for graceful shutdown)
-    handles: Vec<tokio::task::JoinHandle<()>>,
+    handles: Vec<JoinHandle<()>>,
 }
 
 impl Default for OrderSystem {
@@ -57,41 +70,115 @@ impl Default for OrderSystem {
 impl OrderSystem {
     /// Creates and initializes a new `OrderSystem` with all actors running.
     ///
-    /// This method:
-    /// 1. Creates ID generators for each entity type
-    /// 2. Spawns ResourceActors for User, Product, and Order
-    /// 3. Wires up dependencies (OrderClient depends on UserClient and ProductClient)
-    /// 4. Spawns each actor in its own Tokio task
+    /// Uses the default buffer sizes and sequential ids starting at 1. See
+    /// [`OrderSystem::builder`] to customize either.
     ///
     /// # Returns
     ///
     /// A fully initialized `OrderSystem` with all actors running and ready to accept requests.
     pub fn new() -> Self {
-        // 1. Create actors (no dependencies) and wrap generic clients
-        let (user_actor, user_generic_client) = crate::user_actor::new();
-        let user_client = UserClient::new(user_generic_client);
-        let (product_actor, product_generic_client) = crate::product_actor::new();
-        let product_client = ProductClient::new(product_generic_client);
-        let (order_actor, order_generic_client) = crate::order_actor::new();
-        let order_client = OrderClient::new(order_generic_client);
+        Self::builder().build()
+    }
 
-        // 2. Start actors with injected context
-        // User and Product have no dependencies (Context = ())
-        let user_handle = tokio::spawn(user_actor.run(()));
-        let product_handle = tokio::spawn(product_actor.run(()));
+    /// Creates an `OrderSystem` to share behind an `Arc`, and the handle that shuts it down.
+    ///
+    /// For servers whose request handlers each hold the system: [`OrderSystem::shutdown`]
+    /// consumes the system, which an `Arc` cannot give up, and only stops the actors once every
+    /// client clone is dropped. Here the clients are cloned out of the `Arc` as needed, and
+    /// [`OrderSystemShutdown::shutdown`] stops the actors however many clones remain.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let (system, shutdown) = OrderSystem::new_shared();
+    /// let orders = system.order_client.clone();
+    /// // ... hand `system.clone()` to each request handler ...
+    /// shutdown.shutdown().await?;
+    /// ```
+    pub fn new_shared() -> (Arc<Self>, OrderSystemShutdown) {
+        Self::builder().build_shared()
+    }
 
-        // Order actor needs User and Product clients (Context = (UserClient, ProductClient))
-        let order_handle =
-            tokio::spawn(order_actor.run((user_client.clone(), product_client.clone())));
+    /// Creates an `OrderSystem` for tests.
+    ///
+    /// Installs the test subscriber ([`setup_test_tracing`]), so tests need no tracing setup
+    /// of their own, and gives every actor [`TEST_BUFFER_SIZE`] so bursts of concurrent
+    /// requests don't wait for channel capacity. Each actor hands out sequential ids starting
+    /// at 1, so tests can expect e.g. the first user to be `UserId(1)`.
+    pub fn new_for_test() -> Self {
+        setup_test_tracing();
+        Self::builder()
+            .user_buffer_size(TEST_BUFFER_SIZE)
+            .product_buffer_size(TEST_BUFFER_SIZE)
+            .order_buffer_size(TEST_BUFFER_SIZE)
+            .build()
+    }
 
-        Self {
-            order_client,
-            user_client,
-            product_client,
-            handles: vec![user_handle, product_handle, order_handle],
+    /// Returns a builder for configuring id generation and buffer sizes per actor.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut next = 100;
+    /// let system = OrderSystem::builder()
+    ///     .user_ids(move || { next += 1; next })
+    ///     .order_buffer_size(64)
+    ///     .build();
+    /// ```
+    pub fn builder() -> OrderSystemBuilder {
+        OrderSystemBuilder::default()
+    }
+
+    /// Waits until every actor's loop is running (see [`ResourceClient::ready`]).
+    ///
+    /// Requests sent right after [`OrderSystem::new`] already wait in the channels until the
+    /// actors start, so this is for callers that must know the system is serving before going
+    /// on, e.g. before reporting a server as healthy. The actors are pinged concurrently; fails
+    /// if any of them has already stopped.
+    pub async fn wait_ready(&self) -> Result<(), FrameworkError> {
+        tokio::try_join!(
+            self.user_client.inner().ready(),
+            self.product_client.inner().ready(),
+            self.order_client.inner().ready(),
+        )?;
+        Ok(())
+    }
+
+    /// Queries every actor's [`ActorStats`] and aggregates them for a dashboard.
+    ///
+    /// The actors are queried concurrently. An actor that has already stopped is reported as
+    /// `None` and left out of [`SystemStats::total`].
+    pub async fn system_stats(&self) -> SystemStats {
+        let (user, product, order) = tokio::join!(
+            self.user_client.inner().stats(),
+            self.product_client.inner().stats(),
+            self.order_client.inner().stats(),
+        );
+        SystemStats {
+            user: user.ok(),
+            product: product.ok(),
+            order: order.ok(),
         }
     }
 
+    /// Stops every actor from accepting writes while still serving reads.
+    ///
+    /// The first phase of a graceful shutdown: requests already queued finish, later creates,
+    /// updates, deletes and actions fail with `ShuttingDown` (e.g. a new order), and reads keep
+    /// working until [`OrderSystem::shutdown`] stops the actors.
+    ///
+    /// The Order actor drains first: its drain returns once the orders being placed have been
+    /// placed (see [`OrderClient::begin_draining`]), and those still check the user and reserve
+    /// stock, so User and Product only drain after that.
+    pub async fn begin_draining(&self) -> Result<(), FrameworkError> {
+        self.order_client.begin_draining().await?;
+        tokio::try_join!(
+            self.user_client.inner().begin_draining(),
+            self.product_client.inner().begin_draining(),
+        )?;
+        Ok(())
+    }
+
     /// Gracefully shuts down the entire system.
     ///
     /// This method:
@@ -146,3 +233,280 @@ impl OrderSystem {
         Ok(())
     }
 }
+
+/// Stops the actors of a shared [`OrderSystem`], from [`OrderSystem::new_shared`].
+///
+/// Owned separately from the system so that shutting down does not require the last
+/// reference to it.
+pub struct OrderSystemShutdown {
+    order: PriorityClient<Order>,
+    user: PriorityClient<User>,
+    product: PriorityClient<Product>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl OrderSystemShutdown {
+    /// Stops every actor and waits for their tasks to finish.
+    ///
+    /// The Order actor is stopped first, since it calls the other two. Each actor stops
+    /// without working through its queue: requests still queued fail with `ActorDropped` and
+    /// later ones with `ActorClosed`, through any client clone still held. Call
+    /// [`OrderSystem::begin_draining`] first and let in-flight work finish to avoid that.
+    ///
+    /// Returns an error if any actor task panicked.
+    pub async fn shutdown(self) -> Result<(), String> {
+        info!("Shutting down shared system...");
+
+        // An actor that already stopped has nothing left to stop; its task is still awaited
+        let _ = self.order.shutdown().await;
+        let _ = tokio::join!(self.user.shutdown(), self.product.shutdown());
+
+        for handle in self.handles {
+            if let Err(e) = handle.await {
+                error!("Actor task failed: {:?}", e);
+                return Err(format!("Actor task failed: {:?}", e));
+            }
+        }
+
+        info!("System shutdown complete.");
+        Ok(())
+    }
+}
+
+/// Per-actor runtime counters of an [`OrderSystem`], from [`OrderSystem::system_stats`].
+///
+/// Each field is `None` if that actor was unavailable (already stopped).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SystemStats {
+    pub user: Option<ActorStats>,
+    pub product: Option<ActorStats>,
+    pub order: Option<ActorStats>,
+}
+
+impl SystemStats {
+    /// Sums the counters of every available actor.
+    ///
+    /// Use [`ActorStats::error_rate`] on the result for the system-wide error rate.
+    /// `utilization` of the total is the average across actors, weighted by measured time.
+    pub fn total(&self) -> ActorStats {
+        [&self.user, &self.product, &self.order]
+            .into_iter()
+            .flatten()
+            .fold(ActorStats::default(), |mut total, stats| {
+                total.messages += stats.messages;
+                total.errors += stats.errors;
+                total.idle_time += stats.idle_time;
+                total.busy_time += stats.busy_time;
+                total.evictions += stats.evictions;
+                total
+            })
+    }
+
+    /// Returns `true` if every actor answered.
+    pub fn all_available(&self) -> bool {
+        self.user.is_some() && self.product.is_some() && self.order.is_some()
+    }
+}
+
+/// Channel capacity of every actor in [`OrderSystem::new_for_test`].
+pub const TEST_BUFFER_SIZE: usize = 256;
+
+/// A boxed id generator for one actor (see [`ResourceActor::with_id_generator`]).
+type IdGenerator = Box<dyn FnMut() -> u32 + Send>;
+
+/// Builder for [`OrderSystem`], created with [`OrderSystem::builder`].
+///
+/// Each actor can be given its own id generator (deterministic ids in tests, a
+/// different numbering scheme in production) and channel buffer size. Anything
+/// left unset falls back to the defaults used by [`OrderSystem::new`].
+pub struct OrderSystemBuilder {
+    user_ids: Option<IdGenerator>,
+    product_ids: Option<IdGenerator>,
+    order_ids: Option<IdGenerator>,
+    user_buffer_size: usize,
+    product_buffer_size: usize,
+    order_buffer_size: usize,
+    max_restarts: Option<usize>,
+    order_dedup_window: Option<Duration>,
+    order_audit: Option<mpsc::Sender<AuditEntry>>,
+}
+
+impl Default for OrderSystemBuilder {
+    fn default() -> Self {
+        Self {
+            user_ids: None,
+            product_ids: None,
+            order_ids: None,
+            user_buffer_size: crate::user_actor::DEFAULT_BUFFER_SIZE,
+            product_buffer_size: crate::product_actor::DEFAULT_BUFFER_SIZE,
+            order_buffer_size: crate::order_actor::DEFAULT_BUFFER_SIZE,
+            max_restarts: None,
+            order_dedup_window: None,
+            order_audit: None,
+        }
+    }
+}
+
+impl OrderSystemBuilder {
+    /// Sets the id generator for the User actor.
+    pub fn user_ids(mut self, next_id: impl FnMut() -> u32 + Send + 'static) -> Self {
+        self.user_ids = Some(Box::new(next_id));
+        self
+    }
+
+    /// Sets the id generator for the Product actor.
+    pub fn product_ids(mut self, next_id: impl FnMut() -> u32 + Send + 'static) -> Self {
+        self.product_ids = Some(Box::new(next_id));
+        self
+    }
+
+    /// Sets the id generator for the Order actor.
+    pub fn order_ids(mut self, next_id: impl FnMut() -> u32 + Send + 'static) -> Self {
+        self.order_ids = Some(Box::new(next_id));
+        self
+    }
+
+    /// Sets the channel capacity of the User actor.
+    pub fn user_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.user_buffer_size = buffer_size;
+        self
+    }
+
+    /// Sets the channel capacity of the Product actor.
+    pub fn product_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.product_buffer_size = buffer_size;
+        self
+    }
+
+    /// Sets the channel capacity of the Order actor.
+    pub fn order_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.order_buffer_size = buffer_size;
+        self
+    }
+
+    /// Supervises every actor, restarting it with an empty store when it panics.
+    ///
+    /// Each actor is restarted at most `max_restarts` times before it is left stopped
+    /// (see [`ResourceActor::run_supervised`]). Clients stay connected across restarts.
+    /// Without this, an actor that panics stays dead until the system is rebuilt.
+    pub fn max_restarts(mut self, max_restarts: usize) -> Self {
+        self.max_restarts = Some(max_restarts);
+        self
+    }
+
+    /// Answers an order identical to one created less than `window` ago (same user, product
+    /// and quantity) with the existing order's id, instead of creating and reserving stock
+    /// for it again (see [`RecentOrders`]).
+    ///
+    /// Without this, every order is created, however recent an identical one is.
+    pub fn order_dedup_window(mut self, window: Duration) -> Self {
+        self.order_dedup_window = Some(window);
+        self
+    }
+
+    /// Records every read and write of an order to `sink` (see
+    /// [`ResourceActor::with_audit`]), e.g. for a compliance log.
+    pub fn order_audit(mut self, sink: mpsc::Sender<AuditEntry>) -> Self {
+        self.order_audit = Some(sink);
+        self
+    }
+
+    /// Creates, wires and spawns all actors.
+    ///
+    /// This method:
+    /// 1. Creates ResourceActors for User, Product, and Order with the configured id generators
+    /// 2. Wires up dependencies (OrderClient depends on UserClient and ProductClient)
+    /// 3. Spawns each actor in its own Tokio task, supervised if [`max_restarts`](Self::max_restarts) is set
+    pub fn build(self) -> OrderSystem {
+        let (mut system, shutdown) = self.start();
+        system.handles = shutdown.handles;
+        system
+    }
+
+    /// Like [`build`](Self::build), but for sharing the system behind an `Arc` (see
+    /// [`OrderSystem::new_shared`]).
+    pub fn build_shared(self) -> (Arc<OrderSystem>, OrderSystemShutdown) {
+        let (system, shutdown) = self.start();
+        (Arc::new(system), shutdown)
+    }
+
+    /// Creates, wires and spawns all actors, keeping their task handles in the shutdown handle.
+    fn start(self) -> (OrderSystem, OrderSystemShutdown) {
+        // 1. Create actors (no dependencies) and wrap generic clients
+        let (user_actor, user_generic_client) = create_actor(self.user_buffer_size, self.user_ids);
+        let emails = EmailIndex::default();
+        let user_client = UserClient::new(user_generic_client, emails.clone());
+        let (product_actor, product_generic_client) =
+            create_actor(self.product_buffer_size, self.product_ids);
+        let product_client = ProductClient::new(product_generic_client);
+        let (mut order_actor, order_generic_client) =
+            create_actor(self.order_buffer_size, self.order_ids);
+        if let Some(sink) = self.order_audit {
+            order_actor = order_actor.with_audit(sink);
+        }
+        let order_client = OrderClient::new(
+            order_generic_client,
+            user_client.clone(),
+            product_client.clone(),
+            Arc::new(product_client.clone()),
+        );
+
+        let user_priority = user_actor.priority_client();
+        let product_priority = product_actor.priority_client();
+        let order_priority = order_actor.priority_client();
+
+        // 2. Start actors with injected context
+        // User and Product depend on no other actor; the User actor keeps its email index
+        // (Context = EmailIndex), shared with the User client for lookups
+        let user_handle = spawn_actor(user_actor, emails, self.max_restarts);
+        let product_handle = spawn_actor(product_actor, (), self.max_restarts);
+
+        // Order actor needs the User client and its duplicate detector
+        // (Context = (UserClient, RecentOrders)); stock is reserved by the Order client
+        let recent_orders = self
+            .order_dedup_window
+            .map_or_else(RecentOrders::disabled, RecentOrders::new);
+        let order_handle = spawn_actor(
+            order_actor,
+            (user_client.clone(), recent_orders),
+            self.max_restarts,
+        );
+
+        let system = OrderSystem {
+            order_client,
+            user_client,
+            product_client,
+            handles: Vec::new(),
+        };
+        let shutdown = OrderSystemShutdown {
+            order: order_priority,
+            user: user_priority,
+            product: product_priority,
+            handles: vec![user_handle, product_handle, order_handle],
+        };
+        (system, shutdown)
+    }
+}
+
+/// Creates an actor with a custom id generator, or the default sequential ids.
+fn create_actor<T: ActorEntity>(
+    buffer_size: usize,
+    next_id: Option<IdGenerator>,
+) -> (ResourceActor<T>, ResourceClient<T>) {
+    match next_id {
+        Some(next_id) => ResourceActor::with_id_generator(buffer_size, next_id),
+        None => ResourceActor::new(buffer_size),
+    }
+}
+
+/// Spawns an actor's event loop, supervised when a restart cap is configured.
+fn spawn_actor<T: ActorEntity>(
+    actor: ResourceActor<T>,
+    context: T::Context,
+    max_restarts: Option<usize>,
+) -> JoinHandle<()> {
+    match max_restarts {
+        Some(max_restarts) => tokio::spawn(actor.run_supervised(context, max_restarts)),
+        None => tokio::spawn(actor.run(context)),
+    }
+}
@@ -22,7 +22,7 @@
 
 use actor_framework::tracing::setup_tracing;
 use actor_sample::lifecycle::OrderSystem;
-use actor_sample::model::{OrderCreate, ProductCreate, UserCreate};
+use actor_sample::model::{Money, OrderCreate, OrderLine, ProductCreate, UserCreate};
 use tracing::{error, info, Instrument};
 
 #[tokio::main]
@@ -34,6 +34,7 @@ async fn main() -> Result<(), String> {
 
     // Create the entire order system (starts all services)
     let system = OrderSystem::new();
+    system.wait_ready().await.map_err(|e| e.to_string())?;
 
     // Create test user
     let user_params = UserCreate {
@@ -58,7 +59,7 @@ async fn main() -> Result<(), String> {
     // Create test product
     let product_params = ProductCreate {
         name: "Test Product".to_string(),
-        price: 100.0,
+        price: Money::from_dollars(100.0),
         quantity: 10,
     };
     let product_id = async {
@@ -76,9 +77,11 @@ async fn main() -> Result<(), String> {
     // Create test order - this will flow through multiple actors
     let order_params = OrderCreate {
         user_id: user_id.clone(),
-        product_id: product_id.clone(),
-        quantity: 5,
-        total: 500.0,
+        items: vec![OrderLine::new(
+            product_id.clone(),
+            5,
+            Money::from_dollars(100.0),
+        )],
     };
 
     let span = tracing::info_span!("order_processing");
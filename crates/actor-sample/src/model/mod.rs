@@ -51,6 +51,24 @@
 //! This pattern ensures type safety: you **can't** create a user without a name,
 //! but you **can** update just the email without touching the name.
 //!
+//! ### 3. Validation
+//!
+//! Every Create DTO implements [`Validate`] (defined by the framework, so that clients can
+//! also run it before sending a create), checking its own fields (a non-empty name, a
+//! well-formed email, a positive price or order quantity) and reporting all invalid ones in a
+//! single [`ValidationError`]. Update DTOs hold the fields they set to the same rules. Validation is separate from construction, so a payload can be
+//! checked anywhere, e.g. in an HTTP handler before any actor is involved.
+//!
+//! ### 4. Conversions
+//!
+//! The DTO↔entity mapping lives next to the types, as standard conversion traits:
+//!
+//! - `From<(UserId, UserCreate)> for User` (and likewise for `Product` and `Order`) builds an
+//!   entity from the id assigned by the actor plus the create payload.
+//! - `From<User> for UserCreate` (and `Product` → `ProductCreate`) recovers the payload.
+//!
+//! Each `from_create_params` is then `params.validate()?` followed by `(id, params).into()`.
+//!
 //! ## Resource Models
 //!
 //! A **resource** is a business entity that the system manages (User, Product, Order).
@@ -69,7 +87,11 @@
 //! ### [`Order`]
 //!
 //! Represents a customer order. Orders reference both a user (who placed it)
-//! and a product (what was ordered), demonstrating actor coordination.
+//! and one or more products (an [`OrderLine`] each), demonstrating actor coordination.
+//!
+//! ### [`Money`]
+//!
+//! Prices and totals, held as whole cents so that they add up exactly.
 //!
 //! ## Design Patterns
 //!
@@ -95,13 +117,16 @@
 //! my-framework/     # Generic framework code
 //! ```
 //!
-//! The models have **zero dependencies** on the framework, making them
-//! easy to extract into a shared library.
+//! Apart from the [`Validate`] trait, the models have **zero dependencies** on the framework,
+//! making them easy to extract into a shared library.
 
+pub mod money;
 pub mod order;
 pub mod product;
 pub mod user;
 
+pub use actor_framework::validate::{FieldError, Validate, ValidationError};
+pub use money::Money;
 pub use order::*;
 pub use product::*;
 pub use user::*;
@@ -0,0 +1,167 @@
+//! An amount of money in whole cents.
+//!
+//! Prices and totals are sums of money, where `f64` cannot represent most cent amounts
+//! exactly (`0.1 + 0.2 != 0.3`), so totals drift and comparing them is fragile. [`Money`]
+//! stores an integer number of cents and does exact arithmetic on it.
+//!
+//! The operators panic on overflow, in release builds too, rather than wrap to a wrong
+//! amount. Amounts derived from caller input (a quantity times a price) go through
+//! [`Money::checked_mul`] and [`Money::checked_add`] and are rejected when they overflow.
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// An amount of money, stored as a whole number of cents.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct Money(i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    pub const fn from_cents(cents: i64) -> Self {
+        Self(cents)
+    }
+
+    /// Converts a dollar amount, rounding to the nearest cent.
+    ///
+    /// Meant for literals and input parsing; `NaN` becomes zero and infinities saturate.
+    pub fn from_dollars(dollars: f64) -> Self {
+        Self((dollars * 100.0).round() as i64)
+    }
+
+    pub const fn cents(self) -> i64 {
+        self.0
+    }
+
+    /// The amount in dollars, for display or export only; do arithmetic on `Money` itself.
+    pub fn to_dollars(self) -> f64 {
+        self.0 as f64 / 100.0
+    }
+
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    /// `self + rhs`, or `None` if the sum overflows.
+    pub const fn checked_add(self, rhs: Money) -> Option<Money> {
+        match self.0.checked_add(rhs.0) {
+            Some(cents) => Some(Self(cents)),
+            None => None,
+        }
+    }
+
+    /// `self - rhs`, or `None` if the difference overflows.
+    pub const fn checked_sub(self, rhs: Money) -> Option<Money> {
+        match self.0.checked_sub(rhs.0) {
+            Some(cents) => Some(Self(cents)),
+            None => None,
+        }
+    }
+
+    /// `self * quantity`, or `None` if the product overflows.
+    pub const fn checked_mul(self, quantity: u32) -> Option<Money> {
+        match self.0.checked_mul(quantity as i64) {
+            Some(cents) => Some(Self(cents)),
+            None => None,
+        }
+    }
+}
+
+/// Formats as dollars with two decimals, e.g. `$127.50` or `-$0.05`.
+impl Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let cents = self.0.unsigned_abs();
+        write!(f, "{sign}${}.{:02}", cents / 100, cents % 100)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, rhs: Money) -> Money {
+        self.checked_add(rhs).expect("Money addition overflowed")
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, rhs: Money) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, rhs: Money) -> Money {
+        self.checked_sub(rhs).expect("Money subtraction overflowed")
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, rhs: Money) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money(self.0.checked_neg().expect("Money negation overflowed"))
+    }
+}
+
+/// Multiplies a unit price by a quantity.
+impl Mul<u32> for Money {
+    type Output = Money;
+
+    fn mul(self, quantity: u32) -> Money {
+        self.checked_mul(quantity)
+            .expect("Money multiplication overflowed")
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Add::add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cent_amounts_add_exactly() {
+        let total: Money = [0.1, 0.2].into_iter().map(Money::from_dollars).sum();
+        assert_eq!(total, Money::from_dollars(0.3));
+        assert_eq!(Money::from_dollars(25.5) * 5, Money::from_cents(12750));
+    }
+
+    #[test]
+    fn test_checked_arithmetic_reports_overflow() {
+        let max = Money::from_cents(i64::MAX);
+        assert_eq!(max.checked_add(Money::from_cents(1)), None);
+        assert_eq!(max.checked_mul(2), None);
+        assert_eq!(
+            Money::from_cents(-5).checked_sub(Money::from_cents(i64::MAX)),
+            None
+        );
+        assert_eq!(
+            Money::from_cents(250).checked_mul(3),
+            Some(Money::from_cents(750))
+        );
+    }
+
+    #[test]
+    fn test_display_shows_dollars_and_cents() {
+        assert_eq!(Money::from_cents(12750).to_string(), "$127.50");
+        assert_eq!(Money::from_cents(-5).to_string(), "-$0.05");
+        assert_eq!(Money::ZERO.to_string(), "$0.00");
+    }
+}
@@ -6,9 +6,10 @@
 ///
 /// See [`impl ActorEntity for Order`](#impl-ActorEntity-for-Order) for details on:
 /// - Creation parameters ([`OrderCreate`])
-use crate::model::{ProductId, UserId};
+use crate::model::{Money, ProductId, UserId, Validate, ValidationError};
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
+use std::time::SystemTime;
 
 /// Type-safe identifier for Orders.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -26,54 +27,159 @@ impl Display for OrderId {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Order {
     #[allow(dead_code)]
     pub id: OrderId,
     pub user_id: UserId,
-    pub product_id: ProductId,
-    pub quantity: u32,
-    pub total: f64,
+    /// The products ordered, one line per product.
+    pub items: Vec<OrderLine>,
+    /// The sum of the line totals.
+    pub total: Money,
     #[allow(dead_code)]
     pub status: String,
+    /// When the order was deleted. A deleted order is kept as a tombstone for auditing.
+    pub deleted_at: Option<SystemTime>,
 }
 
-/// Payload for creating a new order.
+/// One product of an order: how many units, at what price each.
+///
+/// The caller's `unit_price` is only checked; once the stock is reserved it is replaced by
+/// the Product's price, so a client cannot set its own price.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OrderLine {
+    pub product_id: ProductId,
+    pub quantity: u32,
+    pub unit_price: Money,
+}
+
+impl OrderLine {
+    pub fn new(product_id: ProductId, quantity: u32, unit_price: Money) -> Self {
+        Self {
+            product_id,
+            quantity,
+            unit_price,
+        }
+    }
+
+    /// The price of the whole line.
+    ///
+    /// Panics if it overflows; orders that passed [`OrderCreate::validate`] or
+    /// [`Order::set_unit_prices`] never do.
+    pub fn total(&self) -> Money {
+        self.unit_price * self.quantity
+    }
+
+    /// The price of the whole line, or `None` if it overflows.
+    pub fn checked_total(&self) -> Option<Money> {
+        self.unit_price.checked_mul(self.quantity)
+    }
+}
+
+/// The sum of the lines' totals, or `None` if it overflows.
+fn checked_total(items: &[OrderLine]) -> Option<Money> {
+    items.iter().try_fold(Money::ZERO, |sum, line| {
+        sum.checked_add(line.checked_total()?)
+    })
+}
+
+/// The error for an order whose total does not fit in [`Money`].
+fn total_too_large() -> ValidationError {
+    let mut errors = ValidationError::default();
+    errors.add("total", "is too large");
+    errors
+}
+
+/// An order joined with the names of the user and products it refers to.
+///
+/// Built by [`OrderClient::order_summary`](crate::clients::OrderClient::order_summary) for
+/// responses that show an order without further lookups.
 #[derive(Debug, Clone)]
+pub struct OrderSummary {
+    pub order: Order,
+    pub user_name: String,
+    /// The name of each line's product, in the order of `order.items`.
+    pub product_names: Vec<String>,
+}
+
+/// Payload for creating a new order.
+///
+/// The order's total is computed from the lines.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderCreate {
     pub user_id: UserId,
-    pub product_id: ProductId,
-    pub quantity: u32,
-    pub total: f64,
+    pub items: Vec<OrderLine>,
 }
 
 impl Order {
-    /// Creates a new Order instance.
+    /// Creates a new Order instance, totalling its lines.
     ///
     /// # Arguments
     /// * `id` - Unique identifier (typically set by the actor system)
     /// * `user_id` - ID of the user placing the order
-    /// * `product_id` - ID of the product being ordered
-    /// * `quantity` - Quantity ordered
-    /// * `total` - Total price for the order
+    /// * `items` - The products ordered
     ///
     /// # Notes
     /// The order is initialized with status "Created".
-    /// This constructor is kept for backward compatibility.
-    pub fn new(
-        id: OrderId,
-        user_id: UserId,
-        product_id: ProductId,
-        quantity: u32,
-        total: f64,
-    ) -> Self {
+    pub fn new(id: OrderId, user_id: UserId, items: Vec<OrderLine>) -> Self {
         Self {
             id,
             user_id,
-            product_id,
-            quantity,
-            total,
+            total: items.iter().map(OrderLine::total).sum(),
+            items,
             status: "Created".to_string(),
+            deleted_at: None,
+        }
+    }
+
+    /// Replaces each line's unit price, in line order, and recomputes the total.
+    ///
+    /// Fails with a `ValidationError`, leaving the order unchanged, if the total overflows.
+    pub fn set_unit_prices(&mut self, unit_prices: &[Money]) -> Result<(), ValidationError> {
+        let mut items = self.items.clone();
+        for (line, price) in items.iter_mut().zip(unit_prices) {
+            line.unit_price = *price;
+        }
+        self.total = checked_total(&items).ok_or_else(total_too_large)?;
+        self.items = items;
+        Ok(())
+    }
+}
+
+/// Requires at least one line, each of at least 1 unit at a price that is not negative, and a
+/// total that does not overflow.
+impl Validate for OrderCreate {
+    fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = ValidationError::default();
+        if self.items.is_empty() {
+            errors.add("items", "must not be empty");
+        }
+        if self.items.iter().any(|line| line.quantity == 0) {
+            errors.add("quantity", "must be at least 1");
+        }
+        if self.items.iter().any(|line| line.unit_price.is_negative()) {
+            errors.add("unit_price", "must not be negative");
+        }
+        if checked_total(&self.items).is_none() {
+            errors.add("total", "is too large");
+        }
+        errors.into_result()
+    }
+}
+
+/// Builds the entity from the id assigned by the actor and the create payload.
+impl From<(OrderId, OrderCreate)> for Order {
+    fn from((id, params): (OrderId, OrderCreate)) -> Self {
+        Self::new(id, params.user_id, params.items)
+    }
+}
+
+/// Recovers the create payload from an existing order, dropping its id, total and status.
+impl From<Order> for OrderCreate {
+    fn from(order: Order) -> Self {
+        Self {
+            user_id: order.user_id,
+            items: order.items,
         }
     }
 }
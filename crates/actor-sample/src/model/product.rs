@@ -8,6 +8,7 @@
 /// - Creation parameters ([`ProductCreate`](crate::model::ProductCreate))
 /// - Update parameters ([`ProductUpdate`](crate::model::ProductUpdate))
 /// - Custom actions ([`ProductAction`](crate::product_actor::actions::ProductAction))
+use crate::model::{Money, OrderId, Validate, ValidationError};
 use serde::{Deserialize, Serialize};
 
 use std::fmt::Display;
@@ -28,13 +29,16 @@ impl Display for ProductId {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Product {
     #[allow(dead_code)]
     pub id: ProductId,
     pub name: String,
-    pub price: f64,
+    pub price: Money,
     pub quantity: u32,
+    /// Stock held for each order, already subtracted from `quantity`. At most one entry per
+    /// order.
+    pub reservations: Vec<(OrderId, u32)>,
 }
 
 impl Product {
@@ -45,27 +49,88 @@ impl Product {
     /// * `name` - Product name
     /// * `price` - Product price
     /// * `quantity` - Available stock quantity
-    pub fn new(id: ProductId, name: impl Into<String>, price: f64, quantity: u32) -> Self {
+    pub fn new(id: ProductId, name: impl Into<String>, price: Money, quantity: u32) -> Self {
         Self {
             id,
             name: name.into(),
             price,
             quantity,
+            reservations: Vec::new(),
         }
     }
 }
 
+/// The price and stock of a product, without its name or reservations.
+///
+/// The [`Project::Projection`](actor_framework::Project) of [`Product`], for callers such as
+/// pricing or stock checks that don't need the whole entity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductSummary {
+    pub id: ProductId,
+    pub price: Money,
+    pub quantity: u32,
+}
+
 /// DTOs for Product creation and updates.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProductCreate {
     pub name: String,
-    pub price: f64,
+    pub price: Money,
     pub quantity: u32,
 }
 
+/// Requires a non-empty name and a positive price.
+impl Validate for ProductCreate {
+    fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = ValidationError::default();
+        if self.name.trim().is_empty() {
+            errors.add("name", "must not be empty");
+        }
+        if self.price <= Money::ZERO {
+            errors.add("price", "must be positive");
+        }
+        errors.into_result()
+    }
+}
+
+/// Requires a positive price, like [`ProductCreate`], if the update sets one.
+impl Validate for ProductUpdate {
+    fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = ValidationError::default();
+        if self.price.is_some_and(|price| price <= Money::ZERO) {
+            errors.add("price", "must be positive");
+        }
+        errors.into_result()
+    }
+}
+
+/// Builds the entity from the id assigned by the actor and the create payload.
+impl From<(ProductId, ProductCreate)> for Product {
+    fn from((id, params): (ProductId, ProductCreate)) -> Self {
+        Self::new(id, params.name, params.price, params.quantity)
+    }
+}
+
+/// Recovers the create payload from an existing product, dropping its id and reservations.
+impl From<Product> for ProductCreate {
+    fn from(product: Product) -> Self {
+        Self {
+            name: product.name,
+            price: product.price,
+            quantity: product.quantity,
+        }
+    }
+}
+
 // DTOs for Product updates.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProductUpdate {
-    pub price: Option<f64>,
+    pub price: Option<Money>,
+    /// Sets the stock level absolutely. Prefer `quantity_delta` when other tasks may be
+    /// reserving stock concurrently, since an absolute write overwrites their reservations.
     pub quantity: Option<u32>,
+    /// Adjusts the stock level relative to its current value (e.g. `+10` on restock), applied
+    /// atomically by the actor after `quantity`.
+    #[serde(default)]
+    pub quantity_delta: Option<i64>,
 }
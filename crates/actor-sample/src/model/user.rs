@@ -1,3 +1,4 @@
+use crate::model::{Validate, ValidationError};
 use serde::{Deserialize, Serialize};
 
 /// Represents a registered user in the system.
@@ -27,7 +28,13 @@ impl Display for UserId {
     }
 }
 
+/// The user a request is made on behalf of, sent as its
+/// [`RequestContext`](actor_framework::RequestContext) (see
+/// [`OrderClient::acting_as`](crate::clients::OrderClient::acting_as)).
 #[derive(Debug, Clone, PartialEq)]
+pub struct ActingUser(pub UserId);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct User {
     pub id: UserId,
     pub name: String,
@@ -35,12 +42,40 @@ pub struct User {
 }
 
 /// Payload for creating a new user.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserCreate {
     pub name: String,
     pub email: String,
 }
 
+/// Requires a non-empty name and a well-formed email.
+impl Validate for UserCreate {
+    fn validate(&self) -> Result<(), ValidationError> {
+        check_fields(Some(&self.name), Some(&self.email))
+    }
+}
+
+/// Builds the entity from the id assigned by the actor and the create payload.
+impl From<(UserId, UserCreate)> for User {
+    fn from((id, params): (UserId, UserCreate)) -> Self {
+        Self {
+            id,
+            name: params.name,
+            email: params.email,
+        }
+    }
+}
+
+/// Recovers the create payload from an existing user, dropping its id.
+impl From<User> for UserCreate {
+    fn from(user: User) -> Self {
+        Self {
+            name: user.name,
+            email: user.email,
+        }
+    }
+}
+
 /// Payload for updating an existing user.
 /// DTOs for User updates.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,6 +84,39 @@ pub struct UserUpdate {
     pub email: Option<String>,
 }
 
+/// Holds the fields it sets to the same rules as [`UserCreate`].
+impl Validate for UserUpdate {
+    fn validate(&self) -> Result<(), ValidationError> {
+        check_fields(self.name.as_deref(), self.email.as_deref())
+    }
+}
+
+/// Checks the user fields that are present: a non-empty name and a well-formed email.
+pub(crate) fn check_fields(name: Option<&str>, email: Option<&str>) -> Result<(), ValidationError> {
+    let mut errors = ValidationError::default();
+    if name.is_some_and(|name| name.trim().is_empty()) {
+        errors.add("name", "must not be empty");
+    }
+    if email.is_some_and(|email| !is_valid_email(email)) {
+        errors.add("email", "must look like name@example.com");
+    }
+    errors.into_result()
+}
+
+/// Whether `email` looks like `local@domain.tld`. Deliverability is not checked.
+fn is_valid_email(email: &str) -> bool {
+    match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain
+                    .split_once('.')
+                    .is_some_and(|(name, tld)| !name.is_empty() && !tld.is_empty())
+        }
+        None => false,
+    }
+}
+
 impl User {
     /// Creates a new User instance.
     ///
@@ -0,0 +1,18 @@
+//! Custom actions for the Order actor.
+//!
+//! This module defines the resource-specific operations (Actions) that can be performed
+//! on an [`Order`](crate::model::Order) entity beyond creating and deleting it.
+//! These actions are handled by the [`ActorEntity::handle_action`](actor_framework::ActorEntity::handle_action) method.
+
+use crate::model::Money;
+
+/// Custom actions for Order entities.
+#[derive(Debug, Clone)]
+pub enum OrderAction {
+    /// Replaces each line's unit price, in line order, and recomputes the total; sent by
+    /// `OrderClient::create_order` with the prices the stock was reserved at.
+    ///
+    /// # Errors
+    /// Will fail with `ValidationError`, leaving the order unchanged, if the total overflows.
+    SetUnitPrices(Vec<Money>),
+}
@@ -0,0 +1,80 @@
+//! Duplicate order detection.
+//!
+//! Two identical `create_order` calls (same user, products and quantities) arriving close
+//! together are almost always a retried or double-submitted request. [`RecentOrders`] remembers
+//! the orders created within a time window so `Order::on_create` can answer such a duplicate
+//! with the existing order instead of reserving stock a second time. Deleting an order
+//! (including a rollback by `OrderClient::create_order`) forgets it again.
+//!
+//! The Order actor handles creates one at a time, so a duplicate arriving concurrently with
+//! the original is still caught: it is checked only after the original's `on_create` finished.
+
+use crate::model::{Order, OrderId, ProductId, UserId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// What makes two orders duplicates of each other. Prices are left out: the caller's are
+/// replaced by the Products' once the stock is reserved.
+type OrderKey = (UserId, Vec<(ProductId, u32)>);
+
+/// The orders created within the last `window`, shared by the Order actor's context.
+///
+/// [`RecentOrders::disabled`] (the default) never reports a duplicate, so identical orders
+/// are all created.
+#[derive(Clone, Debug, Default)]
+pub struct RecentOrders {
+    window: Option<Duration>,
+    seen: Arc<Mutex<HashMap<OrderKey, (OrderId, Instant)>>>,
+}
+
+impl RecentOrders {
+    /// Treats an order as a duplicate if an identical one was created less than `window` ago.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window: Some(window),
+            seen: Arc::default(),
+        }
+    }
+
+    /// Never reports a duplicate.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Returns the id of an order identical to `order` created within the window.
+    pub(crate) fn find(&self, order: &Order) -> Option<OrderId> {
+        let window = self.window?;
+        let mut seen = self.seen.lock().unwrap();
+        seen.retain(|_, (_, created)| created.elapsed() < window);
+        seen.get(&key(order)).map(|(id, _)| id.clone())
+    }
+
+    /// Remembers a newly created order.
+    pub(crate) fn record(&self, order: &Order) {
+        if self.window.is_some() {
+            let mut seen = self.seen.lock().unwrap();
+            seen.insert(key(order), (order.id.clone(), Instant::now()));
+        }
+    }
+
+    /// Forgets a deleted order, so resubmitting it creates a new order instead of resolving
+    /// to the deleted one.
+    pub(crate) fn forget(&self, order: &Order) {
+        let mut seen = self.seen.lock().unwrap();
+        let key = key(order);
+        if seen.get(&key).is_some_and(|(id, _)| *id == order.id) {
+            seen.remove(&key);
+        }
+    }
+}
+
+fn key(order: &Order) -> OrderKey {
+    let lines = order
+        .items
+        .iter()
+        .map(|line| (line.product_id.clone(), line.quantity))
+        .collect();
+    (order.user_id.clone(), lines)
+}
@@ -5,11 +5,13 @@
 //!
 //! See the trait implementation on [`Order`] for method documentation.
 
-use crate::clients::{ProductClient, UserClient};
-use crate::model::{Order, OrderCreate, OrderId};
-use crate::order_actor::OrderError;
-use actor_framework::{ActorClient, ActorEntity};
+use crate::clients::UserClient;
+use crate::model::{ActingUser, Order, OrderCreate, OrderId, Validate};
+use crate::order_actor::{OrderAction, OrderError, RecentOrders};
+use actor_framework::{request_context, ActorClient, ActorEntity};
 use async_trait::async_trait;
+use std::time::SystemTime;
+use tracing::{info_span, Instrument};
 
 /// Marker constant to ensure module documentation is rendered.
 #[doc(hidden)]
@@ -22,61 +24,106 @@ impl ActorEntity for Order {
     type Id = OrderId;
     type Create = OrderCreate;
     type Update = (); // No updates for now
-    type Action = (); // No custom actions for now
+    type Action = OrderAction;
     type ActionResult = ();
-    type Context = (UserClient, ProductClient);
+    type Context = (UserClient, RecentOrders);
     type Error = OrderError;
 
-    // fn id(&self) -> &String { &self.id }
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+
+    fn entity_name() -> &'static str {
+        "Order"
+    }
 
-    /// Creates a new Order from creation parameters.
+    /// Creates a new Order from creation parameters, rejecting an order without lines, a zero
+    /// quantity or a negative price with `ValidationError` before any other actor is contacted.
     fn from_create_params(id: Self::Id, params: Self::Create) -> Result<Self, Self::Error> {
-        Ok(Self::new(
-            id,
-            params.user_id,
-            params.product_id,
-            params.quantity,
-            params.total,
-        ))
+        params.validate()?;
+        Ok((id, params).into())
     }
 
-    /// Validates the order by checking User existence and reserving Product stock.
+    /// Validates the order by checking User existence.
+    ///
+    /// A request sent on behalf of a user (an [`ActingUser`] request context) may only order
+    /// for that user, failing with `Forbidden` otherwise. A duplicate of a recent order is
+    /// rejected with `Duplicate` before the user is checked. If the User actor has stopped,
+    /// fails with `DependencyUnavailable` naming it. The stock is reserved afterwards, by
+    /// `OrderClient::create_order`.
+    ///
+    /// Runs inside an `order_on_create` span carrying the order id, so the spans and events of
+    /// the User call it makes show which order triggered them.
     async fn on_create(
         &mut self,
-        (user_client, product_client): &Self::Context,
+        (user_client, recent): &Self::Context,
     ) -> Result<(), Self::Error> {
-        // 1. Validate User
-        let user = user_client.get(self.user_id.clone()).await?;
+        let span = info_span!("order_on_create", order_id = %self.id);
+        async {
+            // 0. Reject an order placed for someone else, then a resubmission of a recent one
+            if let Some(ActingUser(acting)) = request_context::current::<ActingUser>() {
+                if acting != self.user_id {
+                    return Err(OrderError::Forbidden {
+                        acting,
+                        owner: self.user_id.clone(),
+                    });
+                }
+            }
+            if let Some(existing) = recent.find(self) {
+                return Err(OrderError::Duplicate(existing));
+            }
 
-        if user.is_none() {
-            return Err(OrderError::InvalidUser(self.user_id.to_string()));
-        }
+            // 1. Validate User
+            let user = user_client.get(self.user_id.clone()).await.map_err(|e| {
+                if user_client.inner().is_closed() {
+                    OrderError::DependencyUnavailable { service: "user" }
+                } else {
+                    e.into()
+                }
+            })?;
 
-        // 2. Reserve Stock - errors automatically convert via #[from]
-        product_client
-            .reserve_stock(self.product_id.clone(), self.quantity)
-            .await?;
+            if user.is_none() {
+                return Err(OrderError::InvalidUser(self.user_id.to_string()));
+            }
 
-        Ok(())
+            recent.record(self);
+            Ok(())
+        }
+        .instrument(span)
+        .await
     }
 
     async fn handle_action(
         &mut self,
-        _action: Self::Action,
+        action: Self::Action,
         _ctx: &Self::Context,
     ) -> Result<Self::ActionResult, Self::Error> {
-        Ok(())
+        match action {
+            OrderAction::SetUnitPrices(unit_prices) => Ok(self.set_unit_prices(&unit_prices)?),
+        }
     }
 
     async fn on_update(
         &mut self,
         _update: Self::Update,
         _ctx: &Self::Context,
-    ) -> Result<(), Self::Error> {
-        Ok(())
+    ) -> Result<bool, Self::Error> {
+        Ok(true)
     }
 
-    async fn on_delete(&self, _ctx: &Self::Context) -> Result<(), Self::Error> {
+    /// Drops the order from the recent orders, so an identical order is no longer a duplicate.
+    async fn on_delete(&self, (_, recent): &Self::Context) -> Result<(), Self::Error> {
+        recent.forget(self);
         Ok(())
     }
+
+    /// A deleted order is hidden from reads but never removed, so its history stays auditable.
+    fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    fn mark_deleted(&mut self) -> bool {
+        self.deleted_at = Some(SystemTime::now());
+        true
+    }
 }
@@ -1,5 +1,6 @@
 //! Error types for the Order actor.
 
+use crate::model::{OrderId, ProductId, UserId, ValidationError};
 use crate::product_actor::ProductError;
 use crate::user_actor::UserError;
 use thiserror::Error;
@@ -27,9 +28,41 @@ pub enum OrderError {
     #[error("Insufficient stock: {0}")]
     InsufficientStock(String),
 
-    /// The order data provided is invalid.
+    /// A product does not have enough stock to fill its line of the order.
+    ///
+    /// Carries the shortfall so callers can, e.g., suggest ordering `available` instead.
+    #[error("Out of stock for product {product_id}: requested {requested}, available {available}")]
+    OutOfStock {
+        product_id: ProductId,
+        requested: u32,
+        available: u32,
+    },
+
+    /// The acting user placed an order for another user.
+    #[error("User {acting} may not place orders for {owner}")]
+    Forbidden { acting: UserId, owner: UserId },
+
+    /// An identical order (same user and lines) was created recently.
+    ///
+    /// `OrderClient::create_order` turns this into the existing order's id.
+    #[error("Duplicate of order {0}")]
+    Duplicate(OrderId),
+
+    /// The order refers to a user or product that no longer exists (e.g. it was deleted).
+    #[error("Order {order_id} refers to missing {reference}")]
+    DanglingReference {
+        order_id: OrderId,
+        reference: String,
+    },
+
+    /// A service the Order actor depends on (`"user"` or `"product"`) has stopped, so the
+    /// order could not be validated or its stock reserved.
+    #[error("Dependency unavailable: {service} service is down")]
+    DependencyUnavailable { service: &'static str },
+
+    /// The order data provided is invalid, listing each invalid field.
     #[error("Order validation error: {0}")]
-    ValidationError(String),
+    ValidationError(#[from] ValidationError),
 
     /// Error from User service (entity-level)
     #[error("User service error: {0}")]
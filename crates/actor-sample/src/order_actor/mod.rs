@@ -12,6 +12,9 @@
 //!
 //! - [`entity`] - [`ActorEntity`](actor_framework::ActorEntity) implementation for [`Order`]
 //! - [`error`] - [`OrderError`] type with automatic error conversion from dependencies
+//! - [`actions`] - [`OrderAction`], which prices an order once its stock is reserved
+//! - [`stock`] - [`StockReserver`], the Product capability `OrderClient` reserves stock with
+//! - [`dedup`] - [`RecentOrders`], which detects resubmitted orders
 //! - [`new()`] - Factory function that creates the actor and client
 //!
 //! ## Message Flow: create_order
@@ -27,17 +30,22 @@
 //! - All communication is asynchronous via message passing
 //! - Each actor processes messages sequentially (no locks needed)
 //! - Validation happens in `Order::on_create()` before the order is stored
-//! - If any step fails, the entire operation fails atomically
+//! - Stock is reserved by `OrderClient::create_order` once the order exists; if that fails the
+//!   order is deleted again
 //!
 //! ## Context Dependencies
 //!
-//! The Order actor requires User and Product clients in its context:
+//! The Order actor requires a User client and the [`RecentOrders`] used for duplicate
+//! detection in its context. Stock is reserved by the `OrderClient` through a
+//! [`StockReserver`], a trait object, so the real `ProductClient` can be swapped for a fake:
 //!
 //! ```rust
-//! use actor_sample::order_actor;
+//! use actor_sample::order_actor::{self, RecentOrders};
 //! use actor_framework::mock::MockClient;
-//! use actor_sample::clients::{UserClient, ProductClient};
+//! use actor_sample::clients::{OrderClient, UserClient, ProductClient};
 //! use actor_sample::model::{User, Product};
+//! use actor_sample::user_actor::EmailIndex;
+//! use std::sync::Arc;
 //!
 //! #[tokio::main]
 //! async fn main() {
@@ -45,26 +53,36 @@
 //!     let user_mock = MockClient::<User>::new();
 //!     let product_mock = MockClient::<Product>::new();
 //!     
-//!     let user_client = UserClient::new(user_mock.client());
+//!     let user_client = UserClient::new(user_mock.client(), EmailIndex::default());
 //!     let product_client = ProductClient::new(product_mock.client());
 //!
 //!     // Create actor and client
 //!     let (actor, client) = order_actor::new();
 //!
+//!     let client = OrderClient::new(
+//!         client,
+//!         user_client.clone(),
+//!         product_client.clone(),
+//!         Arc::new(product_client),
+//!     );
+//!
 //!     // Start with dependencies injected
-//!     tokio::spawn(actor.run((user_client, product_client)));
+//!     tokio::spawn(actor.run((user_client, RecentOrders::disabled())));
 //! }
 //! ```
 //!
 //! ## Lifecycle Hooks
 //!
-//! The Order actor uses the `on_create` hook to perform validation and coordination:
+//! The Order actor uses the `on_create` hook to perform validation:
 //!
+//! 0. **Reject duplicates** - An identical order created within the [`RecentOrders`] window
+//!    is answered with `OrderError::Duplicate`
 //! 1. **Validate user exists** - Queries User actor
-//! 2. **Reserve product stock** - Calls Product actor's `reserve_stock` action
-//! 3. **Create order** - Only if validation succeeds
+//! 2. **Create order** - Only if validation succeeds
 //!
-//! This ensures orders are always valid and inventory is properly reserved.
+//! `OrderClient::create_order` then reserves the stock and prices the order with
+//! [`OrderAction::SetUnitPrices`], releasing the stock and deleting the order if a later step
+//! fails.
 //!
 //! ## Error Handling
 //!
@@ -89,20 +107,29 @@
 //!
 //! ## Key Features
 //!
-//! - **Context injection**: Depends on `(UserClient, ProductClient)`
-//! - **Cross-actor coordination**: Validates and reserves across multiple actors
+//! - **Context injection**: Depends on `(UserClient, RecentOrders)`
+//! - **Cross-actor coordination**: Validates the user before the order is stored
 //! - **Automatic error conversion**: Uses `#[from]` for clean error handling
 //! - **Lifecycle hooks**: Uses `on_create` for validation logic
 
+pub mod actions;
+pub mod dedup;
 pub mod entity;
 pub mod error;
+pub mod stock;
 
+pub use actions::*;
+pub use dedup::RecentOrders;
 pub use error::*;
+pub use stock::StockReserver;
 
 use crate::model::Order;
 use actor_framework::{ResourceActor, ResourceClient};
 
+/// Channel capacity used by [`new()`].
+pub const DEFAULT_BUFFER_SIZE: usize = 32;
+
 /// Creates a new Order actor and its client.
 pub fn new() -> (ResourceActor<Order>, ResourceClient<Order>) {
-    ResourceActor::new(32)
+    ResourceActor::new(DEFAULT_BUFFER_SIZE)
 }
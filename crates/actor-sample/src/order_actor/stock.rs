@@ -0,0 +1,58 @@
+//! The Order client's view of the Product service.
+//!
+//! `OrderClient::create_order` only needs to reserve stock, so it holds a [`StockReserver`]
+//! trait object rather than using the concrete `ProductClient`. Production wiring passes a
+//! `ProductClient`; tests can pass any fake that implements the trait.
+
+use crate::clients::ProductClient;
+use crate::model::{Money, OrderId, ProductId};
+use crate::product_actor::ProductError;
+use actor_framework::ActorClient;
+use async_trait::async_trait;
+
+/// Reserves product stock on behalf of an order.
+#[async_trait]
+pub trait StockReserver: std::fmt::Debug + Send + Sync {
+    /// Reserves `quantity` units of each `(product, quantity)` line for order `order_id`, all
+    /// or nothing, and returns each line's unit price at the time of the reservation.
+    ///
+    /// Fails with `ProductError::InsufficientStock` naming the first product without enough
+    /// units available, in which case nothing is reserved.
+    async fn reserve(
+        &self,
+        order_id: OrderId,
+        lines: Vec<(ProductId, u32)>,
+    ) -> Result<Vec<Money>, ProductError>;
+
+    /// Whether the Product service behind this reserver is still running.
+    ///
+    /// Checked after `reserve` fails, to report a stopped service as
+    /// `OrderError::DependencyUnavailable` instead of a generic communication error. The
+    /// default, for fakes without a service behind them, is always `true`.
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+#[async_trait]
+impl StockReserver for ProductClient {
+    async fn reserve(
+        &self,
+        order_id: OrderId,
+        mut lines: Vec<(ProductId, u32)>,
+    ) -> Result<Vec<Money>, ProductError> {
+        // A single line needs no atomic batch
+        match lines.len() {
+            1 => {
+                let (id, quantity) = lines.remove(0);
+                let unit_price = self.reserve_stock(id, order_id, quantity).await?;
+                Ok(vec![unit_price])
+            }
+            _ => self.reserve_batch(order_id, lines).await,
+        }
+    }
+
+    fn is_available(&self) -> bool {
+        !self.inner().is_closed()
+    }
+}
@@ -1,11 +1,13 @@
 //! Custom actions for the Product actor.
 //!
 //! This module defines the resource-specific operations (Actions) that can be performed
-//! on a [`Product`](crate::model::Product) entity, such as checking stock or reserving items.
+//! on a [`Product`] entity, such as checking stock or reserving items.
 //! These actions are handled by the [`ActorEntity::handle_action`](actor_framework::ActorEntity::handle_action) method.
 //!
 //! See [`impl ActorEntity for Product`](crate::model::Product#impl-ActorEntity-for-Product) for the implementation details.
 
+use crate::model::{Money, OrderId, Product};
+
 /// Custom actions for Product entities.
 ///
 /// These actions represent resource-specific operations that can be performed
@@ -15,14 +17,41 @@ pub enum ProductAction {
     /// Checks the current stock level without modifying it.
     #[allow(dead_code)]
     CheckStock,
-    /// Reserves a specified amount of stock.
+    /// Reserves a specified amount of stock for an order, recording it in the ledger.
     ///
     /// # Arguments
+    /// * `OrderId` - The reserving order
     /// * `u32` - The quantity to reserve
     ///
     /// # Errors
     /// Will fail if the requested amount exceeds available stock.
-    ReserveStock(u32),
+    ReserveStock(OrderId, u32),
+    /// Reserves as much of a quantity as is in stock for an order, e.g. to fulfil an order
+    /// partially and backorder the rest. Never fails for lack of stock.
+    ///
+    /// # Arguments
+    /// * `OrderId` - The reserving order
+    /// * `u32` - The quantity wanted
+    ReserveUpTo(OrderId, u32),
+    /// Returns an order's whole reservation to stock and removes it from the ledger.
+    ///
+    /// # Arguments
+    /// * `OrderId` - The order whose reservation is released
+    ///
+    /// # Errors
+    /// Will fail with `UnknownReservation` if the order holds no reservation, so an erroneous
+    /// release cannot inflate the stock.
+    ReleaseStock(OrderId),
+    /// Returns the reservation ledger.
+    ListReservations,
+    /// Changes the price, the auditable alternative to a generic update.
+    ///
+    /// # Arguments
+    /// * `Money` - The new price
+    ///
+    /// # Errors
+    /// Will fail with `InvalidPrice` unless the price is positive.
+    SetPrice(Money),
 }
 
 /// Results from ProductActions - variants match 1:1 with ProductAction
@@ -30,6 +59,34 @@ pub enum ProductAction {
 pub enum ProductActionResult {
     /// Result from CheckStock action - returns the current stock level
     CheckStock(u32),
-    /// Result from ReserveStock action - returns unit on success
-    ReserveStock(()),
+    /// Result from ReserveStock action - returns the unit price at the time of the reservation
+    ReserveStock(Money),
+    /// Result from ReserveUpTo action - returns how much was reserved, possibly 0
+    ReserveUpTo { reserved: u32 },
+    /// Result from ReleaseStock action - returns the quantity returned to stock
+    ReleaseStock(u32),
+    /// Result from ListReservations action - returns `(order, quantity)` per reservation
+    ListReservations(Vec<(OrderId, u32)>),
+    /// Result from SetPrice action - returns the previous and the new price
+    SetPrice { old: Money, new: Money },
+}
+
+// Typed forms of the `ProductAction` variants, for `ResourceClient::typed_action`. Each
+// converts into its variant and takes its output out of the matching `ProductActionResult`
+// variant.
+actor_framework::typed_actions! {
+    Product, ProductAction, ProductActionResult;
+    /// [`ProductAction::CheckStock`], returning the stock level.
+    CheckStock => ProductActionResult::CheckStock(level) => u32 = level;
+    /// [`ProductAction::ReserveStock`] of a quantity for an order, returning the unit price.
+    ReserveStock(order_id: OrderId, quantity: u32) => ProductActionResult::ReserveStock(price) => Money = price;
+    /// [`ProductAction::ReserveUpTo`], returning the quantity reserved.
+    ReserveUpTo(order_id: OrderId, quantity: u32) => ProductActionResult::ReserveUpTo { reserved } => u32 = reserved;
+    /// [`ProductAction::ReleaseStock`], returning the quantity released.
+    ReleaseStock(order_id: OrderId) => ProductActionResult::ReleaseStock(quantity) => u32 = quantity;
+    /// [`ProductAction::ListReservations`], returning `(order, quantity)` pairs.
+    ListReservations
+        => ProductActionResult::ListReservations(reservations) => Vec<(OrderId, u32)> = reservations;
+    /// [`ProductAction::SetPrice`], returning `(old_price, new_price)`.
+    SetPrice(price: Money) => ProductActionResult::SetPrice { old, new } => (Money, Money) = (old, new);
 }
@@ -7,10 +7,13 @@
 //!
 //! See the trait implementation on [`Product`] for method documentation.
 
-use crate::model::{Product, ProductCreate, ProductId, ProductUpdate};
+use crate::model::{
+    Money, OrderId, Product, ProductCreate, ProductId, ProductSummary, ProductUpdate, Validate,
+};
 use crate::product_actor::{ProductAction, ProductActionResult, ProductError};
-use actor_framework::ActorEntity;
+use actor_framework::{ActorEntity, Project};
 use async_trait::async_trait;
+use tracing::info;
 
 /// Marker constant to ensure module documentation is rendered.
 #[doc(hidden)]
@@ -28,37 +31,77 @@ impl ActorEntity for Product {
     type Context = ();
     type Error = ProductError;
 
-    // fn id(&self) -> &String { &self.id }
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+
+    fn entity_name() -> &'static str {
+        "Product"
+    }
 
-    /// Creates a new Product from creation parameters.
+    /// Creates a new Product from creation parameters, failing with `ValidationError` for an
+    /// empty name or a price that is not positive.
     fn from_create_params(id: ProductId, params: ProductCreate) -> Result<Self, Self::Error> {
-        Ok(Product::new(id, params.name, params.price, params.quantity))
+        params.validate()?;
+        Ok((id, params).into())
+    }
+
+    /// Rejects a price that is not positive, e.g. one set through a JSON patch.
+    fn validate(&self) -> Result<(), Self::Error> {
+        if self.price <= Money::ZERO {
+            return Err(ProductError::InvalidPrice(self.price));
+        }
+        Ok(())
     }
 
     /// Handles updates to the Product entity.
     ///
+    /// Fails with `ValidationError`, changing nothing, if the update sets a price that is not
+    /// positive.
+    ///
     /// # Fields Updated
     /// - `price`: Product price
-    /// - `quantity`: Available stock quantity
+    /// - `quantity`: Available stock quantity (absolute)
+    /// - `quantity_delta`: Relative stock adjustment; fails with `InsufficientStock` if it
+    ///   would take the quantity below zero
     async fn on_update(
         &mut self,
         update: ProductUpdate,
         _ctx: &Self::Context,
-    ) -> Result<(), Self::Error> {
+    ) -> Result<bool, Self::Error> {
+        update.validate()?;
         if let Some(price) = update.price {
             self.price = price;
         }
         if let Some(quantity) = update.quantity {
             self.quantity = quantity;
         }
-        Ok(())
+        if let Some(delta) = update.quantity_delta {
+            let adjusted = i64::from(self.quantity).saturating_add(delta);
+            self.quantity = u32::try_from(adjusted).map_err(|_| {
+                if adjusted < 0 {
+                    ProductError::InsufficientStock {
+                        product_id: self.id.clone(),
+                        requested: u32::try_from(delta.unsigned_abs()).unwrap_or(u32::MAX),
+                        available: self.quantity,
+                    }
+                } else {
+                    ProductError::InvalidQuantity(u32::MAX)
+                }
+            })?;
+        }
+        Ok(true)
     }
 
     /// Handles custom actions for the Product entity.
     ///
     /// # Actions
     /// - `CheckStock`: Returns true if requested quantity is available
-    /// - `ReserveStock`: Decrements stock if available, returns true on success
+    /// - `ReserveStock`: Decrements stock if available and records it for the order
+    /// - `ReserveUpTo`: Reserves as much as is available (possibly nothing) for the order
+    /// - `ReleaseStock`: Returns an order's recorded reservation to stock
+    /// - `ListReservations`: Returns the reservation ledger
+    /// - `SetPrice`: Replaces a valid price and logs the change for auditing
     async fn handle_action(
         &mut self,
         action: ProductAction,
@@ -66,17 +109,69 @@ impl ActorEntity for Product {
     ) -> Result<ProductActionResult, Self::Error> {
         match action {
             ProductAction::CheckStock => Ok(ProductActionResult::CheckStock(self.quantity)),
-            ProductAction::ReserveStock(quantity) => {
+            ProductAction::ReserveStock(order_id, quantity) => {
                 if self.quantity >= quantity {
-                    self.quantity -= quantity;
-                    Ok(ProductActionResult::ReserveStock(()))
+                    self.reserve(order_id, quantity);
+                    Ok(ProductActionResult::ReserveStock(self.price))
                 } else {
                     Err(ProductError::InsufficientStock {
+                        product_id: self.id.clone(),
                         requested: quantity,
                         available: self.quantity,
                     })
                 }
             }
+            ProductAction::ReserveUpTo(order_id, quantity) => {
+                let reserved = quantity.min(self.quantity);
+                if reserved > 0 {
+                    self.reserve(order_id, reserved);
+                }
+                Ok(ProductActionResult::ReserveUpTo { reserved })
+            }
+            ProductAction::ReleaseStock(order_id) => {
+                let position = self
+                    .reservations
+                    .iter()
+                    .position(|(id, _)| *id == order_id)
+                    .ok_or(ProductError::UnknownReservation(order_id))?;
+                let (_, quantity) = self.reservations.remove(position);
+                self.quantity += quantity;
+                Ok(ProductActionResult::ReleaseStock(quantity))
+            }
+            ProductAction::ListReservations => Ok(ProductActionResult::ListReservations(
+                self.reservations.clone(),
+            )),
+            ProductAction::SetPrice(new) => {
+                if new <= Money::ZERO {
+                    return Err(ProductError::InvalidPrice(new));
+                }
+                let old = std::mem::replace(&mut self.price, new);
+                info!(product_id = %self.id, %old, %new, "Price changed");
+                Ok(ProductActionResult::SetPrice { old, new })
+            }
+        }
+    }
+}
+
+impl Project for Product {
+    type Projection = ProductSummary;
+
+    fn project(&self) -> ProductSummary {
+        ProductSummary {
+            id: self.id.clone(),
+            price: self.price,
+            quantity: self.quantity,
+        }
+    }
+}
+
+impl Product {
+    /// Takes `quantity` out of stock and adds it to the order's ledger entry.
+    fn reserve(&mut self, order_id: OrderId, quantity: u32) {
+        self.quantity -= quantity;
+        match self.reservations.iter_mut().find(|(id, _)| *id == order_id) {
+            Some((_, reserved)) => *reserved += quantity,
+            None => self.reservations.push((order_id, quantity)),
         }
     }
 }
@@ -1,5 +1,6 @@
 //! Error types for the Product actor.
 
+use crate::model::{Money, OrderId, ProductId, ValidationError};
 use thiserror::Error;
 
 /// Errors that can occur during product operations.
@@ -13,17 +14,31 @@ pub enum ProductError {
     #[error("Product not found: {0}")]
     NotFound(String),
 
-    /// The requested quantity exceeds the available stock.
-    #[error("Insufficient stock: requested {requested}, available {available}")]
-    InsufficientStock { requested: u32, available: u32 },
+    /// The requested quantity exceeds the available stock of a product.
+    ///
+    /// Names the product, so a failed reservation of several products says which was short.
+    #[error("Insufficient stock of {product_id}: requested {requested}, available {available}")]
+    InsufficientStock {
+        product_id: ProductId,
+        requested: u32,
+        available: u32,
+    },
+
+    /// A release named an order that holds no reservation.
+    #[error("No stock reserved for order {0}")]
+    UnknownReservation(OrderId),
 
     /// The provided quantity is invalid (e.g., zero or negative).
     #[error("Invalid quantity: {0}")]
     InvalidQuantity(u32),
 
+    /// The product data provided is invalid, listing each invalid field.
+    #[error("Product validation error: {0}")]
+    ValidationError(#[from] ValidationError),
+
     /// Invalid price value (entity-level validation)
     #[error("Invalid price: {0}")]
-    InvalidPrice(f64),
+    InvalidPrice(Money),
 
     /// An underlying database error occurred.
     #[error("Product database error: {0}")]
@@ -22,8 +22,14 @@
 //! // Check current stock level (read-only)
 //! let stock = product_client.check_stock(product_id).await?;
 //!
-//! // Reserve stock for an order (mutating, can fail)
-//! product_client.reserve_stock(product_id, quantity).await?;
+//! // Reserve stock for an order (mutating, can fail); the ledger records the order
+//! product_client.reserve_stock(product_id, order_id, quantity).await?;
+//!
+//! // Or reserve what is in stock and backorder the rest
+//! let reserved = product_client.reserve_up_to(product_id, order_id, quantity).await?;
+//!
+//! // Return that order's reservation, e.g. on cancellation
+//! product_client.release_stock(product_id, order_id).await?;
 //! ```
 //!
 //! ## Usage
@@ -31,7 +37,7 @@
 //! ```rust
 //! use actor_sample::product_actor;
 //! use actor_sample::clients::ProductClient;
-//! use actor_sample::model::ProductCreate;
+//! use actor_sample::model::{Money, OrderId, ProductCreate};
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -45,13 +51,13 @@
 //!     // Create a product
 //!     let params = ProductCreate {
 //!         name: "Widget".to_string(),
-//!         price: 29.99,
+//!         price: Money::from_dollars(29.99),
 //!         quantity: 100,
 //!     };
 //!     let id = client.create_product(params).await?;
 //!
-//!     // Reserve stock
-//!     client.reserve_stock(id, 5).await?;
+//!     // Reserve stock for order 1
+//!     client.reserve_stock(id, OrderId(1), 5).await?;
 //!     Ok(())
 //! }
 //! ```
@@ -72,7 +78,10 @@ pub use error::*;
 use crate::model::Product;
 use actor_framework::{ResourceActor, ResourceClient};
 
+/// Channel capacity used by [`new()`].
+pub const DEFAULT_BUFFER_SIZE: usize = 32;
+
 /// Creates a new Product actor and its client.
 pub fn new() -> (ResourceActor<Product>, ResourceClient<Product>) {
-    ResourceActor::new(32)
+    ResourceActor::new(DEFAULT_BUFFER_SIZE)
 }
@@ -0,0 +1,67 @@
+//! Email → user id index.
+//!
+//! Looking a user up by email (e.g. at login) would otherwise scan every user. The User
+//! actor's hooks keep an [`EmailIndex`] in step with the store: `on_create` claims the new
+//! user's email, an update, `Replace` or JSON patch that changes it moves the claim, and
+//! `on_delete` releases it. A claim on an email held by another user fails with
+//! `UserError::EmailTaken`, so the index also keeps emails unique.
+//!
+//! The actor runs the hooks one at a time, so two concurrent creates with the same email
+//! cannot both claim it. Emails are compared case-insensitively.
+//!
+//! `on_start` rebuilds the index from the store whenever the actor's loop starts, so a
+//! supervised actor restarted with an empty store also starts with an empty index.
+
+use crate::model::{User, UserId};
+use crate::user_actor::UserError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// The User actor's email index, shared with [`UserClient`](crate::clients::UserClient) for
+/// lookups. Clones share the same index.
+#[derive(Clone, Debug, Default)]
+pub struct EmailIndex {
+    ids: Arc<Mutex<HashMap<String, UserId>>>,
+}
+
+impl EmailIndex {
+    /// Returns the id of the user with this email.
+    pub fn get(&self, email: &str) -> Option<UserId> {
+        self.ids.lock().unwrap().get(&key(email)).cloned()
+    }
+
+    /// Records that `id` holds `email`, failing with `EmailTaken` if another user does.
+    pub(crate) fn claim(&self, email: &str, id: &UserId) -> Result<(), UserError> {
+        let mut ids = self.ids.lock().unwrap();
+        match ids.get(&key(email)) {
+            Some(holder) if holder != id => Err(UserError::EmailTaken(email.to_string())),
+            _ => {
+                ids.insert(key(email), id.clone());
+                Ok(())
+            }
+        }
+    }
+
+    /// Replaces the whole index with the emails of `users`.
+    pub(crate) fn rebuild<'a>(&self, users: impl IntoIterator<Item = &'a User>) {
+        let mut ids = self.ids.lock().unwrap();
+        ids.clear();
+        ids.extend(
+            users
+                .into_iter()
+                .map(|user| (key(&user.email), user.id.clone())),
+        );
+    }
+
+    /// Frees `email` if `id` holds it.
+    pub(crate) fn release(&self, email: &str, id: &UserId) {
+        let mut ids = self.ids.lock().unwrap();
+        if ids.get(&key(email)) == Some(id) {
+            ids.remove(&key(email));
+        }
+    }
+}
+
+fn key(email: &str) -> String {
+    email.to_lowercase()
+}
@@ -5,10 +5,13 @@
 //!
 //! See the trait implementation on [`User`] for method documentation.
 
-use crate::model::{User, UserCreate, UserId, UserUpdate};
-use crate::user_actor::UserError;
+use crate::model::user::check_fields;
+use crate::model::{User, UserCreate, UserId, UserUpdate, Validate};
+use crate::user_actor::{EmailIndex, UserError};
 use actor_framework::ActorEntity;
 use async_trait::async_trait;
+use std::collections::HashMap;
+use tracing::info;
 
 #[derive(Debug)]
 pub enum UserAction {
@@ -29,18 +32,32 @@ impl ActorEntity for User {
     type Update = UserUpdate;
     type Action = UserAction;
     type ActionResult = ();
-    type Context = ();
+    type Context = EmailIndex;
     type Error = UserError;
 
-    // fn id(&self) -> &String { &self.id }
+    fn id(&self) -> &Self::Id {
+        &self.id
+    }
+
+    fn entity_name() -> &'static str {
+        "User"
+    }
 
-    /// Creates a new User from creation parameters.
+    /// Creates a new User from creation parameters, failing with `ValidationError` for an
+    /// empty name or a malformed email.
     fn from_create_params(id: UserId, params: UserCreate) -> Result<Self, Self::Error> {
-        Ok(User {
-            id,
-            name: params.name,
-            email: params.email,
-        })
+        params.validate()?;
+        Ok((id, params).into())
+    }
+
+    /// Rejects an empty name or a malformed email, e.g. in a user passed to `Replace`.
+    fn validate(&self) -> Result<(), Self::Error> {
+        Ok(check_fields(Some(&self.name), Some(&self.email))?)
+    }
+
+    /// Claims the new user's email, failing with `EmailTaken` if another user has it.
+    async fn on_create(&mut self, emails: &Self::Context) -> Result<(), Self::Error> {
+        emails.claim(&self.email, &self.id)
     }
 
     /// Handles updates to the User entity.
@@ -48,17 +65,67 @@ impl ActorEntity for User {
     /// # Fields Updated
     /// - `name`: User's display name
     /// - `email`: User's email address
+    ///
+    /// Setting a field to its current value is not a change. Fails with `ValidationError`,
+    /// changing nothing, for an empty name or a malformed email.
     async fn on_update(
         &mut self,
         update: UserUpdate,
         _ctx: &Self::Context,
-    ) -> Result<(), Self::Error> {
+    ) -> Result<bool, Self::Error> {
+        update.validate()?;
+        let mut changed = false;
         if let Some(name) = update.name {
+            changed |= self.name != name;
             self.name = name;
         }
         if let Some(email) = update.email {
+            changed |= self.email != email;
             self.email = email;
         }
+        Ok(changed)
+    }
+
+    /// Applies the update, moves the email's index entry and records email changes for
+    /// auditing. Fails with `EmailTaken` (leaving the user unchanged) if another user has the
+    /// new email.
+    async fn on_update_with_prev(
+        &mut self,
+        update: UserUpdate,
+        prev: &Self,
+        emails: &Self::Context,
+    ) -> Result<bool, Self::Error> {
+        let changed = self.on_update(update, emails).await?;
+        if self.email != prev.email {
+            emails.claim(&self.email, &self.id)?;
+            emails.release(&prev.email, &self.id);
+            info!(user_id = %self.id, from = %prev.email, to = %self.email, "Email changed");
+        }
+        Ok(changed)
+    }
+
+    /// Moves the email's index entry when a `Replace` or JSON patch changes it. Fails with
+    /// `EmailTaken` (keeping the previous user) if another user has the new email.
+    async fn on_replace(&mut self, prev: &Self, emails: &Self::Context) -> Result<(), Self::Error> {
+        if self.email != prev.email {
+            emails.claim(&self.email, &self.id)?;
+            emails.release(&prev.email, &self.id);
+        }
+        Ok(())
+    }
+
+    /// Frees the user's email for reuse.
+    async fn on_delete(&self, emails: &Self::Context) -> Result<(), Self::Error> {
+        emails.release(&self.email, &self.id);
+        Ok(())
+    }
+
+    /// Rebuilds the email index from the users the actor starts with (none after a restart).
+    async fn on_start(
+        store: &HashMap<UserId, Self>,
+        emails: &Self::Context,
+    ) -> Result<(), Self::Error> {
+        emails.rebuild(store.values());
         Ok(())
     }
 
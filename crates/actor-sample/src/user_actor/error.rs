@@ -1,5 +1,6 @@
 //! Error types for the User actor.
 
+use crate::model::ValidationError;
 use thiserror::Error;
 
 /// Errors that can occur during user operations.
@@ -17,9 +18,13 @@ pub enum UserError {
     #[error("User already exists: {0}")]
     AlreadyExists(String),
 
-    /// The user data provided is invalid.
+    /// The user data provided is invalid, listing each invalid field.
     #[error("User validation error: {0}")]
-    ValidationError(String),
+    ValidationError(#[from] ValidationError),
+
+    /// Another user already has this email (compared case-insensitively).
+    #[error("Email already taken: {0}")]
+    EmailTaken(String),
 
     /// Invalid email format (entity-level validation)
     #[error("Invalid email format: {0}")]
@@ -11,12 +11,13 @@
 //!
 //! - [`entity`] - [`ActorEntity`](actor_framework::ActorEntity) implementation for [`User`]
 //! - [`error`] - [`UserError`] type for type-safe error handling
+//! - [`email_index`] - [`EmailIndex`], the email lookup index kept by the actor's hooks
 //! - [`new()`] - Factory function that creates the actor and client
 //!
 //! ## Usage
 //!
 //! ```rust
-//! use actor_sample::user_actor;
+//! use actor_sample::user_actor::{self, EmailIndex};
 //! use actor_sample::clients::UserClient;
 //! use actor_sample::model::UserCreate;
 //!
@@ -24,10 +25,11 @@
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //!     // Create actor and client
 //!     let (actor, generic_client) = user_actor::new();
-//!     let client = UserClient::new(generic_client);
+//!     let emails = EmailIndex::default();
+//!     let client = UserClient::new(generic_client, emails.clone());
 //!
-//!     // Start the actor (no dependencies, so context is ())
-//!     tokio::spawn(actor.run(()));
+//!     // Start the actor; its context is the email index its hooks maintain
+//!     tokio::spawn(actor.run(emails));
 //!
 //!     // Use the client
 //!     let params = UserCreate {
@@ -41,20 +43,25 @@
 //!
 //! ## Key Features
 //!
-//! - **No dependencies**: User actor has no context dependencies (Context = ())
+//! - **No dependencies**: User actor depends on no other actor (Context = EmailIndex)
 //! - **Sequential ID generation**: Uses atomic counter for deterministic IDs
 //! - **Type-safe errors**: All operations return `Result<T, UserError>`
 
+pub mod email_index;
 pub mod entity;
 pub mod error;
 
+pub use email_index::EmailIndex;
 pub use error::*;
 
 use crate::model::User;
 use actor_framework::ResourceActor;
 use actor_framework::ResourceClient;
 
+/// Channel capacity used by [`new()`].
+pub const DEFAULT_BUFFER_SIZE: usize = 10;
+
 /// Creates a new User actor and its client.
 pub fn new() -> (ResourceActor<User>, ResourceClient<User>) {
-    ResourceActor::new(10)
+    ResourceActor::new(DEFAULT_BUFFER_SIZE)
 }
@@ -0,0 +1,149 @@
+use actor_sample::gateway;
+use actor_sample::lifecycle::OrderSystem;
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tower::ServiceExt;
+
+/// Sends one request to the gateway and returns the status and JSON body (`Null` if empty).
+async fn call(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json");
+    let request = match body {
+        Some(body) => request.body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    }
+    .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body = if bytes.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&bytes).unwrap()
+    };
+    (status, body)
+}
+
+#[tokio::test]
+async fn test_gateway_creates_and_reads_every_resource() {
+    let system = OrderSystem::new_for_test();
+    let app = gateway::router(&system);
+
+    let (status, user) = call(
+        &app,
+        "POST",
+        "/users",
+        Some(json!({ "name": "Alice", "email": "alice@example.com" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(user["name"], "Alice");
+
+    let (status, product) = call(
+        &app,
+        "POST",
+        "/products",
+        Some(json!({ "name": "Lamp", "price": 4000, "quantity": 5 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, order) = call(
+        &app,
+        "POST",
+        "/orders",
+        Some(json!({
+            "user_id": user["id"],
+            "items": [{ "product_id": product["id"], "quantity": 2, "unit_price": 4000 }],
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(order["total"], 8000);
+
+    let (status, fetched) = call(&app, "GET", "/users/1", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fetched, user);
+    let (status, orders) = call(&app, "GET", "/orders", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(orders, json!([order]));
+
+    drop(app);
+    system.shutdown().await.unwrap();
+}
+
+/// `POST /orders` places the order through `OrderClient::create_order`: it reserves stock,
+/// and a resubmitted order answers with the order it duplicates.
+#[tokio::test]
+async fn test_gateway_order_reserves_stock_once() {
+    let system = OrderSystem::builder()
+        .order_dedup_window(Duration::from_secs(60))
+        .build();
+    let app = gateway::router(&system);
+
+    let (_, user) = call(
+        &app,
+        "POST",
+        "/users",
+        Some(json!({ "name": "Alice", "email": "alice@example.com" })),
+    )
+    .await;
+    let (_, product) = call(
+        &app,
+        "POST",
+        "/products",
+        Some(json!({ "name": "Lamp", "price": 4000, "quantity": 5 })),
+    )
+    .await;
+    let order = json!({
+        "user_id": user["id"],
+        "items": [{ "product_id": product["id"], "quantity": 2, "unit_price": 4000 }],
+    });
+
+    let (status, first) = call(&app, "POST", "/orders", Some(order.clone())).await;
+    assert_eq!(status, StatusCode::CREATED, "{first}");
+    let (status, second) = call(&app, "POST", "/orders", Some(order)).await;
+    assert_eq!(status, StatusCode::CREATED, "{second}");
+    assert_eq!(second["id"], first["id"]);
+
+    let (_, product) = call(&app, "GET", "/products/1", None).await;
+    assert_eq!(product["quantity"], 3);
+
+    drop(app);
+    system.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_gateway_maps_errors_to_status_codes() {
+    let system = OrderSystem::new_for_test();
+    let app = gateway::router(&system);
+
+    let (status, body) = call(&app, "GET", "/products/9", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "NOT_FOUND");
+
+    let (status, _) = call(&app, "DELETE", "/products/9", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // An entity error, here an order for a user that does not exist, is a rejected request
+    let (status, body) = call(
+        &app,
+        "POST",
+        "/orders",
+        Some(json!({
+            "user_id": 7,
+            "items": [{ "product_id": 1, "quantity": 1, "unit_price": 100 }],
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+
+    drop(app);
+    system.shutdown().await.unwrap();
+}
@@ -1,13 +1,16 @@
 use actor_framework::ActorClient;
 use actor_sample::lifecycle::OrderSystem;
-use actor_sample::model::{Order, OrderCreate, Product, ProductCreate, User, UserCreate};
+use actor_sample::model::{
+    Money, OrderCreate, OrderId, OrderLine, ProductCreate, ProductId, UserCreate, UserId,
+};
+use actor_sample::product_actor::ProductError;
 
 /// Full end-to-end integration test with all real actors.
 /// This tests the entire system working together.
 #[tokio::test]
 async fn test_full_order_system_integration() {
     // Create the full system with all real actors
-    let system = OrderSystem::new();
+    let system = OrderSystem::new_for_test();
 
     // Create a user
     let user_params = UserCreate {
@@ -19,6 +22,8 @@ async fn test_full_order_system_integration() {
         .create_user(user_params)
         .await
         .expect("Failed to create user");
+    // Test systems hand out sequential ids
+    assert_eq!(user_id, UserId(1));
 
     // Verify user was created
     let retrieved_user = system
@@ -33,7 +38,7 @@ async fn test_full_order_system_integration() {
     // Create a product with stock
     let product_params = ProductCreate {
         name: "Super Widget".to_string(),
-        price: 25.50,
+        price: Money::from_dollars(25.50),
         quantity: 100,
     };
     let product_id = system
@@ -53,15 +58,18 @@ async fn test_full_order_system_integration() {
     // Create an order (should reserve stock)
     let order_params = OrderCreate {
         user_id: user_id.clone(),
-        product_id: product_id.clone(),
-        quantity: 5,
-        total: 127.50,
+        items: vec![OrderLine::new(
+            product_id.clone(),
+            5,
+            Money::from_dollars(25.5),
+        )],
     };
     let order_id = system
         .order_client
         .create_order(order_params)
         .await
         .expect("Failed to create order");
+    assert_eq!(order_id, OrderId(1));
 
     // Verify order was created with correct details
     let retrieved_order = system
@@ -71,9 +79,10 @@ async fn test_full_order_system_integration() {
         .expect("Failed to get order")
         .expect("Order not found");
     assert_eq!(retrieved_order.user_id, user_id);
-    assert_eq!(retrieved_order.product_id, product_id);
-    assert_eq!(retrieved_order.quantity, 5);
-    assert_eq!(retrieved_order.total, 127.50);
+    assert_eq!(retrieved_order.items.len(), 1);
+    assert_eq!(retrieved_order.items[0].product_id, product_id);
+    assert_eq!(retrieved_order.items[0].quantity, 5);
+    assert_eq!(retrieved_order.total, Money::from_cents(12750));
 
     // Verify stock was decremented
     let final_stock = system
@@ -89,12 +98,21 @@ async fn test_full_order_system_integration() {
     // Test insufficient stock scenario
     let large_order_params = OrderCreate {
         user_id: user_id.clone(),
-        product_id: product_id.clone(),
-        quantity: 200,
-        total: 5100.0,
+        items: vec![OrderLine::new(
+            product_id.clone(),
+            200,
+            Money::from_dollars(25.5),
+        )],
     };
     let result = system.order_client.create_order(large_order_params).await;
-    assert!(result.is_err(), "Should fail when stock is insufficient");
+    assert!(
+        matches!(
+            result,
+            Err(actor_sample::order_actor::OrderError::OutOfStock { requested: 200, .. })
+        ),
+        "Should fail with OutOfStock when stock is insufficient: {:?}",
+        result
+    );
 
     // Verify stock wasn't changed after failed order
     let stock_after_failure = system
@@ -114,7 +132,7 @@ async fn test_full_order_system_integration() {
 /// Test concurrent order creation to verify actor isolation.
 #[tokio::test]
 async fn test_concurrent_orders() {
-    let system = OrderSystem::new();
+    let system = OrderSystem::new_for_test();
 
     // Create a user
     let user_params = UserCreate {
@@ -126,7 +144,7 @@ async fn test_concurrent_orders() {
     // Create a product with limited stock
     let product_params = ProductCreate {
         name: "Limited Widget".to_string(),
-        price: 10.0,
+        price: Money::from_dollars(10.0),
         quantity: 20,
     };
     let product_id = system
@@ -145,9 +163,7 @@ async fn test_concurrent_orders() {
         let handle = tokio::spawn(async move {
             let order_params = OrderCreate {
                 user_id: uid,
-                product_id: pid,
-                quantity: 2,
-                total: 20.0,
+                items: vec![OrderLine::new(pid, 2, Money::from_dollars(10.0))],
             };
             order_client.create_order(order_params).await
         });
@@ -175,3 +191,1133 @@ async fn test_concurrent_orders() {
 
     system.shutdown().await.unwrap();
 }
+
+/// The builder's id generators control the ids handed out by each actor.
+#[tokio::test]
+async fn test_order_system_builder_custom_ids() {
+    let mut next_user = 1000;
+    let system = OrderSystem::builder()
+        .user_ids(move || {
+            next_user += 1;
+            next_user
+        })
+        .product_ids(|| 7)
+        .user_buffer_size(4)
+        .build();
+
+    let user_id = system
+        .user_client
+        .create_user(UserCreate {
+            name: "Carol".to_string(),
+            email: "carol@example.com".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(user_id, UserId(1001));
+
+    let product_id = system
+        .product_client
+        .create_product(ProductCreate {
+            name: "Gadget".to_string(),
+            price: Money::from_dollars(1.0),
+            quantity: 1,
+        })
+        .await
+        .unwrap();
+    assert_eq!(product_id, ProductId(7));
+
+    system.shutdown().await.unwrap();
+}
+
+/// Client methods accept anything convertible into the entity id, e.g. a bare `u32`.
+#[tokio::test]
+async fn test_client_accepts_raw_ids() {
+    let system = OrderSystem::new_for_test();
+
+    let user_id = system
+        .user_client
+        .create_user(UserCreate {
+            name: "Dave".to_string(),
+            email: "dave@example.com".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let mut user = system
+        .user_client
+        .inner()
+        .get(user_id.0)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(user.name, "Dave");
+
+    user.name = "David".to_string();
+    let replaced = system.user_client.inner().replace(user_id.0, user).await;
+    assert_eq!(replaced.unwrap().name, "David");
+
+    system.user_client.inner().delete(user_id.0).await.unwrap();
+    assert!(system
+        .user_client
+        .inner()
+        .get(user_id)
+        .await
+        .unwrap()
+        .is_none());
+
+    system.shutdown().await.unwrap();
+}
+
+/// With the `testing` feature, `inspect` reads the raw stored entity without any hooks.
+#[tokio::test]
+async fn test_inspect_reads_stored_state() {
+    let system = OrderSystem::new_for_test();
+
+    let product_id = system
+        .product_client
+        .create_product(ProductCreate {
+            name: "Widget".to_string(),
+            price: Money::from_dollars(2.5),
+            quantity: 10,
+        })
+        .await
+        .unwrap();
+    system
+        .product_client
+        .reserve_stock(product_id.clone(), OrderId(1), 4)
+        .await
+        .unwrap();
+
+    let product = system
+        .product_client
+        .inner()
+        .inspect(product_id)
+        .await
+        .unwrap();
+    assert_eq!(product.quantity, 6);
+
+    let missing = system.product_client.inner().inspect(999u32).await;
+    assert!(matches!(
+        missing,
+        Err(actor_framework::FrameworkError::NotFound(_))
+    ));
+
+    system.shutdown().await.unwrap();
+}
+
+/// A JSON merge patch changes the named fields only and is rejected whole when the result is
+/// unreadable or invalid.
+#[tokio::test]
+async fn test_patch_product_with_json() {
+    let system = OrderSystem::new_for_test();
+    let product_id = system
+        .product_client
+        .create_product(ProductCreate {
+            name: "Kettle".to_string(),
+            price: Money::from_dollars(30.0),
+            quantity: 4,
+        })
+        .await
+        .unwrap();
+    let client = system.product_client.inner();
+
+    let patched = client
+        .patch_json(product_id.clone(), serde_json::json!({ "price": 2499 }))
+        .await
+        .unwrap();
+    assert_eq!(patched.price, Money::from_dollars(24.99));
+    assert_eq!((patched.name.as_str(), patched.quantity), ("Kettle", 4));
+
+    for invalid in [
+        serde_json::json!({ "quantity": "plenty" }),
+        serde_json::json!({ "price": -100 }),
+        serde_json::json!({ "price": 0 }),
+    ] {
+        let result = client.patch_json(product_id.clone(), invalid).await;
+        assert!(
+            matches!(
+                result,
+                Err(actor_framework::FrameworkError::EntityError { .. })
+            ),
+            "invalid patch must be rejected: {:?}",
+            result
+        );
+    }
+    let id_change = client
+        .patch_json(product_id.clone(), serde_json::json!({ "id": 999 }))
+        .await;
+    assert!(
+        matches!(
+            id_change,
+            Err(actor_framework::FrameworkError::Validation(_))
+        ),
+        "a patch must not change the id: {:?}",
+        id_change
+    );
+    let product = client.get(product_id).await.unwrap().unwrap();
+    assert_eq!(product.price, Money::from_dollars(24.99));
+    assert_eq!(product.quantity, 4);
+
+    let missing = client.patch_json(999u32, serde_json::json!({})).await;
+    assert!(matches!(
+        missing,
+        Err(actor_framework::FrameworkError::NotFound(_))
+    ));
+
+    system.shutdown().await.unwrap();
+}
+
+/// Create payloads are validated before the entity is built, reporting every invalid field.
+#[tokio::test]
+async fn test_create_rejects_invalid_payloads() {
+    use actor_sample::model::Validate;
+    use actor_sample::user_actor::UserError;
+
+    let system = OrderSystem::new_for_test();
+
+    let result = system
+        .product_client
+        .create_product(ProductCreate {
+            name: " ".to_string(),
+            price: Money::from_dollars(-1.0),
+            quantity: 1,
+        })
+        .await;
+    let Err(ProductError::ValidationError(error)) = result else {
+        panic!("invalid product must be rejected: {result:?}");
+    };
+    assert_eq!(error.message("name"), Some("must not be empty"));
+    assert_eq!(error.message("price"), Some("must be positive"));
+
+    let invalid_user = UserCreate {
+        name: "Mallory".to_string(),
+        email: "mallory.example.com".to_string(),
+    };
+    let error = invalid_user.validate().unwrap_err();
+    assert_eq!(error.to_string(), "email: must look like name@example.com");
+    assert!(matches!(
+        system.user_client.create_user(invalid_user).await,
+        Err(UserError::ValidationError(_))
+    ));
+    assert_eq!(system.user_client.inner().count().await.unwrap(), 0);
+    assert_eq!(system.product_client.inner().count().await.unwrap(), 0);
+
+    system.shutdown().await.unwrap();
+}
+
+/// Updates hold the fields they set to the create rules, and leave the entity unchanged when
+/// they break one.
+#[tokio::test]
+async fn test_update_rejects_invalid_fields() {
+    use actor_sample::clients::ProductClient;
+    use actor_sample::model::{ProductUpdate, UserUpdate};
+    use actor_sample::user_actor::UserError;
+
+    let system = OrderSystem::new_for_test();
+    let user_id = system
+        .user_client
+        .create_user(UserCreate {
+            name: "Erin".to_string(),
+            email: "erin@example.com".to_string(),
+        })
+        .await
+        .unwrap();
+    let product_id = system
+        .product_client
+        .create_product(ProductCreate {
+            name: "Kettle".to_string(),
+            price: Money::from_dollars(30.0),
+            quantity: 4,
+        })
+        .await
+        .unwrap();
+
+    let result = system
+        .user_client
+        .update_user(
+            user_id.clone(),
+            UserUpdate {
+                name: Some(" ".to_string()),
+                email: Some("erin.example.com".to_string()),
+            },
+        )
+        .await;
+    let Err(UserError::ValidationError(error)) = result else {
+        panic!("invalid update must be rejected: {result:?}");
+    };
+    assert_eq!(error.message("name"), Some("must not be empty"));
+    assert!(error.message("email").is_some());
+
+    let result = system
+        .product_client
+        .inner()
+        .update(
+            product_id.clone(),
+            ProductUpdate {
+                price: Some(Money::ZERO),
+                quantity: Some(9),
+                quantity_delta: None,
+            },
+        )
+        .await
+        .map_err(ProductClient::map_error);
+    assert!(matches!(result, Err(ProductError::ValidationError(_))));
+
+    let user = system.user_client.get(user_id).await.unwrap().unwrap();
+    assert_eq!(user.name, "Erin");
+    let product = system
+        .product_client
+        .get(product_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        (product.price, product.quantity),
+        (Money::from_dollars(30.0), 4)
+    );
+
+    system.shutdown().await.unwrap();
+}
+
+/// Relative stock adjustments compose with concurrent reservations instead of overwriting them.
+#[tokio::test]
+async fn test_adjust_stock_composes_with_reservations() {
+    let system = OrderSystem::new_for_test();
+
+    let product_id = system
+        .product_client
+        .create_product(ProductCreate {
+            name: "Bolt".to_string(),
+            price: Money::from_dollars(0.1),
+            quantity: 100,
+        })
+        .await
+        .unwrap();
+
+    let mut tasks = Vec::new();
+    for order in 0..10 {
+        let client = system.product_client.clone();
+        let id = product_id.clone();
+        tasks.push(tokio::spawn(async move {
+            client
+                .reserve_stock(id, OrderId(order), 3)
+                .await
+                .map(|_| ())
+        }));
+        let client = system.product_client.clone();
+        let id = product_id.clone();
+        tasks.push(tokio::spawn(async move {
+            client.adjust_stock(id, 5).await.map(|_| ())
+        }));
+    }
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+    // 100 - 10 * 3 + 10 * 5
+    let stock = system
+        .product_client
+        .check_stock(product_id.clone())
+        .await
+        .unwrap();
+    assert_eq!(stock, 120);
+
+    let result = system
+        .product_client
+        .adjust_stock(product_id.clone(), -500)
+        .await;
+    assert!(matches!(
+        result,
+        Err(
+            actor_sample::product_actor::ProductError::InsufficientStock {
+                requested: 500,
+                available: 120,
+                ..
+            }
+        )
+    ));
+    let stock = system.product_client.check_stock(product_id).await.unwrap();
+    assert_eq!(
+        stock, 120,
+        "a rejected delta must leave the stock untouched"
+    );
+
+    system.shutdown().await.unwrap();
+}
+
+/// Price changes go through the audited `SetPrice` action and are validated.
+#[tokio::test]
+async fn test_adjust_price_returns_old_and_new_price() {
+    let system = OrderSystem::new_for_test();
+
+    let product_id = system
+        .product_client
+        .create_product(ProductCreate {
+            name: "Lamp".to_string(),
+            price: Money::from_dollars(20.0),
+            quantity: 5,
+        })
+        .await
+        .unwrap();
+
+    let (old, new) = system
+        .product_client
+        .adjust_price(product_id.clone(), Money::from_dollars(25.0))
+        .await
+        .unwrap();
+    assert_eq!(
+        (old, new),
+        (Money::from_dollars(20.0), Money::from_dollars(25.0))
+    );
+
+    for invalid in [Money::ZERO, Money::from_dollars(-3.0)] {
+        let result = system
+            .product_client
+            .adjust_price(product_id.clone(), invalid)
+            .await;
+        assert!(matches!(
+            result,
+            Err(actor_sample::product_actor::ProductError::InvalidPrice(_))
+        ));
+    }
+    let product = system
+        .product_client
+        .get(product_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(product.price, Money::from_dollars(25.0));
+
+    system.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_system_stats_aggregates_every_actor() {
+    let system = OrderSystem::new_for_test();
+    system
+        .user_client
+        .create_user(UserCreate {
+            name: "Alice".to_string(),
+            email: "alice@example.com".to_string(),
+        })
+        .await
+        .unwrap();
+    // Unknown user: the Order actor's on_create fails
+    system
+        .order_client
+        .create_order(OrderCreate {
+            user_id: UserId(99),
+            items: vec![OrderLine::new(ProductId(1), 1, Money::from_dollars(10.0))],
+        })
+        .await
+        .unwrap_err();
+
+    let stats = system.system_stats().await;
+    assert!(stats.all_available());
+    assert_eq!(stats.order.as_ref().unwrap().errors, 1);
+    assert_eq!(stats.user.as_ref().unwrap().errors, 0);
+
+    let total = stats.total();
+    assert_eq!(total.errors, 1);
+    // user: create, get, stats; product: stats; order: create, stats
+    assert_eq!(total.messages, 6);
+
+    system.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_wait_ready_pings_every_actor() {
+    let system = OrderSystem::new_for_test();
+    system.wait_ready().await.unwrap();
+
+    let stats = system.system_stats().await;
+    assert!(stats.all_available());
+    // One ping and one stats query per actor
+    assert_eq!(stats.total().messages, 6);
+
+    system.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_order_reservation_is_recorded_and_released_once() {
+    let system = OrderSystem::new_for_test();
+    let user_id = system
+        .user_client
+        .create_user(UserCreate {
+            name: "Alice".to_string(),
+            email: "alice@example.com".to_string(),
+        })
+        .await
+        .unwrap();
+    let product_id = system
+        .product_client
+        .create_product(ProductCreate {
+            name: "Widget".to_string(),
+            price: Money::from_dollars(2.0),
+            quantity: 10,
+        })
+        .await
+        .unwrap();
+    let order_id = system
+        .order_client
+        .create_order(OrderCreate {
+            user_id,
+            items: vec![OrderLine::new(
+                product_id.clone(),
+                3,
+                Money::from_dollars(2.0),
+            )],
+        })
+        .await
+        .unwrap();
+
+    let reservations = system
+        .product_client
+        .list_reservations(product_id.clone())
+        .await
+        .unwrap();
+    assert_eq!(reservations, vec![(order_id.clone(), 3)]);
+
+    let released = system
+        .product_client
+        .release_stock(product_id.clone(), order_id.clone())
+        .await
+        .unwrap();
+    assert_eq!(released, 3);
+    assert_eq!(
+        system
+            .product_client
+            .check_stock(product_id.clone())
+            .await
+            .unwrap(),
+        10
+    );
+
+    // A second release must not inflate the stock
+    let result = system
+        .product_client
+        .release_stock(product_id.clone(), order_id)
+        .await;
+    assert!(matches!(result, Err(ProductError::UnknownReservation(_))));
+    assert_eq!(
+        system.product_client.check_stock(product_id).await.unwrap(),
+        10
+    );
+}
+
+#[tokio::test]
+async fn test_reserve_up_to_reserves_what_is_in_stock() {
+    let system = OrderSystem::new_for_test();
+    let product_id = system
+        .product_client
+        .create_product(ProductCreate {
+            name: "Widget".to_string(),
+            price: Money::from_dollars(2.0),
+            quantity: 7,
+        })
+        .await
+        .unwrap();
+
+    let reserved = system
+        .product_client
+        .reserve_up_to(product_id.clone(), OrderId(1), 10)
+        .await
+        .unwrap();
+    assert_eq!(reserved, 7);
+    let reserved = system
+        .product_client
+        .reserve_up_to(product_id.clone(), OrderId(2), 3)
+        .await
+        .unwrap();
+    assert_eq!(reserved, 0);
+
+    // Nothing reserved, nothing recorded
+    let reservations = system
+        .product_client
+        .list_reservations(product_id.clone())
+        .await
+        .unwrap();
+    assert_eq!(reservations, vec![(OrderId(1), 7)]);
+    assert_eq!(
+        system.product_client.check_stock(product_id).await.unwrap(),
+        0
+    );
+}
+
+/// `find` filters products inside the Product actor.
+#[tokio::test]
+async fn test_find_products_below_a_price() {
+    let system = OrderSystem::new_for_test();
+    for (name, price) in [("Pen", 250), ("Lamp", 2000), ("Mug", 800)] {
+        system
+            .product_client
+            .create_product(ProductCreate {
+                name: name.to_string(),
+                price: Money::from_cents(price),
+                quantity: 1,
+            })
+            .await
+            .unwrap();
+    }
+
+    let mut cheap: Vec<String> = system
+        .product_client
+        .find(|p| p.price < Money::from_dollars(10.0))
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|p| p.name)
+        .collect();
+    cheap.sort();
+    assert_eq!(cheap, ["Mug", "Pen"]);
+
+    system.shutdown().await.unwrap();
+}
+
+/// `get_summary` returns the product's projection rather than the whole entity.
+#[tokio::test]
+async fn test_get_product_summary() {
+    use actor_sample::model::ProductSummary;
+
+    let system = OrderSystem::new_for_test();
+    let product_id = system
+        .product_client
+        .create_product(ProductCreate {
+            name: "Desk".to_string(),
+            price: Money::from_dollars(150.0),
+            quantity: 4,
+        })
+        .await
+        .unwrap();
+
+    let summary = system
+        .product_client
+        .get_summary(product_id.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        summary,
+        Some(ProductSummary {
+            id: product_id,
+            price: Money::from_dollars(150.0),
+            quantity: 4,
+        })
+    );
+    assert_eq!(
+        system
+            .product_client
+            .get_summary(ProductId(999))
+            .await
+            .unwrap(),
+        None
+    );
+
+    system.shutdown().await.unwrap();
+}
+
+/// A cart checkout reserves every line or, if one is short, none of them.
+#[tokio::test]
+async fn test_reserve_batch_is_all_or_nothing() {
+    let system = OrderSystem::new_for_test();
+    let mut product_ids = Vec::new();
+    for (name, quantity) in [("Pen", 10), ("Ink", 2)] {
+        let id = system
+            .product_client
+            .create_product(ProductCreate {
+                name: name.to_string(),
+                price: Money::from_dollars(1.0),
+                quantity,
+            })
+            .await
+            .unwrap();
+        product_ids.push(id);
+    }
+    let (pen, ink) = (product_ids[0].clone(), product_ids[1].clone());
+
+    let short = system
+        .product_client
+        .reserve_batch(OrderId(1), vec![(pen.clone(), 4), (ink.clone(), 3)])
+        .await;
+    assert!(matches!(
+        short,
+        Err(ProductError::InsufficientStock {
+            requested: 3,
+            available: 2,
+            ..
+        })
+    ));
+    assert_eq!(
+        system
+            .product_client
+            .check_stock(pen.clone())
+            .await
+            .unwrap(),
+        10
+    );
+    assert!(system
+        .product_client
+        .list_reservations(pen.clone())
+        .await
+        .unwrap()
+        .is_empty());
+
+    system
+        .product_client
+        .reserve_batch(
+            OrderId(2),
+            vec![(pen.clone(), 4), (ink.clone(), 2), (pen.clone(), 1)],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        system
+            .product_client
+            .check_stock(pen.clone())
+            .await
+            .unwrap(),
+        5
+    );
+    assert_eq!(system.product_client.check_stock(ink).await.unwrap(), 0);
+    assert_eq!(
+        system.product_client.list_reservations(pen).await.unwrap(),
+        [(OrderId(2), 5)]
+    );
+
+    system.shutdown().await.unwrap();
+}
+
+/// While draining, the system refuses new orders but still answers reads.
+#[tokio::test]
+async fn test_begin_draining_rejects_new_orders() {
+    use actor_sample::order_actor::OrderError;
+
+    let system = OrderSystem::new_for_test();
+    let user_id = system
+        .user_client
+        .create_user(UserCreate {
+            name: "Dana".to_string(),
+            email: "dana@example.com".to_string(),
+        })
+        .await
+        .unwrap();
+
+    system.begin_draining().await.unwrap();
+
+    let order = system
+        .order_client
+        .create_order(OrderCreate {
+            user_id: user_id.clone(),
+            items: vec![OrderLine::new(ProductId(1), 1, Money::from_dollars(1.0))],
+        })
+        .await;
+    assert!(matches!(order, Err(OrderError::ActorCommunicationError(_))));
+    assert!(system.user_client.get(user_id).await.unwrap().is_some());
+
+    system.shutdown().await.unwrap();
+}
+
+/// Orders queued before the drain still reserve their stock: the Product actor only drains
+/// once the Order actor has handled it.
+#[tokio::test]
+async fn test_begin_draining_finishes_queued_orders() {
+    let (system, shutdown) = OrderSystem::new_shared();
+    let user_id = system
+        .user_client
+        .create_user(UserCreate {
+            name: "Dana".to_string(),
+            email: "dana@example.com".to_string(),
+        })
+        .await
+        .unwrap();
+    let product_id = system
+        .product_client
+        .create_product(ProductCreate {
+            name: "Lamp".to_string(),
+            price: Money::from_dollars(30.0),
+            quantity: 10,
+        })
+        .await
+        .unwrap();
+
+    // Different quantities, so no order is taken for a duplicate of another
+    let orders: Vec<_> = (1..=3)
+        .map(|quantity| {
+            let orders = system.order_client.clone();
+            let params = OrderCreate {
+                user_id: user_id.clone(),
+                items: vec![OrderLine::new(
+                    product_id.clone(),
+                    quantity,
+                    Money::from_dollars(30.0),
+                )],
+            };
+            tokio::spawn(async move { orders.create_order(params).await })
+        })
+        .collect();
+    // Spawned after the orders, so it runs once they are queued
+    let draining = tokio::spawn({
+        let system = system.clone();
+        async move { system.begin_draining().await }
+    });
+
+    for order in orders {
+        assert!(order.await.unwrap().is_ok());
+    }
+    draining.await.unwrap().unwrap();
+    let product = system
+        .product_client
+        .get(product_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(product.quantity, 4);
+
+    shutdown.shutdown().await.unwrap();
+}
+
+/// An order summary joins the order with its user and product, and reports a deleted
+/// product as a dangling reference.
+#[tokio::test]
+async fn test_order_summary_joins_user_and_product() {
+    use actor_sample::order_actor::OrderError;
+
+    let system = OrderSystem::new_for_test();
+    let user_id = system
+        .user_client
+        .create_user(UserCreate {
+            name: "Alice".to_string(),
+            email: "alice@example.com".to_string(),
+        })
+        .await
+        .unwrap();
+    let product_id = system
+        .product_client
+        .create_product(ProductCreate {
+            name: "Lamp".to_string(),
+            price: Money::from_dollars(40.0),
+            quantity: 5,
+        })
+        .await
+        .unwrap();
+    let order_id = system
+        .order_client
+        .create_order(OrderCreate {
+            user_id,
+            items: vec![OrderLine::new(
+                product_id.clone(),
+                2,
+                Money::from_dollars(40.0),
+            )],
+        })
+        .await
+        .unwrap();
+
+    let summary = system
+        .order_client
+        .order_summary(order_id.clone())
+        .await
+        .unwrap();
+    assert_eq!(summary.order.id, order_id);
+    assert_eq!(summary.user_name, "Alice");
+    assert_eq!(summary.product_names, vec!["Lamp".to_string()]);
+
+    system.product_client.delete(product_id).await.unwrap();
+    let result = system.order_client.order_summary(order_id).await;
+    assert!(matches!(
+        result,
+        Err(OrderError::DanglingReference { reference, .. }) if reference == "product_1"
+    ));
+    assert!(matches!(
+        system.order_client.order_summary(OrderId(999)).await,
+        Err(OrderError::NotFound(_))
+    ));
+
+    system.shutdown().await.unwrap();
+}
+
+/// Users are found by email through the index, and an email cannot be taken twice.
+#[tokio::test]
+async fn test_find_user_by_email_and_reject_taken_email() {
+    use actor_sample::model::UserUpdate;
+    use actor_sample::user_actor::UserError;
+
+    let system = OrderSystem::new_for_test();
+    let users = &system.user_client;
+    let alice = users
+        .create_user(UserCreate {
+            name: "Alice".to_string(),
+            email: "alice@example.com".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let found = users.find_by_email("Alice@Example.com").await.unwrap();
+    assert_eq!(found.map(|user| user.id), Some(alice.clone()));
+    assert!(users
+        .find_by_email("bob@example.com")
+        .await
+        .unwrap()
+        .is_none());
+
+    let duplicate = users
+        .create_user(UserCreate {
+            name: "Impostor".to_string(),
+            email: "alice@example.com".to_string(),
+        })
+        .await;
+    assert!(matches!(duplicate, Err(UserError::EmailTaken(_))));
+    // So is a copy of a stored user
+    let stored = users.get(alice.clone()).await.unwrap().unwrap();
+    assert!(matches!(
+        users.create_user_from(stored).await,
+        Err(UserError::EmailTaken(_))
+    ));
+
+    // Changing the email moves the index entry and frees the old address
+    users
+        .update_user(
+            alice.clone(),
+            UserUpdate {
+                name: None,
+                email: Some("alice@work.example".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+    assert!(users
+        .find_by_email("alice@example.com")
+        .await
+        .unwrap()
+        .is_none());
+    assert!(users
+        .find_by_email("alice@work.example")
+        .await
+        .unwrap()
+        .is_some());
+
+    users.delete(alice).await.unwrap();
+    assert!(users
+        .find_by_email("alice@work.example")
+        .await
+        .unwrap()
+        .is_none());
+
+    system.shutdown().await.unwrap();
+}
+
+/// `Replace` moves the email's index entry, and refuses an email another user holds.
+#[tokio::test]
+async fn test_replace_keeps_the_email_index_in_step() {
+    let system = OrderSystem::new_for_test();
+    let users = &system.user_client;
+    let alice = users
+        .create_user(UserCreate {
+            name: "Alice".to_string(),
+            email: "alice@example.com".to_string(),
+        })
+        .await
+        .unwrap();
+    users
+        .create_user(UserCreate {
+            name: "Bob".to_string(),
+            email: "bob@example.com".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let mut user = users.inner().get(alice.clone()).await.unwrap().unwrap();
+    user.email = "bob@example.com".to_string();
+    assert!(users
+        .inner()
+        .replace(alice.clone(), user.clone())
+        .await
+        .is_err());
+    assert_eq!(
+        users
+            .find_by_email("alice@example.com")
+            .await
+            .unwrap()
+            .map(|user| user.id),
+        Some(alice.clone())
+    );
+
+    user.email = "alice@work.example".to_string();
+    users.inner().replace(alice.clone(), user).await.unwrap();
+    assert!(users
+        .find_by_email("alice@example.com")
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        users
+            .find_by_email("alice@work.example")
+            .await
+            .unwrap()
+            .map(|user| user.id),
+        Some(alice)
+    );
+
+    system.shutdown().await.unwrap();
+}
+
+/// A restarted User actor starts with an empty store, so the emails of the lost users can be
+/// taken again.
+#[tokio::test]
+async fn test_restarted_user_actor_forgets_lost_emails() {
+    use actor_framework::FrameworkError;
+
+    let system = OrderSystem::builder().max_restarts(1).build();
+    let users = &system.user_client;
+    let alice = UserCreate {
+        name: "Alice".to_string(),
+        email: "alice@example.com".to_string(),
+    };
+    users.create_user(alice.clone()).await.unwrap();
+
+    assert!(matches!(
+        users
+            .inner()
+            .find_where(|_| panic!("predicate crashed"))
+            .await,
+        Err(FrameworkError::ActorDropped)
+    ));
+    assert!(users
+        .find_by_email("alice@example.com")
+        .await
+        .unwrap()
+        .is_none());
+    users.create_user(alice).await.unwrap();
+
+    system.shutdown().await.unwrap();
+}
+
+/// A client acting as a user may place orders for that user only.
+#[tokio::test]
+async fn test_acting_user_orders_only_for_themselves() {
+    use actor_sample::order_actor::OrderError;
+
+    let system = OrderSystem::new_for_test();
+    let mut users = Vec::new();
+    for name in ["alice", "bob"] {
+        let id = system
+            .user_client
+            .create_user(UserCreate {
+                name: name.to_string(),
+                email: format!("{name}@example.com"),
+            })
+            .await
+            .unwrap();
+        users.push(id);
+    }
+    let product_id = system
+        .product_client
+        .create_product(ProductCreate {
+            name: "Lamp".to_string(),
+            price: Money::from_dollars(30.0),
+            quantity: 10,
+        })
+        .await
+        .unwrap();
+    let order = OrderCreate {
+        user_id: users[0].clone(),
+        items: vec![OrderLine::new(product_id, 1, Money::from_dollars(30.0))],
+    };
+
+    let as_bob = system.order_client.acting_as(users[1].clone());
+    assert!(matches!(
+        as_bob.create_order(order.clone()).await,
+        Err(OrderError::Forbidden { .. })
+    ));
+    let as_alice = system.order_client.acting_as(users[0].clone());
+    as_alice.create_order(order).await.unwrap();
+
+    drop((as_bob, as_alice));
+    system.shutdown().await.unwrap();
+}
+
+/// A system shared behind an `Arc` serves handlers on many threads and is stopped by its
+/// shutdown handle while clones of it are still held.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_shared_system_shuts_down_with_clones_alive() {
+    let (system, shutdown) = OrderSystem::new_shared();
+
+    let handlers: Vec<_> = (0..8)
+        .map(|i| {
+            let system = system.clone();
+            tokio::spawn(async move {
+                system
+                    .user_client
+                    .create_user(UserCreate {
+                        name: format!("User {i}"),
+                        email: format!("user{i}@example.com"),
+                    })
+                    .await
+            })
+        })
+        .collect();
+    for handler in handlers {
+        handler.await.unwrap().unwrap();
+    }
+    assert_eq!(system.user_client.inner().count().await.unwrap(), 8);
+
+    let users = system.user_client.clone();
+    shutdown.shutdown().await.unwrap();
+    assert!(users.inner().is_closed());
+    assert!(system.order_client.inner().count().await.is_err());
+}
+
+/// Repeating an order derives a new order from the old one, reserving its stock again.
+#[tokio::test]
+async fn test_create_order_from_repeats_an_order() {
+    let system = OrderSystem::new_for_test();
+    let user_id = system
+        .user_client
+        .create_user(UserCreate {
+            name: "Alice".to_string(),
+            email: "alice@example.com".to_string(),
+        })
+        .await
+        .unwrap();
+    let product_id = system
+        .product_client
+        .create_product(ProductCreate {
+            name: "Lamp".to_string(),
+            price: Money::from_dollars(40.0),
+            quantity: 5,
+        })
+        .await
+        .unwrap();
+    let first_id = system
+        .order_client
+        .create_order(OrderCreate {
+            user_id,
+            items: vec![OrderLine::new(
+                product_id.clone(),
+                2,
+                Money::from_dollars(40.0),
+            )],
+        })
+        .await
+        .unwrap();
+
+    let first = system
+        .order_client
+        .get(first_id.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    let repeat_id = system
+        .order_client
+        .create_order_from(first.clone())
+        .await
+        .unwrap();
+    assert_ne!(repeat_id, first_id);
+    let repeat = system.order_client.get(repeat_id).await.unwrap().unwrap();
+    assert_eq!(repeat.items, first.items);
+    assert_eq!(repeat.total, first.total);
+    assert_eq!(
+        system.product_client.check_stock(product_id).await.unwrap(),
+        1
+    );
+
+    system.shutdown().await.unwrap();
+}
@@ -1,8 +1,21 @@
 use actor_framework::mock::MockClient;
-use actor_framework::ActorClient;
+use actor_framework::{ActorClient, FrameworkError, RequestKind};
 use actor_sample::clients::{OrderClient, ProductClient, UserClient};
-use actor_sample::model::{Order, OrderCreate, Product, ProductId, User, UserId};
-use actor_sample::product_actor::ProductActionResult;
+use actor_sample::model::{
+    Money, OrderCreate, OrderId, OrderLine, Product, ProductId, User, UserId,
+};
+use actor_sample::order_actor::OrderError;
+use actor_sample::order_actor::{RecentOrders, StockReserver};
+use actor_sample::product_actor::{ProductActionResult, ProductError};
+use actor_sample::user_actor::EmailIndex;
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// A Product client for tests that reserve stock through a fake and never read products.
+fn idle_product_client() -> ProductClient {
+    ProductClient::new(MockClient::<Product>::new().client())
+}
 
 /// Integration test: Real Order actor with mocked User and Product dependencies.
 /// This tests the Order actor's validation logic (on_create) while isolating it from User/Product actors.
@@ -17,7 +30,8 @@ async fn test_order_actor_with_mocked_dependencies() {
     let mut product_mock = MockClient::<Product>::new();
 
     // Define expectations for the dependencies
-    // Order::on_create will call user_client.get() and product_client.reserve_stock()
+    // Order::on_create will call user_client.get(), then OrderClient::create_order calls
+    // product_client.reserve_stock()
     user_mock
         .expect_get(UserId(1))
         .return_ok(Some(User::new("Alice", "alice@example.com")));
@@ -25,26 +39,30 @@ async fn test_order_actor_with_mocked_dependencies() {
     // reserve_stock() internally calls perform_action()
     product_mock
         .expect_action(ProductId(1))
-        .return_ok(ProductActionResult::ReserveStock(()));
+        .return_ok(ProductActionResult::ReserveStock(Money::from_dollars(20.0)));
 
     // Create clients from mocks
-    let user_client = UserClient::new(user_mock.client());
+    let user_client = UserClient::new(user_mock.client(), EmailIndex::default());
     let product_client = ProductClient::new(product_mock.client());
 
     // Create REAL Order actor using factory function (no dependencies)
     let (order_actor, order_generic_client) = actor_sample::order_actor::new();
-    let order_client = OrderClient::new(order_generic_client);
+    let order_client = OrderClient::new(
+        order_generic_client,
+        user_client.clone(),
+        product_client.clone(),
+        Arc::new(product_client.clone()),
+    );
 
     // Spawn the real actor with injected context
-    let actor_handle = tokio::spawn(order_actor.run((user_client.clone(), product_client.clone())));
+    let actor_handle =
+        tokio::spawn(order_actor.run((user_client.clone(), RecentOrders::disabled())));
 
     // Execute: This will run through the REAL Order actor
     // The validation happens in Order::on_create
     let order_params = OrderCreate {
         user_id: UserId(1),
-        product_id: ProductId(1),
-        quantity: 3,
-        total: 75.0,
+        items: vec![OrderLine::new(ProductId(1), 3, Money::from_dollars(25.0))],
     };
     let result = order_client.create_order(order_params).await;
 
@@ -57,14 +75,606 @@ async fn test_order_actor_with_mocked_dependencies() {
     assert!(retrieved_order.is_some());
     let order = retrieved_order.unwrap();
     assert_eq!(order.user_id, UserId(1));
-    assert_eq!(order.product_id, ProductId(1));
-    assert_eq!(order.quantity, 3);
+    // Priced at the Product's $20, not the $25 the caller asked for
+    assert_eq!(
+        order.items,
+        vec![OrderLine::new(ProductId(1), 3, Money::from_dollars(20.0))]
+    );
+    assert_eq!(order.total, Money::from_dollars(60.0));
 
-    // Verify mocks were called correctly (by Order::on_create)
+    // Verify mocks were called correctly (by Order::on_create and OrderClient::create_order)
     user_mock.verify();
     product_mock.verify();
+    assert_eq!(
+        user_mock.request_log(),
+        vec![(RequestKind::Get, "user_1".to_string())]
+    );
+    assert_eq!(
+        product_mock.request_log(),
+        vec![(RequestKind::Action, "product_1".to_string())]
+    );
 
     // Cleanup
     drop(order_client);
     actor_handle.await.unwrap();
 }
+
+/// If the order cannot be priced once its stock is reserved, `create_order` rolls it back:
+/// the stock is released and the order deleted.
+#[tokio::test]
+async fn test_order_rolled_back_when_pricing_fails() {
+    let mut user_mock = MockClient::<User>::new();
+    user_mock
+        .expect_get(UserId(1))
+        .return_ok(Some(User::new("Alice", "alice@example.com")));
+    let mut product_mock = MockClient::<Product>::new();
+    // A price whose line total overflows
+    product_mock
+        .expect_action(ProductId(1))
+        .return_ok(ProductActionResult::ReserveStock(Money::from_cents(
+            i64::MAX / 2,
+        )));
+    product_mock
+        .expect_action(ProductId(1))
+        .return_ok(ProductActionResult::ReleaseStock(3));
+    let user_client = UserClient::new(user_mock.client(), EmailIndex::default());
+    let product_client = ProductClient::new(product_mock.client());
+
+    let (order_actor, order_generic_client) = actor_sample::order_actor::new();
+    let order_client = OrderClient::new(
+        order_generic_client,
+        user_client.clone(),
+        product_client.clone(),
+        Arc::new(product_client.clone()),
+    );
+    let actor_handle = tokio::spawn(order_actor.run((user_client, RecentOrders::disabled())));
+
+    let result = order_client
+        .create_order(OrderCreate {
+            user_id: UserId(1),
+            items: vec![OrderLine::new(ProductId(1), 3, Money::from_dollars(25.0))],
+        })
+        .await;
+    assert!(
+        matches!(&result, Err(OrderError::ValidationError(e)) if e.message("total").is_some()),
+        "{result:?}"
+    );
+    assert!(order_client.inner().list().await.unwrap().is_empty());
+
+    user_mock.verify();
+    product_mock.verify();
+    drop(order_client);
+    actor_handle.await.unwrap();
+}
+
+/// Span names, each with its parent's name and `order_id`, in creation order.
+type SpanLog = Arc<Mutex<Vec<(String, Option<String>)>>>;
+
+/// Records the name of each new span with the name and `order_id` of its parent, if any.
+#[derive(Clone, Default)]
+struct SpanParents(SpanLog);
+
+impl<S> tracing_subscriber::Layer<S> for SpanParents
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    fn on_new_span(
+        &self,
+        attrs: &tracing::span::Attributes<'_>,
+        id: &tracing::span::Id,
+        ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        struct OrderId(Option<String>);
+        impl tracing::field::Visit for OrderId {
+            fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "order_id" {
+                    self.0 = Some(format!("{value:?}"));
+                }
+            }
+        }
+        let span = ctx.span(id).expect("span was just created");
+        let mut order_id = OrderId(None);
+        attrs.record(&mut order_id);
+        if let Some(order_id) = order_id.0 {
+            span.extensions_mut().insert(order_id);
+        }
+        let parent = span.parent().map(|parent| {
+            let order_id = parent.extensions().get::<String>().cloned();
+            format!("{}({})", parent.name(), order_id.unwrap_or_default())
+        });
+        self.0
+            .lock()
+            .unwrap()
+            .push((span.name().to_string(), parent));
+    }
+}
+
+/// The Product call made by `OrderClient::create_order` runs inside a span naming the order.
+#[tokio::test]
+async fn test_order_dependency_calls_are_traced_under_the_order() {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let spans = SpanParents::default();
+    let subscriber = tracing_subscriber::registry().with(spans.clone());
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let mut user_mock = MockClient::<User>::new();
+    user_mock
+        .expect_get(UserId(1))
+        .return_ok(Some(User::new("Alice", "alice@example.com")));
+    let mut product_mock = MockClient::<Product>::new();
+    product_mock
+        .expect_action(ProductId(1))
+        .return_ok(ProductActionResult::ReserveStock(Money::from_dollars(25.0)));
+    let user_client = UserClient::new(user_mock.client(), EmailIndex::default());
+    let product_client = ProductClient::new(product_mock.client());
+
+    let (order_actor, order_generic_client) = actor_sample::order_actor::new();
+    let order_client = OrderClient::new(
+        order_generic_client,
+        user_client.clone(),
+        product_client.clone(),
+        Arc::new(product_client.clone()),
+    );
+    tokio::spawn(order_actor.run((user_client, RecentOrders::disabled())));
+
+    let order_id = order_client
+        .create_order(OrderCreate {
+            user_id: UserId(1),
+            items: vec![OrderLine::new(ProductId(1), 3, Money::from_dollars(25.0))],
+        })
+        .await
+        .unwrap();
+
+    let spans = spans.0.lock().unwrap();
+    let parent_of = |name: &str| {
+        spans
+            .iter()
+            .find(|(span, _)| span == name)
+            .and_then(|(_, parent)| parent.clone())
+    };
+    assert_eq!(parent_of("order_on_create").as_deref(), Some("handle()"));
+    assert_eq!(
+        parent_of("reserve_stock"),
+        Some(format!("order_reserve_stock({order_id})"))
+    );
+}
+
+/// A stock shortfall reported by the Product actor reaches the caller as a typed
+/// `OrderError::OutOfStock` carrying the product and the available quantity, and the order is
+/// deleted again.
+#[tokio::test]
+async fn test_order_out_of_stock_surfaces_shortfall() {
+    let mut user_mock = MockClient::<User>::new();
+    let mut product_mock = MockClient::<Product>::new();
+
+    user_mock
+        .expect_get(UserId(1))
+        .return_ok(Some(User::new("Alice", "alice@example.com")));
+    product_mock
+        .expect_action(ProductId(7))
+        .return_err(FrameworkError::entity(
+            "Product",
+            ProductError::InsufficientStock {
+                product_id: ProductId(7),
+                requested: 5,
+                available: 2,
+            },
+        ));
+
+    let user_client = UserClient::new(user_mock.client(), EmailIndex::default());
+    let product_client = ProductClient::new(product_mock.client());
+    let (order_actor, order_generic_client) = actor_sample::order_actor::new();
+    let order_client = OrderClient::new(
+        order_generic_client,
+        user_client.clone(),
+        product_client.clone(),
+        Arc::new(product_client.clone()),
+    );
+    let actor_handle = tokio::spawn(order_actor.run((user_client, RecentOrders::disabled())));
+
+    let result = order_client
+        .create_order(OrderCreate {
+            user_id: UserId(1),
+            items: vec![OrderLine::new(ProductId(7), 5, Money::from_dollars(10.0))],
+        })
+        .await;
+
+    match result {
+        Err(OrderError::OutOfStock {
+            product_id,
+            requested,
+            available,
+        }) => {
+            assert_eq!(product_id, ProductId(7));
+            assert_eq!(requested, 5);
+            assert_eq!(available, 2);
+        }
+        other => panic!("Expected OutOfStock, got {:?}", other),
+    }
+    // The order created before the reservation failed is rolled back
+    assert!(order_client.inner().list().await.unwrap().is_empty());
+
+    user_mock.verify();
+    product_mock.verify();
+
+    drop(order_client);
+    actor_handle.await.unwrap();
+}
+
+/// Invalid order payloads are rejected before the User or Product actors are contacted.
+#[tokio::test]
+async fn test_order_rejects_invalid_lines() {
+    // No expectations: any call to a dependency would fail verification
+    let user_mock = MockClient::<User>::new();
+    let product_mock = MockClient::<Product>::new();
+
+    let user_client = UserClient::new(user_mock.client(), EmailIndex::default());
+    let product_client = ProductClient::new(product_mock.client());
+    let (order_actor, order_generic_client) = actor_sample::order_actor::new();
+    let order_client = OrderClient::new(
+        order_generic_client,
+        user_client.clone(),
+        product_client.clone(),
+        Arc::new(product_client.clone()),
+    );
+    let actor_handle = tokio::spawn(order_actor.run((user_client, RecentOrders::disabled())));
+
+    let result = order_client
+        .create_order(OrderCreate {
+            user_id: UserId(1),
+            items: vec![OrderLine::new(ProductId(1), 0, Money::from_dollars(0.0))],
+        })
+        .await;
+    assert!(
+        matches!(&result, Err(OrderError::ValidationError(e)) if e.message("quantity").is_some()),
+        "zero quantity must be rejected: {result:?}"
+    );
+
+    let result = order_client
+        .create_order(OrderCreate {
+            user_id: UserId(1),
+            items: vec![OrderLine::new(ProductId(1), 1, Money::from_cents(-1))],
+        })
+        .await;
+    assert!(
+        matches!(&result, Err(OrderError::ValidationError(e)) if e.message("unit_price").is_some()),
+        "negative unit price must be rejected: {result:?}"
+    );
+
+    let result = order_client
+        .create_order(OrderCreate {
+            user_id: UserId(1),
+            items: vec![],
+        })
+        .await;
+    assert!(
+        matches!(&result, Err(OrderError::ValidationError(e)) if e.message("items").is_some()),
+        "an order without lines must be rejected: {result:?}"
+    );
+
+    let result = order_client
+        .create_order(OrderCreate {
+            user_id: UserId(1),
+            items: vec![OrderLine::new(
+                ProductId(1),
+                u32::MAX,
+                Money::from_cents(i64::MAX / 2),
+            )],
+        })
+        .await;
+    assert!(
+        matches!(&result, Err(OrderError::ValidationError(e)) if e.message("total").is_some()),
+        "an overflowing total must be rejected: {result:?}"
+    );
+
+    user_mock.verify();
+    product_mock.verify();
+
+    drop(order_client);
+    actor_handle.await.unwrap();
+}
+
+/// The unit price `FakeStock` charges for every product.
+const FAKE_PRICE: Money = Money::from_cents(1000);
+
+/// A hand-written `StockReserver` that tracks stock in memory, one pool for every product.
+#[derive(Debug)]
+struct FakeStock {
+    available: AtomicU32,
+}
+
+#[async_trait]
+impl StockReserver for FakeStock {
+    async fn reserve(
+        &self,
+        _order_id: OrderId,
+        lines: Vec<(ProductId, u32)>,
+    ) -> Result<Vec<Money>, ProductError> {
+        let mut available = self.available.load(Ordering::SeqCst);
+        let unit_prices = vec![FAKE_PRICE; lines.len()];
+        // Nothing is taken unless every line fits
+        for (product_id, quantity) in lines {
+            if quantity > available {
+                return Err(ProductError::InsufficientStock {
+                    product_id,
+                    requested: quantity,
+                    available,
+                });
+            }
+            available -= quantity;
+        }
+        self.available.store(available, Ordering::SeqCst);
+        Ok(unit_prices)
+    }
+}
+
+/// The Order client depends on the `StockReserver` trait, so a plain fake can stand in for
+/// the Product actor without scripting mock expectations.
+#[tokio::test]
+async fn test_order_actor_with_fake_stock_reserver() {
+    let mut user_mock = MockClient::<User>::new();
+    // Once per order
+    for _ in 0..2 {
+        user_mock
+            .expect_get(UserId(1))
+            .return_ok(Some(User::new("Alice", "alice@example.com")));
+    }
+    let stock = Arc::new(FakeStock {
+        available: AtomicU32::new(5),
+    });
+
+    let (order_actor, order_generic_client) = actor_sample::order_actor::new();
+    let user_client = UserClient::new(user_mock.client(), EmailIndex::default());
+    let order_client = OrderClient::new(
+        order_generic_client,
+        user_client.clone(),
+        idle_product_client(),
+        stock.clone(),
+    );
+    let actor_handle = tokio::spawn(order_actor.run((user_client, RecentOrders::disabled())));
+
+    let order = |quantity| OrderCreate {
+        user_id: UserId(1),
+        items: vec![OrderLine::new(
+            ProductId(1),
+            quantity,
+            Money::from_dollars(10.0),
+        )],
+    };
+    order_client.create_order(order(3)).await.unwrap();
+    let result = order_client.create_order(order(3)).await;
+    assert!(matches!(
+        result,
+        Err(OrderError::OutOfStock { available: 2, .. })
+    ));
+    assert_eq!(stock.available.load(Ordering::SeqCst), 2);
+
+    user_mock.verify();
+    drop(order_client);
+    actor_handle.await.unwrap();
+}
+
+/// An order of several products reserves every line or none.
+#[tokio::test]
+async fn test_multi_line_order_reserves_all_lines_or_none() {
+    let mut user_mock = MockClient::<User>::new();
+    // Once per order
+    for _ in 0..2 {
+        user_mock
+            .expect_get(UserId(1))
+            .return_ok(Some(User::new("Alice", "alice@example.com")));
+    }
+    let stock = Arc::new(FakeStock {
+        available: AtomicU32::new(5),
+    });
+
+    let (order_actor, order_generic_client) = actor_sample::order_actor::new();
+    let user_client = UserClient::new(user_mock.client(), EmailIndex::default());
+    let order_client = OrderClient::new(
+        order_generic_client,
+        user_client.clone(),
+        idle_product_client(),
+        stock.clone(),
+    );
+    let actor_handle = tokio::spawn(order_actor.run((user_client, RecentOrders::disabled())));
+
+    let order = |second| OrderCreate {
+        user_id: UserId(1),
+        items: vec![
+            OrderLine::new(ProductId(1), 2, Money::from_dollars(10.0)),
+            OrderLine::new(ProductId(2), second, Money::from_dollars(2.5)),
+        ],
+    };
+    let id = order_client.create_order(order(2)).await.unwrap();
+    let created = order_client.get(id).await.unwrap().unwrap();
+    // Both lines at the fake's price
+    assert_eq!(created.total, FAKE_PRICE * 4);
+    assert_eq!(stock.available.load(Ordering::SeqCst), 1);
+
+    let result = order_client.create_order(order(4)).await;
+    assert!(matches!(
+        result,
+        Err(OrderError::OutOfStock {
+            product_id: ProductId(1),
+            available: 1,
+            ..
+        })
+    ));
+    assert_eq!(stock.available.load(Ordering::SeqCst), 1);
+
+    user_mock.verify();
+    drop(order_client);
+    actor_handle.await.unwrap();
+}
+
+/// With duplicate detection on, identical orders submitted concurrently resolve to one
+/// order, and stock is reserved only once.
+#[tokio::test]
+async fn test_order_actor_deduplicates_concurrent_orders() {
+    let mut user_mock = MockClient::<User>::new();
+    user_mock
+        .expect_get(UserId(1))
+        .return_ok(Some(User::new("Alice", "alice@example.com")));
+    let stock = Arc::new(FakeStock {
+        available: AtomicU32::new(10),
+    });
+
+    let (order_actor, order_generic_client) = actor_sample::order_actor::new();
+    let user_client = UserClient::new(user_mock.client(), EmailIndex::default());
+    let order_client = OrderClient::new(
+        order_generic_client,
+        user_client.clone(),
+        idle_product_client(),
+        stock.clone(),
+    );
+    let actor_handle = tokio::spawn(order_actor.run((
+        user_client,
+        RecentOrders::new(std::time::Duration::from_secs(60)),
+    )));
+
+    let order = || OrderCreate {
+        user_id: UserId(1),
+        items: vec![OrderLine::new(ProductId(1), 3, Money::from_dollars(10.0))],
+    };
+    let (first, second) = tokio::join!(
+        order_client.create_order(order()),
+        order_client.create_order(order())
+    );
+    assert_eq!(first.unwrap(), second.unwrap());
+    assert_eq!(stock.available.load(Ordering::SeqCst), 7);
+
+    user_mock.verify();
+    drop(order_client);
+    actor_handle.await.unwrap();
+}
+
+/// Deleting an order takes it out of duplicate detection: resubmitting it creates a new
+/// order and reserves its stock again.
+#[tokio::test]
+async fn test_resubmitting_a_deleted_order_creates_a_new_one() {
+    let mut user_mock = MockClient::<User>::new();
+    for _ in 0..2 {
+        user_mock
+            .expect_get(UserId(1))
+            .return_ok(Some(User::new("Alice", "alice@example.com")));
+    }
+    let stock = Arc::new(FakeStock {
+        available: AtomicU32::new(10),
+    });
+
+    let (order_actor, order_generic_client) = actor_sample::order_actor::new();
+    let user_client = UserClient::new(user_mock.client(), EmailIndex::default());
+    let order_client = OrderClient::new(
+        order_generic_client,
+        user_client.clone(),
+        idle_product_client(),
+        stock.clone(),
+    );
+    let actor_handle = tokio::spawn(order_actor.run((
+        user_client,
+        RecentOrders::new(std::time::Duration::from_secs(60)),
+    )));
+
+    let order = || OrderCreate {
+        user_id: UserId(1),
+        items: vec![OrderLine::new(ProductId(1), 3, Money::from_dollars(10.0))],
+    };
+    let first = order_client.create_order(order()).await.unwrap();
+    order_client.delete(first.clone()).await.unwrap();
+    let second = order_client.create_order(order()).await.unwrap();
+    assert_ne!(first, second);
+    assert!(order_client.get(second).await.unwrap().is_some());
+    assert_eq!(stock.available.load(Ordering::SeqCst), 4);
+
+    user_mock.verify();
+    drop(order_client);
+    actor_handle.await.unwrap();
+}
+
+/// Deleting an order keeps it as a tombstone: hidden from `get`, but still readable with
+/// `get_including_deleted`.
+#[tokio::test]
+async fn test_deleted_order_is_kept_as_tombstone() {
+    let mut user_mock = MockClient::<User>::new();
+    user_mock
+        .expect_get(UserId(1))
+        .return_ok(Some(User::new("Alice", "alice@example.com")));
+    let stock = Arc::new(FakeStock {
+        available: AtomicU32::new(5),
+    });
+
+    let (order_actor, order_generic_client) = actor_sample::order_actor::new();
+    let user_client = UserClient::new(user_mock.client(), EmailIndex::default());
+    let order_client = OrderClient::new(
+        order_generic_client,
+        user_client.clone(),
+        idle_product_client(),
+        stock,
+    );
+    let actor_handle = tokio::spawn(order_actor.run((user_client, RecentOrders::disabled())));
+
+    let id = order_client
+        .create_order(OrderCreate {
+            user_id: UserId(1),
+            items: vec![OrderLine::new(ProductId(1), 1, Money::from_dollars(10.0))],
+        })
+        .await
+        .unwrap();
+    order_client.delete(id.clone()).await.unwrap();
+
+    assert!(order_client.get(id.clone()).await.unwrap().is_none());
+    let tombstone = order_client
+        .inner()
+        .get_including_deleted(id.clone())
+        .await
+        .unwrap()
+        .expect("deleted order is kept");
+    assert!(tombstone.deleted_at.is_some());
+    // A tombstone cannot be deleted again
+    assert!(order_client.delete(id).await.is_err());
+
+    user_mock.verify();
+    drop(order_client);
+    actor_handle.await.unwrap();
+}
+
+/// A stopped Product actor surfaces as `DependencyUnavailable` naming the product service,
+/// not as an opaque communication error.
+#[tokio::test]
+async fn test_order_reports_stopped_product_service() {
+    let mut user_mock = MockClient::<User>::new();
+    user_mock
+        .expect_get(UserId(1))
+        .return_ok(Some(User::new("Alice", "alice@example.com")));
+    // Dropping the actor without running it closes its channel
+    let (product_actor, product_generic_client) =
+        actor_framework::ResourceActor::<Product>::new(10);
+    drop(product_actor);
+    let product_client = ProductClient::new(product_generic_client);
+
+    let (order_actor, order_generic_client) = actor_sample::order_actor::new();
+    let user_client = UserClient::new(user_mock.client(), EmailIndex::default());
+    let order_client = OrderClient::new(
+        order_generic_client,
+        user_client.clone(),
+        product_client.clone(),
+        Arc::new(product_client.clone()),
+    );
+    let actor_handle = tokio::spawn(order_actor.run((user_client, RecentOrders::disabled())));
+
+    let result = order_client
+        .create_order(OrderCreate {
+            user_id: UserId(1),
+            items: vec![OrderLine::new(ProductId(1), 1, Money::from_dollars(10.0))],
+        })
+        .await;
+    assert!(matches!(
+        result,
+        Err(OrderError::DependencyUnavailable { service: "product" })
+    ));
+
+    user_mock.verify();
+    drop(order_client);
+    actor_handle.await.unwrap();
+}